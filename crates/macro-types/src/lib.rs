@@ -1,7 +1,10 @@
 //! An internal library of types shared between our macros and the nvi library.
 
+use serde_derive::{Deserialize, Serialize};
+
 /// The type of method being generated
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum MethodType {
     /// A request method
     Request,
@@ -14,16 +17,18 @@ pub enum MethodType {
 }
 
 /// An argument to a method
-#[derive(Default, Debug, Clone, PartialEq, Eq)]
+#[derive(Default, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Arg {
     /// The name of the argument
     pub name: String,
     /// The type of the argument
+    #[serde(rename = "type")]
     pub typ: String,
 }
 
 /// The return type of a method
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "type", rename_all = "snake_case")]
 pub enum Return {
     /// A void return
     Void,
@@ -36,7 +41,7 @@ pub enum Return {
 }
 
 /// An autocommand definition
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct AutoCmd {
     /// The events to listen for
     pub events: Vec<String>,
@@ -46,10 +51,13 @@ pub struct AutoCmd {
     pub group: Option<String>,
     /// Whether the autocommand is nested
     pub nested: bool,
+    /// The documentation for the autocommand. If not specified explicitly, this is the
+    /// documentation of the method it invokes.
+    pub docs: String,
 }
 
 /// A method definition
-#[derive(Debug, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub struct Method {
     /// The name of the method
     pub name: String,
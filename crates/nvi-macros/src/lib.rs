@@ -1,10 +1,15 @@
 //! Macros for the nvi library.
-use std::{result::Result as StdResult, vec};
+use std::{env, fs, path::Path, result::Result as StdResult, vec};
 
 use macro_types::*;
 use proc_macro2_diagnostics::SpanDiagnosticExt;
 use quote::{ToTokens, quote};
-use syn::{Expr, ExprLit, Lit, Meta, Token, punctuated::Punctuated, spanned::Spanned};
+use syn::{
+    Expr, ExprLit, Lit, Meta, Token,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+};
 
 /// Result type for macro operations
 type Result<T> = StdResult<T, syn::Error>;
@@ -26,6 +31,8 @@ const RPC_AUTOCMD_PATTERNS: &str = "patterns";
 const RPC_AUTOCMD_GROUP: &str = "group";
 /// The name of the nested argument for autocmd
 const RPC_AUTOCMD_NESTED: &str = "nested";
+/// The name of the docs argument for autocmd
+const RPC_AUTOCMD_DOCS: &str = "docs";
 
 #[derive(Debug, Eq, PartialEq)]
 /// A parsed implementation block
@@ -90,7 +97,7 @@ fn request_invocation(m: &Method) -> proc_macro2::TokenStream {
     for (idx, _) in m.args.iter().enumerate() {
         let a = quote! {
             nvi::serde_rmpv::from_value(&params[#idx])
-                .map_err(|e| nvi::Value::from(format!("{e}")))?
+                .map_err(|e| nvi::error::PluginError::value(&plugin_name, method, e))?
         };
         args.push(a);
     }
@@ -106,7 +113,7 @@ fn request_invocation(m: &Method) -> proc_macro2::TokenStream {
         }
         Return::ResultVoid => {
             quote! {
-                    self.#method(client, #(#args),*).await.map_err(|e| nvi::Value::from(format!("{e}")))?;
+                    self.#method(client, #(#args),*).await.map_err(|e| nvi::error::PluginError::value(&plugin_name, method, e))?;
                     nvi::Value::Nil
             }
         }
@@ -114,15 +121,15 @@ fn request_invocation(m: &Method) -> proc_macro2::TokenStream {
             quote! {
                     nvi::serde_rmpv::to_value(
                         &self.#method(client, #(#args),*).await
-                            .map_err(|e| nvi::Value::from(format!("{e}")))?
-                    ).map_err(|e| nvi::Value::from(format!("{e}")))?
+                            .map_err(|e| nvi::error::PluginError::value(&plugin_name, method, e))?
+                    ).map_err(|e| nvi::error::PluginError::value(&plugin_name, method, e))?
             }
         }
         Return::Type(_) => {
             quote! {
                     nvi::serde_rmpv::to_value(
                         &self.#method(client, #(#args),*).await
-                    ).map_err(|e| nvi::Value::from(format!("{e}")))?
+                    ).map_err(|e| nvi::error::PluginError::value(&plugin_name, method, e))?
             }
         }
     };
@@ -130,7 +137,14 @@ fn request_invocation(m: &Method) -> proc_macro2::TokenStream {
     quote! {
         #name => {
             if params.len() != #arg_len {
-                nvi::error::Result::Err(nvi::Value::from("invalid number of arguments"))?
+                nvi::error::Result::Err(nvi::error::PluginError::value(
+                    &plugin_name,
+                    method,
+                    nvi::error::Error::Decode {
+                        msg: format!("expected {} arguments, got {}", #arg_len, params.len()),
+                        source: None,
+                    },
+                ))?
             }
             #inv
         }
@@ -143,6 +157,7 @@ fn parse_autocmd_options(
     patterns: &mut Vec<String>,
     group: &mut Option<String>,
     nested: &mut bool,
+    docs: &mut String,
 ) -> Result<()> {
     if let Expr::Path(path) = &*assign.left {
         let ident = path.path.get_ident().unwrap().to_string();
@@ -176,6 +191,14 @@ fn parse_autocmd_options(
                     *nested = lit.value();
                 }
             }
+            RPC_AUTOCMD_DOCS => {
+                if let Expr::Lit(ExprLit {
+                    lit: Lit::Str(lit), ..
+                }) = &*assign.right
+                {
+                    *docs = lit.value().trim().to_string();
+                }
+            }
             _ => return Err(syn::Error::new(assign.span(), "invalid autocmd attribute")),
         }
     }
@@ -188,6 +211,7 @@ fn parse_autocmd(a: &syn::Attribute) -> Result<Option<AutoCmd>> {
         let mut patterns = vec![];
         let mut group = None;
         let mut nested = false;
+        let mut docs = String::new();
         let mut events = vec![];
 
         let nested_metas = list.parse_args_with(Punctuated::<Expr, Token![,]>::parse_terminated)?;
@@ -225,7 +249,7 @@ fn parse_autocmd(a: &syn::Attribute) -> Result<Option<AutoCmd>> {
         // Parse optional named arguments
         for meta in iter {
             if let Expr::Assign(assign) = meta {
-                parse_autocmd_options(assign, &mut patterns, &mut group, &mut nested)?;
+                parse_autocmd_options(assign, &mut patterns, &mut group, &mut nested, &mut docs)?;
             }
         }
 
@@ -234,6 +258,7 @@ fn parse_autocmd(a: &syn::Attribute) -> Result<Option<AutoCmd>> {
             patterns,
             group,
             nested,
+            docs,
         }))
     } else {
         Ok(None)
@@ -344,8 +369,9 @@ fn parse_method(method: &syn::ImplItemFn) -> Result<Option<Method>> {
             syn::Type::Path(p) => {
                 if p.path.segments.last().unwrap().ident == "Result" {
                     match &p.path.segments.last().unwrap().arguments {
+                        // Result<T>, or Result<T, E> where E converts into nvi::error::Error
                         syn::PathArguments::AngleBracketed(a) => {
-                            if a.args.len() != 1 {
+                            if !(1..=2).contains(&a.args.len()) {
                                 return Err(syn::Error::new(method.span(), "invalid rpc method"));
                             } else {
                                 match a.args.first().unwrap() {
@@ -438,12 +464,19 @@ fn parse_method(method: &syn::ImplItemFn) -> Result<Option<Method>> {
         _ => {}
     }
 
+    let docs = docs.join("\n");
+    if let Some(a) = &mut autocmd
+        && a.docs.is_empty()
+    {
+        a.docs = docs.clone();
+    }
+
     Ok(Some(Method {
         name,
         method_type,
         ret,
         args,
-        docs: docs.join("\n"),
+        docs,
         autocmd,
         is_mut,
     }))
@@ -517,12 +550,14 @@ fn generate_methods(imp: &ImplBlock) -> impl Iterator<Item = proc_macro2::TokenS
                 None => quote! { None },
             };
             let nested = a.nested;
+            let docs = &a.docs;
             quote! {
                 Some(nvi::macro_types::AutoCmd {
                     events: vec![#(#events.to_string()),*],
                     patterns: vec![#(#patterns.to_string()),*],
                     group: #group,
                     nested: #nested,
+                    docs: #docs.to_string(),
                 })
             }
         } else {
@@ -670,6 +705,10 @@ fn inner_nvi_plugin(
                 #namestr.into()
             }
 
+            fn version(&self) -> String {
+                env!("CARGO_PKG_VERSION").into()
+            }
+
             fn highlights(&self) -> nvi::error::Result<nvi::highlights::Highlights> {
                 #highlights
             }
@@ -685,11 +724,16 @@ fn inner_nvi_plugin(
                 method: &str,
                 params: &[nvi::Value],
             ) -> nvi::error::Result<nvi::Value, nvi::Value> {
+                let plugin_name = self.name();
                 Ok(
                     match method {
                         #(#request_invocations),*
                         _ => {
-                            nvi::error::Result::Err(nvi::Value::from(format!("Unknown method: {method}")))?
+                            nvi::error::Result::Err(nvi::error::PluginError::value(
+                                &plugin_name,
+                                method,
+                                nvi::error::Error::Internal { msg: format!("Unknown method: {method}") },
+                            ))?
                         }
                     }
                 )
@@ -701,11 +745,16 @@ fn inner_nvi_plugin(
                 method: &str,
                 params: &[nvi::Value],
             ) -> nvi::error::Result<nvi::Value, nvi::Value> {
+                let plugin_name = self.name();
                 Ok(
                     match method {
                         #(#request_invocations_mut),*
                         _ => {
-                            nvi::error::Result::Err(nvi::Value::from(format!("Unknown method: {method}")))?
+                            nvi::error::Result::Err(nvi::error::PluginError::value(
+                                &plugin_name,
+                                method,
+                                nvi::error::Error::Internal { msg: format!("Unknown method: {method}") },
+                            ))?
                         }
                     }
                 )
@@ -787,7 +836,8 @@ pub fn notify(
     input
 }
 
-/// Mark a method as an AutoCommand.
+/// Mark a method as an AutoCommand. The autocommand is documented with the method's doc comment,
+/// unless a `docs="..."` argument is given.
 #[proc_macro_attribute]
 pub fn autocmd(
     _attr: proc_macro::TokenStream,
@@ -796,6 +846,263 @@ pub fn autocmd(
     input
 }
 
+/// Derive the error codes for an error enum.
+fn inner_error_code(input: &proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    let item: syn::DeriveInput = syn::parse2(input.clone())?;
+    let syn::Data::Enum(data) = &item.data else {
+        return Err(syn::Error::new(
+            item.span(),
+            "ErrorCode can only be derived for enums",
+        ));
+    };
+    let name = &item.ident;
+    let (impl_generics, ty_generics, where_clause) = item.generics.split_for_impl();
+
+    let mut arms = vec![];
+    let mut data_arms = vec![];
+    for v in &data.variants {
+        let mut code = heck::ToSnakeCase::to_snake_case(v.ident.to_string().as_str());
+        for attr in v.attrs.iter().filter(|a| a.path().is_ident("nvi")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("code") {
+                    code = meta.value()?.parse::<syn::LitStr>()?.value();
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `code = \"...\"`"))
+                }
+            })?;
+        }
+        let id = &v.ident;
+        arms.push(quote! { Self::#id { .. } => #code });
+
+        let mut data_fields = vec![];
+        for (i, f) in v.fields.iter().enumerate() {
+            for attr in f.attrs.iter().filter(|a| a.path().is_ident("nvi")) {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("data") {
+                        Ok(())
+                    } else {
+                        Err(meta.error("expected `data`"))
+                    }
+                })?;
+                let member = match &f.ident {
+                    Some(ident) => syn::Member::Named(ident.clone()),
+                    None => syn::Member::Unnamed(i.into()),
+                };
+                data_fields.push((member, f.span()));
+            }
+        }
+        match data_fields.as_slice() {
+            [] => (),
+            [(member, _)] => data_arms.push(quote! {
+                Self::#id { #member: data, .. } => nvi::serde_json::to_value(data).ok()
+            }),
+            [_, (_, span), ..] => {
+                return Err(syn::Error::new(
+                    *span,
+                    "only one field of a variant can be #[nvi(data)]",
+                ));
+            }
+        }
+    }
+
+    Ok(quote! {
+        impl #impl_generics nvi::error::ErrorCode for #name #ty_generics #where_clause {
+            fn code(&self) -> &'static str {
+                match self {
+                    #(#arms),*
+                }
+            }
+
+            #[allow(unreachable_patterns)]
+            fn data(&self) -> Option<nvi::serde_json::Value> {
+                match self {
+                    #(#data_arms,)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl #impl_generics From<#name #ty_generics> for nvi::error::Error #where_clause {
+            fn from(e: #name #ty_generics) -> Self {
+                nvi::error::Error::Plugin {
+                    kind: nvi::error::ErrorCode::code(&e).into(),
+                    message: e.to_string(),
+                    data: nvi::error::ErrorCode::data(&e),
+                }
+            }
+        }
+    })
+}
+
+/// Derive `nvi::error::ErrorCode` for an error enum, along with a conversion into
+/// `nvi::error::Error`, so the enum can be returned from plugin methods. Lua callers see the
+/// variant name in snake_case as the error's `kind`, unless overridden with
+/// `#[nvi(code = "...")]`. One field of a variant can be marked `#[nvi(data)]`, and is serialized
+/// to JSON as the error's `data`. The enum must implement `Display`.
+#[proc_macro_derive(ErrorCode, attributes(nvi))]
+pub fn error_code(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match inner_error_code(&input.into()) {
+        Ok(x) => x.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
+/// The arguments to `plugin_client!`: the name of the client type, and the path to a manifest.
+struct ClientArgs {
+    /// The name of the generated client type
+    name: syn::Ident,
+    /// The path to the manifest, relative to the crate root
+    path: syn::LitStr,
+}
+
+impl Parse for ClientArgs {
+    fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+        input.parse::<Option<Token![,]>>()?;
+        Ok(Self { name, path })
+    }
+}
+
+/// Parse a type name recorded in a manifest.
+fn manifest_type(typ: &str, span: proc_macro2::Span) -> Result<syn::Type> {
+    syn::parse_str(typ)
+        .map_err(|e| syn::Error::new(span, format!("invalid type {typ:?} in manifest: {e}")))
+}
+
+/// Generate a typed client from the JSON manifest of a plugin.
+fn client_from_manifest(
+    name: &syn::Ident,
+    manifest: &str,
+    span: proc_macro2::Span,
+) -> Result<proc_macro2::TokenStream> {
+    let manifest: serde_json::Value = serde_json::from_str(manifest)
+        .map_err(|e| syn::Error::new(span, format!("invalid manifest: {e}")))?;
+    let plugin = manifest["name"]
+        .as_str()
+        .ok_or_else(|| syn::Error::new(span, "manifest has no plugin name"))?;
+    let methods: Vec<Method> = serde_json::from_value(manifest["methods"].clone())
+        .map_err(|e| syn::Error::new(span, format!("invalid methods in manifest: {e}")))?;
+
+    let mut calls = vec![];
+    for m in &methods {
+        if !matches!(m.method_type, MethodType::Request | MethodType::Notify) {
+            continue;
+        }
+        let method = syn::Ident::new(&m.name, span);
+        let method_name = &m.name;
+        let docs = &m.docs;
+        let arg_names: Vec<syn::Ident> = m
+            .args
+            .iter()
+            .map(|a| syn::Ident::new(&a.name, span))
+            .collect();
+        let arg_types = m
+            .args
+            .iter()
+            .map(|a| manifest_type(&a.typ, span))
+            .collect::<Result<Vec<_>>>()?;
+        let ret = match &m.ret {
+            Return::Void | Return::ResultVoid => quote! { () },
+            Return::Result(t) | Return::Type(t) => manifest_type(t, span)?.to_token_stream(),
+        };
+        calls.push(quote! {
+            #[doc = #docs]
+            pub async fn #method(&self, #(#arg_names: #arg_types),*) -> nvi::error::Result<#ret> {
+                self.client
+                    .call_plugin(
+                        Self::PLUGIN,
+                        #method_name,
+                        vec![#(nvi::serde_rmpv::to_value(&#arg_names)?),*],
+                    )
+                    .await
+            }
+        });
+    }
+
+    let docs = format!("A typed client for the `{plugin}` plugin, generated from its manifest.");
+    Ok(quote! {
+        #[doc = #docs]
+        pub struct #name<'a> {
+            client: &'a nvi::Client,
+        }
+
+        impl<'a> #name<'a> {
+            /// The name of the plugin.
+            pub const PLUGIN: &'static str = #plugin;
+
+            /// Make a client for the plugin, checking through its status request that it is
+            /// running.
+            pub async fn new(client: &'a nvi::Client) -> nvi::error::Result<Self> {
+                match client.plugin_status(Self::PLUGIN).await? {
+                    Some(report) if report.status == nvi::Status::Running => Ok(Self { client }),
+                    Some(report) => Err(nvi::error::Error::User(format!(
+                        "plugin {} is {}", Self::PLUGIN, report.status
+                    ))),
+                    None => Err(nvi::error::Error::User(format!(
+                        "plugin {} is not loaded", Self::PLUGIN
+                    ))),
+                }
+            }
+
+            /// Make a client for the plugin, waiting up to `timeout` for it to be running.
+            pub async fn wait(
+                client: &'a nvi::Client,
+                timeout: std::time::Duration,
+            ) -> nvi::error::Result<Self> {
+                client.await_plugin(Self::PLUGIN, timeout).await?;
+                Ok(Self { client })
+            }
+
+            #(#calls)*
+        }
+    })
+}
+
+/// Generate a typed client for a plugin.
+fn inner_plugin_client(input: proc_macro2::TokenStream) -> Result<proc_macro2::TokenStream> {
+    let args: ClientArgs = syn::parse2(input)?;
+    let root = env::var("CARGO_MANIFEST_DIR").unwrap_or_default();
+    let path = Path::new(&root).join(args.path.value());
+    let manifest = fs::read_to_string(&path).map_err(|e| {
+        syn::Error::new(
+            args.path.span(),
+            format!("could not read {}: {e}", path.display()),
+        )
+    })?;
+    let client = client_from_manifest(&args.name, &manifest, args.path.span())?;
+    // Rebuild when the manifest changes.
+    let path = path.to_string_lossy();
+    Ok(quote! {
+        const _: &str = include_str!(#path);
+        #client
+    })
+}
+
+/// Generate a typed client for another plugin from its JSON manifest, as written by the
+/// plugin's `inspect --json` command or `nvi::manifest::Manifest::to_json`. The path is relative
+/// to the crate root.
+///
+/// ```ignore
+/// nvi_macros::plugin_client!(GreeterClient, "manifests/greeter.json");
+///
+/// let greeter = GreeterClient::new(&client).await?;
+/// let greeting: String = greeter.greet("world".into()).await?;
+/// ```
+///
+/// Each request and notification becomes an async method that calls the plugin's function
+/// through Neovim. Argument and return types are taken from the manifest as written in the
+/// plugin's source, so any custom types must be in scope where the macro is used.
+#[proc_macro]
+pub fn plugin_client(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    match inner_plugin_client(input.into()) {
+        Ok(x) => x.into(),
+        Err(e) => e.into_compile_error().into(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
@@ -1168,6 +1475,7 @@ mod tests {
                 patterns: vec!["*.rs".into()],
                 group: Some("test".into()),
                 nested: true,
+                docs: "".into(),
             })
         );
     }
@@ -1194,6 +1502,7 @@ mod tests {
                 patterns: vec![],
                 group: None,
                 nested: false,
+                docs: "".into(),
             })
         );
     }
@@ -1220,6 +1529,7 @@ mod tests {
                 patterns: vec!["*.rs".into()],
                 group: None,
                 nested: false,
+                docs: "".into(),
             })
         );
     }
@@ -1246,10 +1556,38 @@ mod tests {
                 patterns: vec![],
                 group: Some("test".into()),
                 nested: false,
+                docs: "".into(),
             })
         );
     }
 
+    #[test]
+    fn it_parses_autocmd_docs() {
+        let s = quote! {
+            impl Test {
+                /// Method docs
+                #[autocmd(["BufEnter"])]
+                async fn from_method(&self, client: &mut nvi::Client) -> nvi::error::Result<()> {
+                    Ok(())
+                }
+
+                /// Method docs
+                #[autocmd(["BufEnter"], docs="Autocmd docs")]
+                async fn explicit(&self, client: &mut nvi::Client) -> nvi::error::Result<()> {
+                    Ok(())
+                }
+            }
+        };
+
+        let (_, ret) = parse_impl(&s).unwrap();
+        assert_eq!(ret.methods[0].autocmd.as_ref().unwrap().docs, "Method docs");
+        assert_eq!(
+            ret.methods[1].autocmd.as_ref().unwrap().docs,
+            "Autocmd docs"
+        );
+        assert_eq!(ret.methods[1].docs, "Method docs");
+    }
+
     #[test]
     fn it_parses_autocmd_with_nested() {
         let s = quote! {
@@ -1272,6 +1610,7 @@ mod tests {
                 patterns: vec![],
                 group: None,
                 nested: true,
+                docs: "".into(),
             })
         );
     }
@@ -1299,4 +1638,76 @@ mod tests {
         );
         assert_eq!(ret.methods[1].is_mut, true, "mut_method should be mut");
     }
+
+    #[test]
+    fn it_derives_error_codes() {
+        let s = quote! {
+            enum MyError {
+                NotFound(String),
+                #[nvi(code = "denied")]
+                PermissionDenied { #[nvi(data)] user: String },
+                Other,
+            }
+        };
+        let result = inner_error_code(&s).unwrap().to_string();
+        assert!(result.contains(r#"Self :: NotFound { .. } => "not_found""#));
+        assert!(result.contains(r#"Self :: PermissionDenied { .. } => "denied""#));
+        assert!(result.contains(r#"Self :: Other { .. } => "other""#));
+        assert!(result.contains("Self :: PermissionDenied { user : data , .. } =>"));
+        assert!(!result.contains("Self :: NotFound { 0 : data"));
+
+        let s = quote! {
+            enum MyError {
+                Both(#[nvi(data)] String, #[nvi(data)] String),
+            }
+        };
+        assert!(inner_error_code(&s).is_err());
+
+        let s = quote! {
+            struct NotAnEnum;
+        };
+        assert!(inner_error_code(&s).is_err());
+    }
+
+    #[test]
+    fn it_generates_plugin_clients() {
+        let manifest = r#"{
+            "name": "greeter",
+            "methods": [
+                {
+                    "name": "greet",
+                    "docs": "Say hello.",
+                    "ret": { "kind": "result", "type": "String" },
+                    "method_type": "request",
+                    "args": [{ "name": "name", "type": "String" }],
+                    "autocmd": null,
+                    "is_mut": false
+                },
+                {
+                    "name": "poke",
+                    "docs": "",
+                    "ret": { "kind": "result_void" },
+                    "method_type": "notify",
+                    "args": [],
+                    "autocmd": null,
+                    "is_mut": true
+                }
+            ]
+        }"#;
+        let name = syn::Ident::new("GreeterClient", proc_macro2::Span::call_site());
+        let span = proc_macro2::Span::call_site();
+        let result = client_from_manifest(&name, manifest, span)
+            .unwrap()
+            .to_string();
+        assert!(result.contains("pub struct GreeterClient < 'a >"));
+        assert!(result.contains(r#"const PLUGIN : & 'static str = "greeter""#));
+        assert!(result.contains(
+            "pub async fn greet (& self , name : String) -> nvi :: error :: Result < String >"
+        ));
+        assert!(result.contains("pub async fn poke (& self ,) -> nvi :: error :: Result < () >"));
+
+        assert!(client_from_manifest(&name, "{}", span).is_err());
+        let bad = manifest.replace(r#""type": "String" }]"#, r#""type": "Vec<" }]"#);
+        assert!(client_from_manifest(&name, &bad, span).is_err());
+    }
 }
@@ -0,0 +1,102 @@
+//! Shared-secret authentication for listening plugins.
+//!
+//! A plugin listening on TCP can be reached by anything on the machine, and through it can drive
+//! the editor. With a [`Token`], the connecting side must present the token as its first RPC
+//! call, before the plugin is bootstrapped:
+//!
+//! ```lua
+//! local chan = vim.fn.sockconnect("tcp", "127.0.0.1:9999", { rpc = true })
+//! vim.rpcrequest(chan, "__nvi_auth", os.getenv("NVI_TOKEN"))
+//! ```
+//!
+//! Any other call before the token, a wrong token, or no token within the listener's timeout
+//! (usually [`DEFAULT_TIMEOUT`]), closes the connection with a logged warning.
+
+use std::{env, fmt, fs, path::Path, sync::Arc, time::Duration};
+
+use crate::error::{Error, Result};
+
+/// The request a connecting client uses to present its token.
+pub const AUTH_MESSAGE: &str = "__nvi_auth";
+
+/// The environment variable [`Token::load`] reads the token from.
+pub const ENV_VAR: &str = "NVI_TOKEN";
+
+/// A reasonable time for a connection to authenticate before it is closed.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// A shared secret that connecting clients must present.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Token(Arc<str>);
+
+impl Token {
+    /// Create a token from a string. Surrounding whitespace is trimmed, and empty tokens are
+    /// rejected.
+    pub fn new(token: &str) -> Result<Self> {
+        let token = token.trim();
+        if token.is_empty() {
+            return Err(Error::User("authentication token is empty".into()));
+        }
+        Ok(Self(token.into()))
+    }
+
+    /// Read the token from the environment variable `var`.
+    pub fn from_env(var: &str) -> Result<Self> {
+        let token =
+            env::var(var).map_err(|_| Error::User(format!("{var} is not set to a token")))?;
+        Self::new(&token)
+    }
+
+    /// Read the token from a file, e.g. one only readable by the user.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref();
+        let token = fs::read_to_string(path).map_err(|e| Error::IO {
+            msg: format!("reading token from {}", path.display()),
+            source: Some(e.into()),
+        })?;
+        Self::new(&token)
+    }
+
+    /// Read the token from [`ENV_VAR`].
+    pub fn load() -> Result<Self> {
+        Self::from_env(ENV_VAR)
+    }
+
+    /// Check a token presented by a client. The comparison takes the same time wherever the
+    /// first difference is, so it doesn't leak the token.
+    pub fn verify(&self, candidate: &str) -> bool {
+        let (a, b) = (self.0.as_bytes(), candidate.as_bytes());
+        a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+    }
+}
+
+// Never print the secret itself.
+impl fmt::Debug for Token {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Token(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token() {
+        let t = Token::new(" secret\n").unwrap();
+        assert!(t.verify("secret"));
+        assert!(!t.verify("secreT"));
+        assert!(!t.verify("secret2"));
+        assert!(!t.verify(""));
+        assert_eq!(format!("{t:?}"), "Token(<redacted>)");
+
+        assert!(Token::new("  ").is_err());
+        assert!(Token::from_env("NVI_TEST_TOKEN_UNSET").is_err());
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("token");
+        assert!(Token::from_file(&path).is_err());
+        fs::write(&path, "secret\n").unwrap();
+        assert_eq!(Token::from_file(&path).unwrap(), t);
+    }
+}
@@ -1,14 +1,115 @@
 //! A Neovim client. This is the primary interface for interacting with Neovim from a service.
-use std::{path::PathBuf, time::Duration};
+use std::{
+    collections::BTreeMap,
+    fmt,
+    future::Future,
+    path::PathBuf,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
+};
 
+use futures::future::join_all;
+use serde::{Serialize, de::DeserializeOwned};
 use tokio::sync::broadcast;
-use tracing::trace;
+use tracing::{trace, warn};
 
 use crate::{
-    error::{Error, Result},
+    Value,
+    error::{Error, PLUGIN_ERROR_MARKER, PluginError, RemoteError, RemoteErrorKind, Result},
+    events::{Subscription, Subscriptions},
     highlights, lua, lua_exec, nvim, service,
 };
 
+/// The source of connection IDs, shared by all connections in the process.
+static NEXT_CONNECTION_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Identifies one connection between a plugin and a Neovim instance. IDs are unique within the
+/// plugin process, and are never reused.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ConnectionId(pub u64);
+
+impl ConnectionId {
+    /// Allocate a new, unique connection ID.
+    pub(crate) fn next() -> Self {
+        Self(NEXT_CONNECTION_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+impl fmt::Display for ConnectionId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The clients for every Neovim instance connected to a plugin, by connection. With the
+/// shared-state listeners (`connect::listen_unix_shared`, `connect::listen_tcp_shared`) this
+/// spans all connections to the plugin; otherwise it holds only the current connection.
+#[derive(Clone, Default)]
+pub struct Peers(Arc<Mutex<BTreeMap<ConnectionId, Client>>>);
+
+impl Peers {
+    /// The IDs of all connected editors.
+    pub fn ids(&self) -> Vec<ConnectionId> {
+        self.0.lock().unwrap().keys().copied().collect()
+    }
+
+    /// The client for a connection, if it is still connected.
+    pub fn get(&self, id: ConnectionId) -> Option<Client> {
+        self.0.lock().unwrap().get(&id).cloned()
+    }
+
+    /// The clients for all connected editors.
+    pub fn clients(&self) -> Vec<Client> {
+        self.0.lock().unwrap().values().cloned().collect()
+    }
+
+    /// The number of connected editors.
+    pub fn len(&self) -> usize {
+        self.0.lock().unwrap().len()
+    }
+
+    /// Whether there are no connected editors.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Add a connected client.
+    pub(crate) fn insert(&self, client: Client) {
+        self.0.lock().unwrap().insert(client.connection_id, client);
+    }
+
+    /// Remove a connection that has closed.
+    pub(crate) fn remove(&self, id: ConnectionId) {
+        self.0.lock().unwrap().remove(&id);
+    }
+
+    /// A handle to the peers that doesn't keep them alive, for the clients stored in them.
+    pub(crate) fn downgrade(&self) -> WeakPeers {
+        WeakPeers(Arc::downgrade(&self.0))
+    }
+}
+
+// Clients refer to their peers, so only the IDs are shown to avoid recursing.
+impl fmt::Debug for Peers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Peers").field(&self.ids()).finish()
+    }
+}
+
+/// A reference from a client to its peers. It is weak, because the peers hold the clients.
+#[derive(Clone, Debug, Default)]
+pub struct WeakPeers(Weak<Mutex<BTreeMap<ConnectionId, Client>>>);
+
+impl WeakPeers {
+    /// The peers, or none if every connection sharing them has gone.
+    fn upgrade(&self) -> Peers {
+        self.0.upgrade().map(Peers).unwrap_or_default()
+    }
+}
+
 /// A client to Neovim. A `Client` object is passed to every method invocation in a `NviService`.
 /// It exposes the full auto-generated API for Neovim on its `nvim` field, and provides a set of
 /// higher-level methods directly on the `Client` object.
@@ -20,6 +121,15 @@ pub struct Client {
     pub nvim: nvim::NvimApi,
     /// The MessagePack-RPC channel ID for this client. Channel ID 0 is global.
     pub channel_id: u64,
+    /// The connection this client belongs to.
+    pub connection_id: ConnectionId,
+    /// The clients for every editor connected to this plugin.
+    pub(crate) peers: WeakPeers,
+    /// Prepended to the RPC method names of registered functions, so that a
+    /// [`PluginHost`](crate::host::PluginHost) can route calls to its plugins.
+    pub(crate) method_prefix: String,
+    /// The subscribers to events sent by Lua on this connection.
+    pub(crate) subscriptions: Subscriptions,
 
     /// The channel used to signal shutdown.
     shutdown_tx: broadcast::Sender<()>,
@@ -35,15 +145,86 @@ impl Client {
     ) -> Self {
         Self {
             name: name.into(),
-            nvim: nvim::NvimApi { rpc_sender },
+            nvim: nvim::NvimApi::new(rpc_sender),
             shutdown_tx,
             channel_id,
+            connection_id: ConnectionId::default(),
+            peers: WeakPeers::default(),
+            method_prefix: String::new(),
+            subscriptions: Subscriptions::default(),
         }
     }
 
+    /// The clients for every editor connected to this plugin, including this one.
+    pub fn peers(&self) -> Peers {
+        self.peers.upgrade()
+    }
+
+    /// Run `f` against every connected editor concurrently, including this one. Failures are
+    /// logged, and the first one is returned once all editors have been tried.
+    ///
+    /// ```ignore
+    /// client
+    ///     .broadcast(|c| async move { c.info("build finished").await })
+    ///     .await?;
+    /// ```
+    pub async fn broadcast<F, Fut>(&self, f: F) -> Result<()>
+    where
+        F: Fn(Self) -> Fut,
+        Fut: Future<Output = Result<()>>,
+    {
+        let clients = self.peers().clients();
+        let ids: Vec<ConnectionId> = clients.iter().map(|c| c.connection_id).collect();
+        let results = join_all(clients.into_iter().map(f)).await;
+        let mut first = None;
+        for (id, ret) in ids.into_iter().zip(results) {
+            if let Err(e) = ret {
+                warn!("broadcast to connection {id} failed: {e}");
+                first.get_or_insert(e);
+            }
+        }
+        first.map_or(Ok(()), Err)
+    }
+
+    /// Subscribe to an event that Lua sends with `vim.rpcnotify(chan, event, data)`, returning a
+    /// stream of decoded payloads. Events are delivered to every subscriber, but only if the
+    /// plugin has no method of the same name.
+    ///
+    /// ```ignore
+    /// let mut moves = client.subscribe::<Position>("cursor_moved");
+    /// while let Some(pos) = moves.next().await {
+    ///     // ...
+    /// }
+    /// ```
+    pub fn subscribe<T: DeserializeOwned>(&self, event: &str) -> Subscription<T> {
+        Subscription::new(event, self.subscriptions.add(event))
+    }
+
+    /// Fire the `User` autocmds matching `name`, passing `data` to their callbacks as `ev.data`.
+    pub async fn emit_user_event<T: Serialize>(&self, name: &str, data: &T) -> Result<()> {
+        self.nvim
+            .exec_autocmds(
+                &[nvim::types::Event::User],
+                nvim::opts::ExecAutocmds::default()
+                    .pattern(vec![name.into()])
+                    .data(serde_rmpv::to_value(data)?),
+            )
+            .await
+    }
+
+    /// Typed bindings for Vimscript builtin functions, e.g. `client.fn_().expand("%", None)`.
+    pub fn fn_(&self) -> nvim::vimfn::VimFn<'_> {
+        nvim::vimfn::VimFn::new(&self.nvim)
+    }
+
+    /// Typed access to global options, e.g. `client.options().columns()`.
+    pub fn options(&self) -> nvim::options::Options<'_> {
+        nvim::options::Options::new(&self.nvim)
+    }
+
     /// Get the current working directory from Neovim.
     pub async fn getcwd(&self) -> Result<PathBuf> {
-        lua!(self, "return vim.fn.getcwd()",).await
+        Ok(self.fn_().getcwd(None, None).await?.into())
     }
 
     /// Register an RPC method in Neovim. This creates a Lua function under the specified namespace
@@ -70,6 +251,33 @@ impl Client {
             kind, namespace, method, arg_list
         );
         let channel_id = self.channel_id;
+        let prefix = &self.method_prefix;
+        let call = format!("vim.{kind}({channel_id}, '{prefix}{method}'{extra_sep} {arg_list})");
+        // Requests re-raise structured plugin errors as a table, so callers can inspect them with
+        // pcall. See error::PluginError.
+        let body = if kind == "rpcrequest" {
+            format!(
+                "
+                    local ok, ret = pcall(function() return {call} end)
+                    if ok then
+                        return ret
+                    end
+                    local payload = type(ret) == 'string' and ret:match('{marker}(.*)$')
+                    if payload then
+                        local err = vim.json.decode(payload)
+                        error(setmetatable(err, {{
+                            __tostring = function(e)
+                                return e.plugin .. '.' .. e.method .. ': ' .. e.message
+                            end,
+                        }}), 2)
+                    end
+                    error(ret, 2)
+                ",
+                marker = PLUGIN_ERROR_MARKER.replace('-', "%-"),
+            )
+        } else {
+            format!("return {call}")
+        };
 
         lua_exec!(
             self,
@@ -79,7 +287,7 @@ impl Client {
                         _G.{namespace} = {{}}
                     end
                     _G.{namespace}.{method} = function({arg_list})
-                        return vim.{kind}({channel_id}, '{method}'{extra_sep} {arg_list})
+                        {body}
                     end
                 "
             ),
@@ -331,14 +539,8 @@ impl Client {
                     msg: format!("Plugin failed to reach running state after {timeout:?}"),
                 });
             }
-            let val = lua_exec!(
-                self,
-                &format!("return {name}.{status}()", status = service::STATUS_MESSAGE)
-            )
-            .await;
-            if let Ok(val) = val
-                && let Some(val) = val.as_str()
-                && val == service::Status::Running.to_string()
+            if let Ok(Some(report)) = self.plugin_status(name).await
+                && report.status == service::Status::Running
             {
                 break;
             }
@@ -347,6 +549,87 @@ impl Client {
         Ok(())
     }
 
+    /// Ask a plugin for its status through its status request. Returns `None` if no plugin
+    /// with this name is registered in Neovim.
+    pub async fn plugin_status(&self, name: &str) -> Result<Option<service::StatusReport>> {
+        let val = lua_exec!(
+            self,
+            &format!(
+                "
+                    local p = rawget(_G, ...)
+                    if type(p) == 'table' and p.{status} then
+                        return p.{status}()
+                    end
+                    return nil
+                ",
+                status = service::STATUS_MESSAGE
+            ),
+            name
+        )
+        .await?;
+        if val.is_nil() {
+            return Ok(None);
+        }
+        Ok(Some(serde_rmpv::from_value(&val)?))
+    }
+
+    /// Call a function another plugin has registered in Neovim, e.g. `other.method(...)`, and
+    /// decode its return value. Errors raised by the plugin are returned as an [`Error::Plugin`],
+    /// wrapped in context naming the plugin and method. Usually called through a client
+    /// generated with `nvi_macros::plugin_client!`.
+    pub async fn call_plugin<T>(&self, plugin: &str, method: &str, args: Vec<Value>) -> Result<T>
+    where
+        T: DeserializeOwned,
+    {
+        let mut params = vec![Value::from(plugin), Value::from(method)];
+        params.extend(args);
+        let ret: Value = self
+            .nvim
+            .exec_lua(
+                "
+                    local plugin, method = ...
+                    local p = rawget(_G, plugin)
+                    local f = type(p) == 'table' and p[method]
+                    if not f then
+                        return { false, 'no function ' .. plugin .. '.' .. method }
+                    end
+                    local ok, ret = pcall(f, select(3, ...))
+                    if ok then
+                        return { true, ret }
+                    elseif type(ret) == 'table' then
+                        return { false, vim.json.encode(ret) }
+                    end
+                    return { false, tostring(ret) }
+                ",
+                params,
+            )
+            .await?;
+        let (ok, ret) = match ret.as_array().map(Vec::as_slice) {
+            Some([ok]) => (ok.as_bool(), Value::Nil),
+            Some([ok, ret]) => (ok.as_bool(), ret.clone()),
+            _ => (None, ret.clone()),
+        };
+        match ok {
+            Some(true) => Ok(serde_rmpv::from_value(&ret)?),
+            Some(false) => {
+                let msg = ret.as_str().unwrap_or_default();
+                Err(match serde_json::from_str::<PluginError>(msg) {
+                    Ok(e) => e.into(),
+                    Err(_) => RemoteError {
+                        kind: RemoteErrorKind::Exception,
+                        message: msg.into(),
+                        method: Some(format!("{plugin}.{method}")),
+                    }
+                    .into(),
+                })
+            }
+            None => Err(Error::Decode {
+                msg: format!("unexpected reply from {plugin}.{method}: {ret}"),
+                source: None,
+            }),
+        }
+    }
+
     /// Redraw pending screen updates.
     ///
     /// Equivalent to the :redraw command in Neovim.
@@ -389,20 +672,24 @@ impl Client {
     /// This departs from the camelcase convention for highlight group names, but it gives us a
     /// consistent way to namespace over all features.
     pub fn hl_name(&self, group: &str) -> Result<String> {
-        let name = format!("{}_{group}", self.name);
-        highlights::check_group_name(&name)?;
-        Ok(name)
+        highlights::full_name(&self.name, group)
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::sync::{Arc, Mutex};
+
     use async_trait::async_trait;
     use tokio::sync::broadcast;
     use tracing::warn;
     use tracing_test::traced_test;
 
-    use crate::{error::Result, *};
+    use crate::{
+        error::{Error, Result},
+        nvim::types,
+        *,
+    };
 
     macro_rules! qtest {
         (@inner $s:stmt) => { $s };
@@ -449,6 +736,21 @@ mod tests {
         .unwrap();
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn it_tracks_peers() {
+        qtest! {
+            async fn ret(c: &Client) -> Result<()> {
+                assert_ne!(c.connection_id, ConnectionId::default());
+                assert_eq!(c.peers().ids(), vec![c.connection_id]);
+                c.broadcast(|c| async move { c.getcwd().await.map(|_| ()) })
+                    .await
+            }
+        }
+        .await
+        .unwrap();
+    }
+
     #[derive(Clone)]
     struct RequestPlugin {}
 
@@ -545,4 +847,94 @@ mod tests {
             .await
             .unwrap();
     }
+
+    #[derive(Clone)]
+    struct ErrorEventPlugin {
+        errors: Arc<Mutex<Vec<types::ErrorEvent>>>,
+    }
+
+    #[async_trait]
+    impl crate::NviPlugin for ErrorEventPlugin {
+        fn name(&self) -> String {
+            "ErrorEventPlugin".into()
+        }
+
+        async fn connected(&mut self, client: &mut Client) -> Result<()> {
+            client.nvim.command_notify("nvi_no_such_command").await?;
+            Ok(())
+        }
+
+        async fn error_event(&self, client: &mut Client, error: types::ErrorEvent) -> Result<()> {
+            self.errors.lock().unwrap().push(error);
+            client.shutdown();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_receives_error_events() {
+        let errors = Arc::new(Mutex::new(vec![]));
+        let (tx, _) = broadcast::channel(16);
+        test::run_plugin_with_shutdown(
+            ErrorEventPlugin {
+                errors: errors.clone(),
+            },
+            tx,
+        )
+        .await
+        .unwrap();
+        let errors = errors.lock().unwrap();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].message.contains("nvi_no_such_command"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_sends_notifications() {
+        let nvit = test::NviTest::builder().run().await.unwrap();
+        let nv = &nvit.client.nvim;
+        nv.set_var_notify("nvi_notify_test", 5).await.unwrap();
+        // Messages are handled in order, so the notification has landed once this returns.
+        let v: i64 = nv.get_var("nvi_notify_test").await.unwrap();
+        assert_eq!(v, 5);
+        nvit.finish().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_checks_api_level() {
+        let nvit = test::NviTest::builder().run().await.unwrap();
+        let nv = &nvit.client.nvim;
+        nv.require("nvim_open_tabpage", u64::MAX).unwrap();
+        let level = nv.detect_api_level().await.unwrap();
+        nv.require("nvim_get_mode", level).unwrap();
+        assert!(matches!(
+            nv.require("nvim_open_tabpage", level + 1),
+            Err(Error::Unsupported {
+                required_level,
+                ..
+            }) if required_level == level + 1
+        ));
+        nvit.finish().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_calls_vimfn() {
+        let nvit = test::NviTest::builder().run().await.unwrap();
+        let f = nvit.client.fn_();
+        assert_eq!(f.fnamemodify("/a/b.rs", ":t").await.unwrap(), "b.rs");
+        assert_eq!(f.has("nvim").await.unwrap(), 1);
+        assert_eq!(
+            f.getcwd(None, None).await.unwrap(),
+            f.getcwd(Some(0), None).await.unwrap()
+        );
+        assert_eq!(
+            f.printf("%d-%s", vec![1.into(), "a".into()]).await.unwrap(),
+            "1-a"
+        );
+        assert_eq!(f.getline_list(1, 1).await.unwrap(), vec![String::new()]);
+        nvit.finish().await.unwrap();
+    }
 }
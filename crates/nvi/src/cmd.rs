@@ -1,19 +1,18 @@
 //! The standard Nvi command line interface.
 
-use std::{path::PathBuf, process::Command};
+use std::{fs, path::PathBuf, process::Command};
 
 use clap::{Parser, Subcommand};
 use clap_verbosity_flag::{InfoLevel, Verbosity};
 use tokio::sync::broadcast;
 use tracing_log::AsTrace;
-use tracing_subscriber::{fmt, prelude::*};
 
 use crate::{
     NviPlugin, connect,
     demo::Demos,
     docs,
     error::{Error, Result},
-    process,
+    logging, manifest, process,
 };
 
 #[derive(Parser)]
@@ -34,20 +33,32 @@ enum Commands {
         /// Address to connect to
         addr: String,
 
+        /// Keep reconnecting, with backoff, when the connection fails or Neovim restarts
+        #[arg(long)]
+        reconnect: bool,
+
         #[command(flatten)]
         /// Verbosity level
         verbose: Verbosity<InfoLevel>,
     },
     /// Show plugin documentation
     Docs {
-        /// Output format (terminal or markdown)
+        /// Output format (terminal, markdown or vimdoc)
         #[arg(long, default_value = "terminal")]
         fmt: String,
+        /// Write the documentation to a file named after the plugin in this directory, rather
+        /// than printing it. For vimdoc, pass the plugin's `doc/` directory.
+        #[arg(long)]
+        write: Option<PathBuf>,
     },
     /// List available demos
     Demos,
     /// Inspect the plugin
-    Inspect,
+    Inspect {
+        /// Emit a machine-readable JSON manifest
+        #[arg(long)]
+        json: bool,
+    },
     /// Launch an interactive Neovim session listening on a socket
     Nvim {
         /// Unix domain socket path for communicating with Neovim
@@ -60,6 +71,9 @@ enum Commands {
     RunDemo {
         /// Name of the demo to run
         name: String,
+        /// Run the demo against a headless Neovim instance, and report its result
+        #[arg(long)]
+        headless: bool,
     },
     /// Run an interactive Neovim session with the plugin connected
     Run {
@@ -77,6 +91,18 @@ enum Commands {
     },
 }
 
+/// Set up logging for a plugin. Logging is a convenience, so a failure is reported as a warning
+/// rather than stopping the plugin.
+fn init_logging(plugin: &str, verbose: &Verbosity<InfoLevel>, stderr: bool) {
+    let ret = logging::Logging::default()
+        .level(verbose.log_level_filter().as_trace())
+        .stderr(stderr)
+        .init(plugin);
+    if let Err(e) = ret {
+        eprintln!("warning: could not set up logging: {e}");
+    }
+}
+
 /// Run the plugin with the given arguments.
 async fn inner_run<T>(plugin: T, demos: Option<Demos>) -> Result<()>
 where
@@ -84,14 +110,19 @@ where
 {
     let cli = Cli::parse();
     match &cli.command {
-        Commands::Connect { addr, verbose } => {
-            let fmt_layer = fmt::layer().without_time().with_target(true);
-            tracing_subscriber::registry()
-                .with(fmt_layer)
-                .with(verbose.log_level_filter().as_trace())
-                .init();
+        Commands::Connect {
+            addr,
+            reconnect,
+            verbose,
+        } => {
+            init_logging(&plugin.name(), verbose, false);
             let (tx, _rx) = broadcast::channel(16);
-            connect::connect_unix(tx, addr.clone(), plugin).await
+            let policy = if *reconnect {
+                connect::ReconnectPolicy::forever()
+            } else {
+                connect::ReconnectPolicy::none()
+            };
+            connect::reconnect_unix(tx, addr.clone(), plugin, policy).await
         }
         Commands::Demos => {
             if let Some(demos) = demos {
@@ -108,7 +139,7 @@ where
             }
             Ok(())
         }
-        Commands::Docs { fmt } => {
+        Commands::Docs { fmt, write } => {
             let name = plugin.name();
             let docs = plugin.docs()?;
             let methods = plugin.inspect();
@@ -117,14 +148,30 @@ where
             let format = match fmt.to_lowercase().as_str() {
                 "markdown" => docs::Formats::Markdown,
                 "terminal" => docs::Formats::Terminal,
+                "vimdoc" => docs::Formats::Vimdoc,
                 _ => return Err(Error::User(format!("Invalid format: {fmt}"))),
             };
 
-            println!("{}", docs::render_docs(format, &name, &docs, hl, methods)?);
+            let rendered = docs::render_docs(format, &name, &docs, &hl, methods)?;
+            if let Some(dir) = write {
+                let ext = format.extension().ok_or_else(|| {
+                    Error::User(format!("Format {fmt} can't be written to a file"))
+                })?;
+                fs::create_dir_all(dir)?;
+                let path = dir.join(format!("{name}.{ext}"));
+                fs::write(&path, rendered)?;
+                println!("Wrote {}", path.display());
+            } else {
+                println!("{rendered}");
+            }
             Ok(())
         }
-        Commands::Inspect => {
-            println!("{:#?}", plugin.inspect());
+        Commands::Inspect { json } => {
+            if *json {
+                println!("{}", manifest::Manifest::new(&plugin)?.to_json()?);
+            } else {
+                println!("{:#?}", plugin.inspect());
+            }
             Ok(())
         }
         Commands::Nvim { socket, no_clean } => {
@@ -142,9 +189,13 @@ where
 
             Ok(())
         }
-        Commands::RunDemo { name } => {
+        Commands::RunDemo { name, headless } => {
             if let Some(demos) = demos {
-                demos.run(name, plugin).await
+                if *headless {
+                    demos.run_headless(name, plugin).await
+                } else {
+                    demos.run(name, plugin).await
+                }
             } else {
                 eprintln!("No demos available.");
                 Ok(())
@@ -155,11 +206,7 @@ where
             no_clean,
             verbose,
         } => {
-            let fmt_layer = fmt::layer().without_time().with_target(true);
-            tracing_subscriber::registry()
-                .with(fmt_layer)
-                .with(verbose.log_level_filter().as_trace())
-                .init();
+            init_logging(&plugin.name(), verbose, true);
 
             let tempdir = tempfile::tempdir()?;
             let socket_path = tempdir.path().join("nvim.socket");
@@ -6,18 +6,121 @@
 //!
 //! Each function takes a shutdown broadcast channel that can be used to gracefully terminate
 //! the connection.
+//!
+//! The `listen_*_shared` variants serve every connection from a single plugin instance, so that
+//! state is shared between editors. Handlers can tell connections apart with
+//! `client.connection_id`, and reach every connected editor through `client.peers()` or
+//! `client.broadcast()`. Handlers taking `&mut self` hold the plugin exclusively, so they block
+//! every connection while they run.
+//!
+//! [`listen_tcp_authenticated`] and [`listen_tcp_shared_authenticated`] require connecting
+//! clients to present a shared-secret [`auth::Token`] before the plugin is bootstrapped. The
+//! standard command line in [`cmd`](crate::cmd) only connects out to Neovim, over a Unix socket
+//! or to a Neovim it starts itself, so it has no listener to authenticate; plugins that listen on
+//! TCP call these functions from their own `main`.
+//!
+//! The `reconnect_*` variants keep a plugin attached across Neovim restarts, retrying with
+//! backoff according to a [`ReconnectPolicy`]. The same plugin instance is used for every
+//! connection, and `bootstrap` and `connected` are run again each time, so namespaces, autocmds
+//! and highlights are restored.
 
-use std::{net::SocketAddr, path::Path};
+use std::{
+    collections::hash_map::RandomState,
+    future::Future,
+    hash::{BuildHasher, Hasher},
+    net::SocketAddr,
+    path::Path,
+    time::Duration,
+};
 
+use derive_setters::Setters;
 use mrpc::{Client, Server};
-use tokio::{signal, sync::broadcast};
-use tracing::{error, trace};
+use tokio::{signal, sync::broadcast, time};
+use tracing::{error, info, trace, warn};
 
 use crate::{
+    auth,
     error::Result,
-    service::{NviPlugin, RpcConnection},
+    service::{NviPlugin, RpcConnection, SharedPlugin},
 };
 
+/// When and how often to retry a connection to Neovim.
+///
+/// Delays grow exponentially from `initial_delay` by `multiplier`, up to `max_delay`, with a
+/// random jitter so that many plugins don't reconnect in lockstep. The attempt count resets
+/// after every successful connection.
+#[derive(Debug, Clone, PartialEq, Setters)]
+#[setters(strip_option)]
+pub struct ReconnectPolicy {
+    /// The number of consecutive retries before giving up, or `None` to retry forever.
+    /// Default: 10.
+    pub max_attempts: Option<u32>,
+    /// The delay before the first retry. Default: 100ms.
+    pub initial_delay: Duration,
+    /// The longest delay between retries. Default: 30s.
+    pub max_delay: Duration,
+    /// The factor by which the delay grows after each retry. Default: 2.
+    pub multiplier: f64,
+    /// The fraction of the delay that is randomly added or removed, from 0 to 1. Default: 0.2.
+    pub jitter: f64,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: Some(10),
+            initial_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(30),
+            multiplier: 2.0,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// A policy that never retries: connect once, and stop when the connection closes.
+    pub fn none() -> Self {
+        Self {
+            max_attempts: Some(0),
+            ..Self::default()
+        }
+    }
+
+    /// A policy that retries forever.
+    pub fn forever() -> Self {
+        Self {
+            max_attempts: None,
+            ..Self::default()
+        }
+    }
+
+    /// The delay before retry number `attempt` (counting from 0), without jitter.
+    pub fn delay(&self, attempt: u32) -> Duration {
+        let secs = self.initial_delay.as_secs_f64()
+            * self.multiplier.powi(attempt.min(i32::MAX as u32) as i32);
+        Duration::from_secs_f64(secs.min(self.max_delay.as_secs_f64()))
+    }
+
+    /// The delay before retry number `attempt`, with jitter applied.
+    fn jittered_delay(&self, attempt: u32) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0) * (2.0 * random_unit() - 1.0);
+        self.delay(attempt)
+            .mul_f64(1.0 + jitter)
+            .min(self.max_delay)
+    }
+
+    /// Whether `attempts` consecutive retries have used up the policy.
+    fn exhausted(&self, attempts: u32) -> bool {
+        self.max_attempts.is_some_and(|m| attempts >= m)
+    }
+}
+
+/// A random number in `[0, 1)`, drawn from the standard library's randomly seeded hasher.
+fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish();
+    (bits >> 11) as f64 / (1u64 << 53) as f64
+}
+
 /// Listen for incoming connections on a Unix domain socket.
 ///
 /// Creates a Unix domain socket at the specified path and listens for incoming connections.
@@ -40,17 +143,32 @@ where
     let server = Server::from_fn(move || RpcConnection::new(itx.clone(), make_plugin()))
         .unix(path)
         .await?;
-    let mut shutdown_rx = shutdown_tx.subscribe();
-    tokio::select! {
-        result = server.run() => {
-            if let Err(e) = result {
-                error!("Server error: {}", e);
-            }
-        }
-        _ = shutdown_rx.recv() => {
-            trace!("Shutdown signal received, stopping listener.");
-        }
+    serve(&shutdown_tx, server).await;
+    if let Err(e) = std::fs::remove_file(path) {
+        error!("Failed to remove socket file: {}", e);
     }
+    Ok(())
+}
+
+/// Listen for incoming connections on a Unix domain socket, serving every connection from a
+/// single shared plugin instance.
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `path` - Path where the Unix domain socket will be created
+/// * `plugin` - The plugin instance shared by all connections
+pub async fn listen_unix_shared<T>(
+    shutdown_tx: broadcast::Sender<()>,
+    path: impl AsRef<Path>,
+    plugin: T,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let path = path.as_ref();
+    let server = Server::from_fn(shared_factory(shutdown_tx.clone(), plugin))
+        .unix(path)
+        .await?;
+    serve(&shutdown_tx, server).await;
     if let Err(e) = std::fs::remove_file(path) {
         error!("Failed to remove socket file: {}", e);
     }
@@ -81,7 +199,105 @@ where
     })
     .tcp(&addr.to_string())
     .await?;
+    serve(&shutdown_tx, server).await;
+    Ok(())
+}
 
+/// Listen for incoming connections on a TCP socket, requiring each client to present `token`
+/// with an [`auth::AUTH_MESSAGE`] request as its first call. Clients that don't, or that take
+/// longer than `timeout`, are closed with a logged warning, without the plugin seeing them.
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `addr` - TCP socket address to bind to
+/// * `token` - The shared secret clients must present
+/// * `timeout` - How long clients have to authenticate, e.g. [`auth::DEFAULT_TIMEOUT`]
+/// * `make_plugin` - Factory function to create plugin instances
+pub async fn listen_tcp_authenticated<T, F>(
+    shutdown_tx: broadcast::Sender<()>,
+    addr: SocketAddr,
+    token: auth::Token,
+    timeout: Duration,
+    make_plugin: F,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+    F: Fn() -> T + Send + Sync + 'static,
+{
+    let itx = shutdown_tx.clone();
+    let server = Server::from_fn(move || {
+        RpcConnection::new(itx.clone(), make_plugin()).with_auth(token.clone(), timeout)
+    })
+    .tcp(&addr.to_string())
+    .await?;
+    serve(&shutdown_tx, server).await;
+    Ok(())
+}
+
+/// Listen for incoming connections on a TCP socket, serving every connection from a single
+/// shared plugin instance.
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `addr` - TCP socket address to bind to
+/// * `plugin` - The plugin instance shared by all connections
+pub async fn listen_tcp_shared<T>(
+    shutdown_tx: broadcast::Sender<()>,
+    addr: SocketAddr,
+    plugin: T,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let server = Server::from_fn(shared_factory(shutdown_tx.clone(), plugin))
+        .tcp(&addr.to_string())
+        .await?;
+    serve(&shutdown_tx, server).await;
+    Ok(())
+}
+
+/// Listen for incoming connections on a TCP socket, serving every connection from a single
+/// shared plugin instance, and requiring each client to authenticate as with
+/// [`listen_tcp_authenticated`].
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `addr` - TCP socket address to bind to
+/// * `token` - The shared secret clients must present
+/// * `timeout` - How long clients have to authenticate, e.g. [`auth::DEFAULT_TIMEOUT`]
+/// * `plugin` - The plugin instance shared by all connections
+pub async fn listen_tcp_shared_authenticated<T>(
+    shutdown_tx: broadcast::Sender<()>,
+    addr: SocketAddr,
+    token: auth::Token,
+    timeout: Duration,
+    plugin: T,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let make = shared_factory(shutdown_tx.clone(), plugin);
+    let server = Server::from_fn(move || make().with_auth(token.clone(), timeout))
+        .tcp(&addr.to_string())
+        .await?;
+    serve(&shutdown_tx, server).await;
+    Ok(())
+}
+
+/// Make a factory for connections that all share one plugin instance and one set of peers.
+fn shared_factory<T>(
+    shutdown_tx: broadcast::Sender<()>,
+    plugin: T,
+) -> impl Fn() -> RpcConnection<T> + Send + Sync + 'static
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let shared = SharedPlugin::new(plugin);
+    move || shared.connection(shutdown_tx.clone())
+}
+
+/// Run a server until it fails or a shutdown signal is received.
+async fn serve<T>(shutdown_tx: &broadcast::Sender<()>, server: Server<RpcConnection<T>>)
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
     let mut shutdown_rx = shutdown_tx.subscribe();
     tokio::select! {
         result = server.run() => {
@@ -93,7 +309,6 @@ where
             trace!("Shutdown signal received, stopping listener.");
         }
     }
-    Ok(())
 }
 
 /// Connect to a Neovim instance through a Unix domain socket.
@@ -113,9 +328,34 @@ where
     P: AsRef<Path>,
     T: NviPlugin + Send + Sync + 'static,
 {
-    let rpc_conn = RpcConnection::new(shutdown_tx.clone(), plugin);
-    let client = Client::connect_unix(path, rpc_conn).await?;
-    handle_client(shutdown_tx.subscribe(), client).await
+    reconnect_unix(shutdown_tx, path, plugin, ReconnectPolicy::none()).await
+}
+
+/// Connect to a Neovim instance through a Unix domain socket, reconnecting when the connection
+/// fails or closes.
+///
+/// Returns when a shutdown signal is received, or with the last connection error once the
+/// policy's attempts are used up.
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `path` - Path to the Unix domain socket to connect to
+/// * `plugin` - Plugin instance to run
+/// * `policy` - When to retry the connection
+pub async fn reconnect_unix<T, P>(
+    shutdown_tx: broadcast::Sender<()>,
+    path: P,
+    plugin: T,
+    policy: ReconnectPolicy,
+) -> Result<()>
+where
+    P: AsRef<Path>,
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let path = path.as_ref().to_path_buf();
+    run_connections(shutdown_tx, plugin, &policy, |conn| {
+        Client::connect_unix(path.clone(), conn)
+    })
+    .await
 }
 
 /// Connect to a Neovim instance through a TCP socket.
@@ -134,38 +374,120 @@ pub async fn connect_tcp<T>(
 where
     T: NviPlugin + Send + Sync + 'static,
 {
-    let rpc_conn = RpcConnection::new(shutdown_tx.clone(), plugin);
-    let client = Client::connect_tcp(&addr.to_string(), rpc_conn).await?;
-    handle_client(shutdown_tx.subscribe(), client).await
+    reconnect_tcp(shutdown_tx, addr, plugin, ReconnectPolicy::none()).await
+}
+
+/// Connect to a Neovim instance through a TCP socket, reconnecting when the connection fails or
+/// closes.
+///
+/// Returns when a shutdown signal is received, or with the last connection error once the
+/// policy's attempts are used up.
+///
+/// * `shutdown_tx` - Broadcast channel for shutdown signals
+/// * `addr` - TCP address to connect to
+/// * `plugin` - Plugin instance to run
+/// * `policy` - When to retry the connection
+pub async fn reconnect_tcp<T>(
+    shutdown_tx: broadcast::Sender<()>,
+    addr: SocketAddr,
+    plugin: T,
+    policy: ReconnectPolicy,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+{
+    let addr = addr.to_string();
+    run_connections(shutdown_tx, plugin, &policy, |conn| {
+        let addr = addr.clone();
+        async move { Client::connect_tcp(&addr, conn).await }
+    })
+    .await
+}
+
+/// Run a plugin over successive connections made by `connect`, until shutdown or until the
+/// policy gives up.
+async fn run_connections<T, C, F>(
+    shutdown_tx: broadcast::Sender<()>,
+    plugin: T,
+    policy: &ReconnectPolicy,
+    connect: C,
+) -> Result<()>
+where
+    T: NviPlugin + Send + Sync + 'static,
+    C: Fn(RpcConnection<T>) -> F,
+    F: Future<Output = mrpc::Result<Client>>,
+{
+    let shared = SharedPlugin::new(plugin);
+    let mut shutdown_rx = shutdown_tx.subscribe();
+    let mut attempts = 0;
+    loop {
+        let conn = shared.connection(shutdown_tx.clone());
+        let ret = match connect(conn).await {
+            Ok(client) => {
+                attempts = 0;
+                if handle_client(&mut shutdown_rx, client).await == Ended::Shutdown {
+                    return Ok(());
+                }
+                Ok(())
+            }
+            Err(e) => Err(e.into()),
+        };
+        if policy.exhausted(attempts) {
+            return ret;
+        }
+        let delay = policy.jittered_delay(attempts);
+        attempts += 1;
+        match ret {
+            Ok(()) => info!("connection closed, reconnecting in {delay:?}"),
+            Err(e) => warn!("connection failed, retrying in {delay:?}: {e}"),
+        }
+        tokio::select! {
+            _ = time::sleep(delay) => {}
+            _ = shutdown_rx.recv() => return Ok(()),
+            _ = signal::ctrl_c() => return Ok(()),
+        }
+    }
+}
+
+/// How a connection ended.
+#[derive(Debug, PartialEq, Eq)]
+enum Ended {
+    /// The connection was closed, or failed
+    Closed,
+    /// We were asked to shut down
+    Shutdown,
 }
 
 /// Handle the client connection until shutdown or error.
-async fn handle_client(mut shutdown_rx: broadcast::Receiver<()>, client: Client) -> Result<()> {
+async fn handle_client(shutdown_rx: &mut broadcast::Receiver<()>, client: Client) -> Ended {
     tokio::select! {
         _ = client.join()  => {
             trace!("Client connection closed.");
-            Ok(())
+            Ended::Closed
         }
         _ = shutdown_rx.recv() => {
             trace!("Shutdown signal received, closing connection.");
-            Ok(())
+            Ended::Shutdown
         }
         _ = signal::ctrl_c() => {
             trace!("Ctrl-C received, closing connection.");
-            Ok(())
+            Ended::Shutdown
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use std::path::PathBuf;
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
 
-    use tokio::sync::broadcast;
+    use tokio::sync::{broadcast, mpsc};
     use tracing_test::traced_test;
 
     use super::*;
-    use crate::{Client, NviPlugin, lua_exec, test};
+    use crate::{Client, ConnectionId, NviPlugin, Value, lua, lua_exec, process, test};
 
     #[derive(Clone)]
     struct TestPlugin {
@@ -242,6 +564,353 @@ mod tests {
         // signal.
     }
 
+    #[tokio::test]
+    #[traced_test]
+    async fn it_shares_state_between_editors() {
+        /// Asks the editor it runs in to connect to the shared listener.
+        #[derive(Clone)]
+        struct ConnectBack {
+            socket_path: PathBuf,
+        }
+
+        #[async_trait::async_trait]
+        impl NviPlugin for ConnectBack {
+            fn name(&self) -> String {
+                "ConnectBack".into()
+            }
+
+            async fn connected(&mut self, client: &mut Client) -> Result<()> {
+                lua_exec!(
+                    client,
+                    "vim.fn.sockconnect('pipe', ...,  {{rpc = true}});",
+                    self.socket_path.as_os_str().to_string_lossy()
+                )
+                .await?;
+                Ok(())
+            }
+        }
+
+        /// What the shared plugin saw once both editors connected.
+        struct Seen {
+            /// The connection IDs passed to `connected`
+            ids: Vec<ConnectionId>,
+            /// The IDs of the peers
+            peers: Vec<ConnectionId>,
+            /// Whether each peer received the broadcast
+            broadcast: Vec<bool>,
+        }
+
+        /// Shared by both editors. Once the second connects, it records what it sees and shuts
+        /// down.
+        struct SharedPlugin {
+            ids: Vec<ConnectionId>,
+            ret: Arc<Mutex<Option<Seen>>>,
+        }
+
+        #[async_trait::async_trait]
+        impl NviPlugin for SharedPlugin {
+            fn name(&self) -> String {
+                "SharedPlugin".into()
+            }
+
+            async fn connected(&mut self, client: &mut Client) -> Result<()> {
+                self.ids.push(client.connection_id);
+                if self.ids.len() < 2 {
+                    return Ok(());
+                }
+                client
+                    .broadcast(|c| async move {
+                        lua_exec!(c, "vim.g.nvi_broadcast = true").await.map(|_| ())
+                    })
+                    .await?;
+                let mut broadcast = vec![];
+                for c in client.peers().clients() {
+                    broadcast.push(lua!(c, "return vim.g.nvi_broadcast").await?);
+                }
+                self.ret.lock().unwrap().replace(Seen {
+                    ids: self.ids.clone(),
+                    peers: client.peers().ids(),
+                    broadcast,
+                });
+                client.shutdown();
+                Ok(())
+            }
+        }
+
+        let (tx, _) = broadcast::channel(16);
+        let tempdir = tempfile::tempdir().unwrap();
+        let socket_path = tempdir.path().join("shared.socket");
+        let ret = Arc::new(Mutex::new(None));
+        let plugin = SharedPlugin {
+            ids: vec![],
+            ret: ret.clone(),
+        };
+        let ls = tokio::spawn(listen_unix_shared(tx.clone(), socket_path.clone(), plugin));
+        test::wait_for_path(&socket_path).await.unwrap();
+
+        let editors: Vec<_> = (0..2)
+            .map(|_| {
+                let plugin = ConnectBack {
+                    socket_path: socket_path.clone(),
+                };
+                tokio::spawn(test::run_plugin_with_shutdown(plugin, tx.clone()))
+            })
+            .collect();
+        for e in editors {
+            e.await.unwrap().unwrap();
+        }
+        ls.await.unwrap().unwrap();
+
+        let mut seen = ret.lock().unwrap().take().unwrap();
+        assert_ne!(seen.ids[0], seen.ids[1]);
+        seen.ids.sort();
+        assert_eq!(seen.peers, seen.ids);
+        assert_eq!(seen.broadcast, vec![true, true]);
+    }
+
+    /// Start a TCP listener that requires the token "secret", then have Neovim run `code` with
+    /// the listener's address as its argument. Returns what `code` returns. If `stop` is false,
+    /// the test ends when the listening plugin connects. If `shared` is true, the listener serves
+    /// a single shared plugin.
+    async fn auth_connect(code: &'static str, stop: bool, shared: bool) -> Value {
+        #[derive(Clone)]
+        struct AuthClientPlugin {
+            addr: String,
+            code: &'static str,
+            stop: bool,
+            ret: Arc<Mutex<Option<Value>>>,
+        }
+
+        #[async_trait::async_trait]
+        impl NviPlugin for AuthClientPlugin {
+            fn name(&self) -> String {
+                "AuthClientPlugin".into()
+            }
+
+            async fn connected(&mut self, client: &mut Client) -> Result<()> {
+                let v: Value = lua!(client, self.code, self.addr).await?;
+                self.ret.lock().unwrap().replace(v);
+                if self.stop {
+                    client.shutdown();
+                }
+                Ok(())
+            }
+        }
+
+        let (tx, _) = broadcast::channel(16);
+        let addr = std::net::TcpListener::bind("127.0.0.1:0")
+            .unwrap()
+            .local_addr()
+            .unwrap();
+        let itx = tx.clone();
+        let token = auth::Token::new("secret").unwrap();
+        let ls = if shared {
+            tokio::spawn(listen_tcp_shared_authenticated(
+                tx.clone(),
+                addr,
+                token,
+                auth::DEFAULT_TIMEOUT,
+                TestPlugin { tx: itx },
+            ))
+        } else {
+            tokio::spawn(listen_tcp_authenticated(
+                tx.clone(),
+                addr,
+                token,
+                auth::DEFAULT_TIMEOUT,
+                move || TestPlugin { tx: itx.clone() },
+            ))
+        };
+
+        let ret = Arc::new(Mutex::new(None));
+        let plugin = AuthClientPlugin {
+            addr: addr.to_string(),
+            code,
+            stop,
+            ret: ret.clone(),
+        };
+        test::run_plugin_with_shutdown(plugin, tx).await.unwrap();
+        ls.await.unwrap().unwrap();
+        ret.lock().unwrap().take().unwrap()
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_accepts_authenticated_clients() {
+        let ret = auth_connect(
+            "local chan = vim.fn.sockconnect('tcp', ..., {rpc = true})
+            return vim.rpcrequest(chan, '__nvi_auth', 'secret')",
+            false,
+            false,
+        )
+        .await;
+        assert_eq!(ret, Value::Boolean(true));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_accepts_authenticated_shared_clients() {
+        let ret = auth_connect(
+            "local chan = vim.fn.sockconnect('tcp', ..., {rpc = true})
+            return vim.rpcrequest(chan, '__nvi_auth', 'secret')",
+            false,
+            true,
+        )
+        .await;
+        assert_eq!(ret, Value::Boolean(true));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_rejects_bad_tokens() {
+        let ret = auth_connect(
+            "local chan = vim.fn.sockconnect('tcp', ..., {rpc = true})
+            return pcall(vim.rpcrequest, chan, '__nvi_auth', 'wrong')",
+            true,
+            false,
+        )
+        .await;
+        assert_eq!(ret, Value::Boolean(false));
+        assert!(logs_contain("presented an invalid token"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_rejects_missing_tokens() {
+        let ret = auth_connect(
+            "local chan = vim.fn.sockconnect('tcp', ..., {rpc = true})
+            return pcall(vim.rpcrequest, chan, '__nvi_status')",
+            true,
+            false,
+        )
+        .await;
+        assert_eq!(ret, Value::Boolean(false));
+        assert!(logs_contain("before authenticating"));
+    }
+
+    #[test]
+    fn test_reconnect_delay() {
+        let p = ReconnectPolicy::default()
+            .initial_delay(Duration::from_millis(100))
+            .max_delay(Duration::from_secs(1));
+        assert_eq!(p.delay(0), Duration::from_millis(100));
+        assert_eq!(p.delay(1), Duration::from_millis(200));
+        assert_eq!(p.delay(3), Duration::from_millis(800));
+        assert_eq!(p.delay(4), Duration::from_secs(1));
+        assert_eq!(p.delay(u32::MAX), Duration::from_secs(1));
+
+        for attempt in 0..5 {
+            let d = p.jittered_delay(attempt);
+            let base = p.delay(attempt);
+            assert!(d >= base.mul_f64(0.8) && d <= base.mul_f64(1.2).min(p.max_delay));
+        }
+
+        assert!(ReconnectPolicy::none().exhausted(0));
+        assert!(!ReconnectPolicy::forever().exhausted(u32::MAX));
+        assert!(ReconnectPolicy::default().max_attempts(3).exhausted(3));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_gives_up_reconnecting() {
+        let (tx, _) = broadcast::channel(16);
+        let tempdir = tempfile::tempdir().unwrap();
+        let policy = ReconnectPolicy::default()
+            .max_attempts(2)
+            .initial_delay(Duration::from_millis(10));
+        let ret = reconnect_unix(
+            tx.clone(),
+            tempdir.path().join("missing.socket"),
+            TestPlugin { tx },
+            policy,
+        )
+        .await;
+        assert!(ret.is_err());
+        assert!(logs_contain("retrying in"));
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_rebootstraps_on_reconnect() {
+        /// Registers a function in bootstrap, and once connected reports the result of calling
+        /// it from Lua.
+        struct PingPlugin {
+            tx: mpsc::UnboundedSender<String>,
+        }
+
+        #[async_trait::async_trait]
+        impl NviPlugin for PingPlugin {
+            fn name(&self) -> String {
+                "ping_plugin".into()
+            }
+
+            async fn bootstrap(&self, client: &mut Client) -> Result<()> {
+                client
+                    .register_rpcrequest::<String>("ping_plugin", "ping", &[])
+                    .await
+            }
+
+            async fn connected(&mut self, client: &mut Client) -> Result<()> {
+                // The call is handled once `connected` returns and the plugin is unlocked.
+                let (client, tx) = (client.clone(), self.tx.clone());
+                tokio::spawn(async move {
+                    let ret: Result<String> = lua!(client, "return ping_plugin.ping()").await;
+                    tx.send(ret.unwrap()).unwrap();
+                });
+                Ok(())
+            }
+
+            async fn request(
+                &self,
+                _client: &mut Client,
+                method: &str,
+                _params: &[Value],
+            ) -> Result<Value, Value> {
+                assert_eq!(method, "ping");
+                Ok(Value::from("pong"))
+            }
+        }
+
+        let tempdir = tempfile::tempdir().unwrap();
+        let socket_path = tempdir.path().join("nvim.socket");
+        let start_nvim = |nv_tx: &broadcast::Sender<()>| {
+            let (rx, path) = (nv_tx.subscribe(), socket_path.clone());
+            tokio::spawn(async move { process::start_nvim_headless(rx, path, true).await })
+        };
+
+        let (nv_tx, _) = broadcast::channel(1);
+        let nv = start_nvim(&nv_tx);
+        test::wait_for_path(&socket_path).await.unwrap();
+
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let (plugin_tx, _) = broadcast::channel(1);
+        let policy = ReconnectPolicy::forever()
+            .initial_delay(Duration::from_millis(10))
+            .max_delay(Duration::from_millis(100));
+        let plugin = tokio::spawn(reconnect_unix(
+            plugin_tx.clone(),
+            socket_path.clone(),
+            PingPlugin { tx },
+            policy,
+        ));
+        assert_eq!(rx.recv().await.unwrap(), "pong");
+
+        // Restart Neovim. The plugin's function only exists in the new instance if the plugin
+        // was bootstrapped again.
+        nv_tx.send(()).unwrap();
+        nv.await.unwrap().unwrap();
+        let _ = std::fs::remove_file(&socket_path);
+        let (nv_tx, _) = broadcast::channel(1);
+        let nv = start_nvim(&nv_tx);
+        assert_eq!(rx.recv().await.unwrap(), "pong");
+        assert!(logs_contain("reconnecting in"));
+
+        plugin_tx.send(()).unwrap();
+        plugin.await.unwrap().unwrap();
+        nv_tx.send(()).unwrap();
+        nv.await.unwrap().unwrap();
+    }
+
     #[tokio::test]
     #[traced_test]
     async fn it_connects() {
@@ -2,7 +2,7 @@
 use std::{collections::HashMap, process::Command as StdCommand, time::Duration};
 
 use futures::future::BoxFuture;
-use tokio::sync::broadcast;
+use tokio::{sync::broadcast, task::JoinError};
 
 use crate::{
     NviPlugin,
@@ -10,6 +10,7 @@ use crate::{
     connect::connect_unix,
     error::{Error, Result},
     process,
+    test::wait_for_path,
 };
 
 /// The timeout for waiting for the plugin to start.
@@ -64,6 +65,13 @@ impl Demos {
             .insert(name.into(), Box::new(Self::demo_fn(f)));
     }
 
+    /// Look up a named demo function.
+    fn get(&self, demo_name: &str) -> Result<&DemoFunction> {
+        self.functions
+            .get(demo_name)
+            .ok_or_else(|| Error::User(format!("no such demo: {demo_name}")))
+    }
+
     /// Run a named demo function with a plugin instance.
     ///
     /// This starts an interactive Neovim instance, connects the plugin to it, runs the demo,
@@ -89,20 +97,12 @@ impl Demos {
         let mut demo_result = client.await_plugin(&plugin_name, TIMEOUT).await;
 
         if demo_result.is_ok() {
-            let f = self
-                .functions
-                .get(demo_name)
-                .ok_or_else(|| Error::User(format!("no such demo: {demo_name}")))?;
+            let f = self.get(demo_name)?;
             demo_result = f(&client).await;
         }
 
         let (plugin_result, neovim_result) = tokio::join!(plugin_task, neovim_handle);
-
-        let plugin_result = plugin_result
-            .map_err(|e| Error::Internal {
-                msg: format!("plugin task failed: {e}"),
-            })
-            .and_then(|r| r);
+        let plugin_result = task_result("plugin", plugin_result);
 
         if let Err(e) = neovim_result {
             eprintln!("neovim task failed: {e}");
@@ -119,6 +119,57 @@ impl Demos {
         drop(StdCommand::new("reset").status());
         Ok(())
     }
+
+    /// Run a named demo function with a plugin instance against a headless Neovim.
+    ///
+    /// This starts a headless Neovim instance, connects the plugin to it, runs the demo, and then
+    /// shuts everything down. Unlike `run`, errors from the demo, the plugin and Neovim are
+    /// returned to the caller, so demos can be used as tests.
+    pub async fn run_headless<T>(&self, demo_name: &str, plugin: T) -> Result<()>
+    where
+        T: NviPlugin + Send + Sync + Unpin + 'static,
+    {
+        let f = self.get(demo_name)?;
+
+        let tempdir = tempfile::tempdir()?;
+        let socket_path = tempdir.path().join("nvim.socket");
+
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let sp = socket_path.clone();
+        let srx = shutdown_tx.subscribe();
+        let neovim_task =
+            tokio::spawn(async move { process::start_nvim_headless(srx, sp, true).await });
+        wait_for_path(&socket_path).await?;
+
+        let rpc_client = mrpc::Client::connect_unix(&socket_path, ()).await?;
+        let client = Client::new(rpc_client.sender(), "demo", 0, shutdown_tx.clone());
+
+        let plugin_shutdown = shutdown_tx.clone();
+        let plugin_name = plugin.name();
+        let plugin_task = tokio::spawn(connect_unix(plugin_shutdown, socket_path.clone(), plugin));
+
+        let demo_result = match client.await_plugin(&plugin_name, TIMEOUT).await {
+            Ok(()) => f(&client).await,
+            Err(e) => Err(e),
+        };
+
+        client.shutdown();
+        let (plugin_result, neovim_result) = tokio::join!(plugin_task, neovim_task);
+
+        demo_result?;
+        task_result("plugin", plugin_result)?;
+        task_result("neovim", neovim_result)?;
+        Ok(())
+    }
+}
+
+/// Flatten the result of a spawned task that itself returns a `Result`.
+fn task_result(task: &str, result: Result<Result<()>, JoinError>) -> Result<()> {
+    result
+        .map_err(|e| Error::Internal {
+            msg: format!("{task} task failed: {e}"),
+        })
+        .and_then(|r| r)
 }
 
 #[cfg(test)]
@@ -143,4 +194,21 @@ mod tests {
         let lst = d.list();
         assert_eq!(lst, vec!["one", "two"]);
     }
+
+    #[tokio::test]
+    async fn test_run_headless_unknown_demo() {
+        #[derive(Clone)]
+        struct TestPlugin {}
+
+        #[async_trait::async_trait]
+        impl NviPlugin for TestPlugin {
+            fn name(&self) -> String {
+                "TestPlugin".into()
+            }
+        }
+
+        let d = Demos::new();
+        let err = d.run_headless("missing", TestPlugin {}).await.unwrap_err();
+        assert!(err.to_string().contains("no such demo: missing"));
+    }
 }
@@ -1,7 +1,7 @@
 #![allow(missing_docs)]
 #![allow(clippy::missing_docs_in_private_items)]
 #![allow(clippy::absolute_paths)]
-use macro_types::{Method, MethodType};
+use macro_types::{AutoCmd, Method, MethodType};
 
 use crate::{
     error::{Error, Result},
@@ -9,16 +9,92 @@ use crate::{
     highlights::full_name,
 };
 
+/// The text width for Vim help files.
+const VIMDOC_WIDTH: usize = 78;
+
+/// The indent for body text under a Vim help entry.
+const VIMDOC_INDENT: &str = "    ";
+
 #[derive(Clone, Copy)]
 pub enum Formats {
     Markdown,
     Terminal,
+    Vimdoc,
+}
+
+impl Formats {
+    /// The file extension used when writing documentation in this format to disk. Returns `None`
+    /// for formats that are only meant for display.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Self::Markdown => Some("md"),
+            Self::Terminal => None,
+            Self::Vimdoc => Some("txt"),
+        }
+    }
+}
+
+/// Return the short description for a doc string, which is its first line.
+pub fn short_doc(docs: &str) -> &str {
+    docs.lines().next().unwrap_or_default().trim()
+}
+
+/// A highlight group entry: the full group name, a description of its definition, and its docs.
+struct HlEntry<'a> {
+    name: String,
+    definition: String,
+    docs: &'a str,
+}
+
+/// Collect the highlight definitions and links for a plugin, with their full names.
+fn hl_entries<'a>(name: &str, hl: &'a highlights::Highlights) -> Result<Vec<HlEntry<'a>>> {
+    let defs = hl.highlights.iter().map(|(n, h)| (n, h.to_string()));
+    let links = hl
+        .links
+        .iter()
+        .map(|(n, dst)| (n, format!("links to {dst}")));
+    defs.chain(links)
+        .map(|(n, definition)| {
+            Ok(HlEntry {
+                name: full_name(name, n)?,
+                definition,
+                docs: hl.doc(n),
+            })
+        })
+        .collect()
+}
+
+/// Collect the methods that have autocommands attached.
+fn autocmds(methods: &[Method]) -> Vec<(&Method, &AutoCmd)> {
+    methods
+        .iter()
+        .filter_map(|m| m.autocmd.as_ref().map(|a| (m, a)))
+        .collect()
+}
+
+/// Describe the events and patterns that trigger an autocommand, e.g. "BufEnter,BufLeave *.rs".
+fn autocmd_trigger(a: &AutoCmd) -> String {
+    let mut ret = a.events.join(",");
+    if !a.patterns.is_empty() {
+        ret.push(' ');
+        ret.push_str(&a.patterns.join(","));
+    }
+    ret
+}
+
+/// Format a listing line, using the short description if there are docs.
+fn listing(item: &str, detail: &str, docs: &str) -> String {
+    if docs.is_empty() {
+        format!("{item}: {detail}")
+    } else {
+        format!("{item}: {} ({detail})", short_doc(docs))
+    }
 }
 
 fn render_text_markdown(
     name: &str,
     docs: &str,
-    hl: highlights::Highlights,
+    hl: &highlights::Highlights,
     methods: Vec<Method>,
 ) -> Result<String> {
     let mut ret = format!("# {name}\n");
@@ -27,11 +103,19 @@ fn render_text_markdown(
     }
     if !hl.is_empty() {
         ret.push_str("\n## Highlights\n\n");
-        for (n, hl) in hl.highlights {
-            ret.push_str(&format!("* {}: {hl}\n", full_name(name, &n)?));
+        for e in hl_entries(name, hl)? {
+            ret.push_str(&format!("* {}\n", listing(&e.name, &e.definition, e.docs)));
         }
-        for (n, dst) in hl.links {
-            ret.push_str(&format!("* {}: {dst}\n", full_name(name, &n)?));
+    }
+    let acmds = autocmds(&methods);
+    if !acmds.is_empty() {
+        ret.push_str("\n## Autocommands\n\n");
+        for (m, a) in acmds {
+            let target = format!("calls {name}.{}", m.name);
+            ret.push_str(&format!(
+                "* {}\n",
+                listing(&autocmd_trigger(a), &target, &a.docs)
+            ));
         }
     }
     if !methods.is_empty() {
@@ -55,7 +139,7 @@ fn render_text_markdown(
 fn render_text_terminal(
     name: &str,
     docs: &str,
-    hl: highlights::Highlights,
+    hl: &highlights::Highlights,
     methods: Vec<Method>,
 ) -> Result<String> {
     use std::io::Write;
@@ -94,20 +178,29 @@ fn render_text_terminal(
         writeln!(&mut buffer, "{}", "-".repeat(10))?;
         buffer.reset()?;
 
-        for (n, hl) in hl.highlights {
-            let full_name = full_name(name, &n)?;
+        for e in hl_entries(name, hl)? {
             buffer.set_color(&hl_style)?;
-            write!(&mut buffer, "\n{full_name}")?;
+            write!(&mut buffer, "\n{}", e.name)?;
             buffer.reset()?;
-            write!(&mut buffer, ": {hl}")?;
+            let line = listing("", &e.definition, e.docs);
+            write!(&mut buffer, "{line}")?;
         }
+    }
+
+    // Autocommands
+    let acmds = autocmds(&methods);
+    if !acmds.is_empty() {
+        buffer.set_color(&heading_style)?;
+        writeln!(&mut buffer, "\n\nAutocommands")?;
+        writeln!(&mut buffer, "{}", "-".repeat(12))?;
+        buffer.reset()?;
 
-        for (n, dst) in hl.links {
-            let full_name = full_name(name, &n)?;
+        for (m, a) in acmds {
             buffer.set_color(&hl_style)?;
-            write!(&mut buffer, "\n{full_name}")?;
+            write!(&mut buffer, "\n{}", autocmd_trigger(a))?;
             buffer.reset()?;
-            write!(&mut buffer, ": {dst}")?;
+            let line = listing("", &format!("calls {name}.{}", m.name), &a.docs);
+            write!(&mut buffer, "{line}")?;
         }
     }
 
@@ -139,15 +232,268 @@ fn render_text_terminal(
     })
 }
 
+/// Re-flow text to a given width, treating blank lines as paragraph breaks.
+fn wrap(text: &str, width: usize, indent: &str) -> String {
+    let opts = textwrap::Options::new(width)
+        .initial_indent(indent)
+        .subsequent_indent(indent);
+    text.split("\n\n")
+        .map(|para| {
+            let para = para.split_whitespace().collect::<Vec<_>>().join(" ");
+            textwrap::fill(&para, &opts)
+        })
+        .filter(|para| !para.trim().is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Render a Vim help line with `left` at the start, and `tags` right-aligned to the text width.
+/// If both don't fit on one line, the tags get a right-aligned line of their own before `left`,
+/// as in Neovim's own help files.
+fn vimdoc_tagline(left: &str, tags: &str) -> String {
+    if left.len() + tags.len() < VIMDOC_WIDTH {
+        let pad = VIMDOC_WIDTH - left.len() - tags.len();
+        format!("{left}{}{tags}\n", " ".repeat(pad))
+    } else {
+        let pad = VIMDOC_WIDTH.saturating_sub(tags.len());
+        format!("{}{tags}\n{left}\n", " ".repeat(pad))
+    }
+}
+
+/// Render a Vim help section separator and heading.
+fn vimdoc_heading(num: usize, title: &str, tags: &str) -> String {
+    format!(
+        "{}\n{}",
+        "=".repeat(VIMDOC_WIDTH),
+        vimdoc_tagline(&format!("{num}. {title}"), tags)
+    )
+}
+
+fn render_text_vimdoc(
+    name: &str,
+    docs: &str,
+    hl: &highlights::Highlights,
+    methods: Vec<Method>,
+) -> Result<String> {
+    // Each section is a (title, tags, body) tuple.
+    let mut sections = vec![(
+        "Introduction",
+        format!("*{name}* *{name}-introduction*"),
+        wrap(docs, VIMDOC_WIDTH, ""),
+    )];
+
+    if !hl.is_empty() {
+        let mut body = String::new();
+        for e in hl_entries(name, hl)? {
+            body.push_str(&vimdoc_tagline(&e.name, &format!("*{}*", e.name)));
+            body.push_str(&wrap(&e.definition, VIMDOC_WIDTH, VIMDOC_INDENT));
+            body.push_str("\n\n");
+            if !e.docs.is_empty() {
+                body.push_str(&wrap(e.docs, VIMDOC_WIDTH, VIMDOC_INDENT));
+                body.push_str("\n\n");
+            }
+        }
+        sections.push(("Highlights", format!("*{name}-highlights*"), body));
+    }
+
+    let acmds = autocmds(&methods);
+    if !acmds.is_empty() {
+        let mut body = String::new();
+        for (m, a) in acmds {
+            let tag = format!("*{name}-autocmd-{}*", m.name);
+            body.push_str(&vimdoc_tagline(&autocmd_trigger(a), &tag));
+            if !a.docs.is_empty() {
+                body.push_str(&wrap(&a.docs, VIMDOC_WIDTH, VIMDOC_INDENT));
+                body.push_str("\n\n");
+            }
+            body.push_str(&format!("{VIMDOC_INDENT}Calls |{name}.{}()|.\n\n", m.name));
+        }
+        sections.push(("Autocommands", format!("*{name}-autocommands*"), body));
+    }
+
+    let methods: Vec<Method> = methods
+        .into_iter()
+        .filter(|m| matches!(m.method_type, MethodType::Request | MethodType::Notify))
+        .collect();
+    if !methods.is_empty() {
+        let mut body = String::new();
+        for m in &methods {
+            let args = m
+                .args
+                .iter()
+                .map(|a| format!("{{{}}}", a.name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            let sig = format!("{name}.{}({args})", m.name);
+            body.push_str(&vimdoc_tagline(&sig, &format!("*{name}.{}()*", m.name)));
+            if !m.docs.is_empty() {
+                body.push_str(&wrap(&m.docs, VIMDOC_WIDTH, VIMDOC_INDENT));
+                body.push('\n');
+            }
+            body.push('\n');
+        }
+        sections.push(("Methods", format!("*{name}-methods*"), body));
+    }
+
+    let mut ret = format!("*{name}.txt*  {}\n\n", short_doc(docs));
+
+    ret.push_str(&"=".repeat(VIMDOC_WIDTH));
+    ret.push('\n');
+    ret.push_str(&vimdoc_tagline("CONTENTS", &format!("*{name}-contents*")));
+    ret.push('\n');
+    for (i, (title, _, _)) in sections.iter().enumerate() {
+        let entry = format!("{VIMDOC_INDENT}{}. {title}", i + 1);
+        let link = format!("|{name}-{}|", title.to_lowercase());
+        let dots = VIMDOC_WIDTH.saturating_sub(entry.len() + link.len() + 2);
+        ret.push_str(&format!("{entry} {} {link}\n", ".".repeat(dots)));
+    }
+
+    for (i, (title, tags, body)) in sections.iter().enumerate() {
+        ret.push('\n');
+        ret.push_str(&vimdoc_heading(i + 1, title, tags));
+        ret.push('\n');
+        ret.push_str(body.trim_end());
+        ret.push('\n');
+    }
+
+    ret.push_str(&format!(
+        "\n vim:tw={VIMDOC_WIDTH}:ts=8:noet:ft=help:norl:\n"
+    ));
+    Ok(ret)
+}
+
 pub fn render_docs(
     fmt: Formats,
     name: &str,
     docs: &str,
-    hl: highlights::Highlights,
+    hl: &highlights::Highlights,
     methods: Vec<Method>,
 ) -> Result<String> {
     match fmt {
         Formats::Markdown => render_text_markdown(name, docs, hl, methods),
         Formats::Terminal => render_text_terminal(name, docs, hl, methods),
+        Formats::Vimdoc => render_text_vimdoc(name, docs, hl, methods),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macro_types::{Arg, AutoCmd, Return};
+
+    use super::*;
+    use crate::highlights::{Highlights, Hl};
+
+    fn method(name: &str, docs: &str, args: &[&str]) -> Method {
+        Method {
+            name: name.into(),
+            docs: docs.into(),
+            ret: Return::ResultVoid,
+            method_type: MethodType::Request,
+            args: args
+                .iter()
+                .map(|a| Arg {
+                    name: a.to_string(),
+                    typ: "String".into(),
+                })
+                .collect(),
+            autocmd: None,
+            is_mut: false,
+        }
+    }
+
+    #[test]
+    fn test_render_vimdoc() {
+        let long = "word ".repeat(40);
+        let hl = Highlights::new()
+            .hl("Normal", Hl::new().bold(true))
+            .link("Title", "Normal");
+        let out = render_docs(
+            Formats::Vimdoc,
+            "myplugin",
+            &format!("A short description.\n\n{long}"),
+            &hl,
+            vec![method("greet", &long, &["name", "count"])],
+        )
+        .unwrap();
+
+        assert!(out.starts_with("*myplugin.txt*  A short description.\n"));
+        assert!(out.contains("*myplugin-contents*"));
+        assert!(out.contains("|myplugin-introduction|"));
+        assert!(out.contains("|myplugin-highlights|"));
+        assert!(out.contains("|myplugin-methods|"));
+        assert!(out.contains("*myplugin* *myplugin-introduction*"));
+        assert!(out.contains("*myplugin.greet()*"));
+        assert!(out.contains("myplugin.greet({name}, {count})"));
+        assert!(out.contains("*myplugin-highlights*"));
+        assert!(out.ends_with(" vim:tw=78:ts=8:noet:ft=help:norl:\n"));
+        for line in out.lines() {
+            assert!(line.len() <= VIMDOC_WIDTH, "line too long: {line:?}");
+        }
+    }
+
+    fn documented() -> (Highlights, Vec<Method>) {
+        let hl = Highlights::new()
+            .hl_doc(
+                "Normal",
+                Hl::new().bold(true),
+                "Plain text.\n\nUsed for everything else.",
+            )
+            .link("Title", "Normal");
+        let mut m = method("on_enter", "Handle enter.", &[]);
+        m.autocmd = Some(AutoCmd {
+            events: vec!["BufEnter".into(), "BufLeave".into()],
+            patterns: vec!["*.rs".into()],
+            group: None,
+            nested: false,
+            docs: "Track Rust buffers.\n\nMore detail.".into(),
+        });
+        (hl, vec![m])
+    }
+
+    #[test]
+    fn test_render_hl_and_autocmd_docs() {
+        let (hl, methods) = documented();
+        let out = render_docs(Formats::Vimdoc, "p", "", &hl, methods).unwrap();
+        assert!(out.contains("|p-autocommands|"));
+        assert!(out.contains("*p-autocmd-on_enter*"));
+        assert!(out.contains("BufEnter,BufLeave *.rs"));
+        assert!(out.contains("    More detail."));
+        assert!(out.contains("    Calls |p.on_enter()|."));
+        assert!(out.contains("    Used for everything else."));
+        assert!(out.contains("    links to Normal"));
+
+        let (hl, methods) = documented();
+        let out = render_docs(Formats::Markdown, "p", "", &hl, methods).unwrap();
+        assert!(out.contains("* p_Normal: Plain text. (bold)\n"));
+        assert!(out.contains("* p_Title: links to Normal\n"));
+        assert!(out.contains("## Autocommands"));
+        assert!(out.contains("* BufEnter,BufLeave *.rs: Track Rust buffers. (calls p.on_enter)\n"));
+        assert!(!out.contains("More detail."));
+    }
+
+    #[test]
+    fn test_vimdoc_tagline() {
+        assert_eq!(
+            vimdoc_tagline("foo()", "*foo()*"),
+            format!("foo(){}*foo()*\n", " ".repeat(VIMDOC_WIDTH - 12))
+        );
+        let sig = format!("p.long({})", "{argument}, ".repeat(5));
+        let out = vimdoc_tagline(&sig, "*p.long()*");
+        assert_eq!(
+            out,
+            format!("{}*p.long()*\n{sig}\n", " ".repeat(VIMDOC_WIDTH - 10))
+        );
+        for line in out.lines() {
+            assert!(line.len() <= VIMDOC_WIDTH, "line too long: {line:?}");
+        }
+    }
+
+    #[test]
+    fn test_render_vimdoc_omits_empty_sections() {
+        let out = render_docs(Formats::Vimdoc, "myplugin", "", &Highlights::new(), vec![]).unwrap();
+        assert!(out.contains("|myplugin-introduction|"));
+        assert!(!out.contains("|myplugin-highlights|"));
+        assert!(!out.contains("|myplugin-methods|"));
+        assert!(!out.contains("|myplugin-autocommands|"));
     }
 }
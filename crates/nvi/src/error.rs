@@ -4,41 +4,318 @@
 
 #![allow(clippy::absolute_paths)]
 
-use std::{convert::Infallible, io, result};
+use std::{convert::Infallible, fmt, io, result};
+
+use serde_derive::{Deserialize, Serialize};
+
+use crate::Value;
+
+/// The marker that precedes the JSON-encoded [`PluginError`] in an error returned to Neovim.
+pub const PLUGIN_ERROR_MARKER: &str = "nvi-error:";
 
 /// Standard Result type for Nvi operations, defaulting to the Nvi Error type.
 pub type Result<T, E = Error> = result::Result<T, E>;
 
+/// A boxed error, kept as the `source()` of an [`Error`].
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// Nvi standard error types
 #[derive(thiserror::Error, Debug)]
 pub enum Error {
     /// Error connecting to a Neovim instance
     #[error("connection error: {msg:}")]
-    Connect { msg: String },
+    Connect {
+        msg: String,
+        #[source]
+        source: Option<BoxError>,
+    },
     /// Error decoding MessagePack data from Neovim
     #[error("decoding error: {msg:}")]
-    Decode { msg: String },
+    Decode {
+        msg: String,
+        #[source]
+        source: Option<BoxError>,
+    },
     /// Error encoding MessagePack data for Neovim
     #[error("encoding error: {msg:}")]
-    Encode { msg: String },
+    Encode {
+        msg: String,
+        #[source]
+        source: Option<BoxError>,
+    },
     /// File system or network I/O error
     #[error("io error: {msg:}")]
-    IO { msg: String },
+    IO {
+        msg: String,
+        #[source]
+        source: Option<BoxError>,
+    },
     /// Error returned from a Neovim RPC call
-    #[error("remote error: {0:}")]
-    RemoteError(rmpv::Value),
+    #[error("remote error: {0}")]
+    Remote(#[from] RemoteError),
+    /// A Neovim API function that the connected Neovim is too old to support
+    #[error("unsupported: {function} requires Neovim API level {required_level}")]
+    Unsupported {
+        function: String,
+        required_level: u64,
+    },
     /// Internal error in the Nvi library
     #[error("internal: {msg:}")]
     Internal { msg: String },
     /// An error caused by the user through invalid input
     #[error("{0}")]
     User(String),
+    /// An error raised by plugin code, with a kind that Lua callers can match on. Usually
+    /// produced from a plugin's own error type with `#[derive(ErrorCode)]`.
+    #[error("{message}")]
+    Plugin {
+        kind: String,
+        message: String,
+        data: Option<serde_json::Value>,
+    },
+    /// An error with a description of what was being done when it occurred, added with
+    /// [`Context::context`]
+    #[error("{context}: {source}")]
+    Context {
+        context: String,
+        #[source]
+        source: Box<Self>,
+    },
+}
+
+/// The kind of a [`RemoteError`], as reported by Neovim.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum RemoteErrorKind {
+    /// A failure while executing the call, e.g. a Vimscript or Lua error
+    Exception,
+    /// The call was rejected before execution, e.g. for an invalid buffer or argument
+    Validation,
+}
+
+/// An error returned by Neovim, decoded from its `[type, message]` error response.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub struct RemoteError {
+    /// Whether the call failed validation or raised an exception
+    pub kind: RemoteErrorKind,
+    /// The error message
+    pub message: String,
+    /// The API method that failed, if known
+    pub method: Option<String>,
+}
+
+impl RemoteError {
+    /// Decode an error response from Neovim. Responses that aren't in the usual `[type,
+    /// message]` form are treated as exceptions, with the value as the message.
+    pub fn from_value(method: Option<&str>, v: &Value) -> Self {
+        let (kind, message) = match v.as_array().map(Vec::as_slice) {
+            Some([typ, msg]) if msg.is_str() => (
+                match typ.as_u64() {
+                    Some(1) => RemoteErrorKind::Validation,
+                    _ => RemoteErrorKind::Exception,
+                },
+                msg.as_str().unwrap_or_default().to_string(),
+            ),
+            _ => (
+                RemoteErrorKind::Exception,
+                match v.as_str() {
+                    Some(s) => s.to_string(),
+                    None => v.to_string(),
+                },
+            ),
+        };
+        Self {
+            kind,
+            message,
+            method: method.map(String::from),
+        }
+    }
+}
+
+impl fmt::Display for RemoteError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(method) = &self.method {
+            write!(f, "{method}: ")?;
+        }
+        write!(f, "{}: {}", self.kind, self.message)
+    }
+}
+
+impl Error {
+    /// A short, stable name for the kind of error, reported to Lua callers as `err.kind`.
+    pub fn kind(&self) -> &str {
+        match self {
+            Self::Connect { .. } => "connect",
+            Self::Decode { .. } => "decode",
+            Self::Encode { .. } => "encode",
+            Self::IO { .. } => "io",
+            Self::Remote(_) => "remote",
+            Self::Unsupported { .. } => "unsupported",
+            Self::Internal { .. } => "internal",
+            Self::User(_) => "user",
+            Self::Plugin { kind, .. } => kind,
+            Self::Context { source, .. } => source.kind(),
+        }
+    }
+
+    /// Convert an RPC error, recording the API method that failed.
+    pub fn rpc(method: &str, e: mrpc::RpcError) -> Self {
+        match e {
+            mrpc::RpcError::Service(e) => RemoteError::from_value(Some(method), &e.value).into(),
+            e => Self::from(e).context(format!("calling {method}")),
+        }
+    }
+
+    /// Wrap this error with a description of what was being done when it occurred.
+    pub fn context(self, context: impl Into<String>) -> Self {
+        Self::Context {
+            context: context.into(),
+            source: Box::new(self),
+        }
+    }
+
+    /// The innermost error, beneath any context.
+    pub fn root(&self) -> &Self {
+        match self {
+            Self::Context { source, .. } => source.root(),
+            e => e,
+        }
+    }
+}
+
+/// Add context to errors, in the style of `anyhow::Context`.
+///
+/// ```ignore
+/// pane.show(client).await.context("while opening pane")?;
+/// ```
+pub trait Context<T> {
+    /// Wrap the error with a description of what was being done when it occurred.
+    fn context(self, context: impl Into<String>) -> Result<T>;
+
+    /// Like [`Context::context`], but only builds the description if there is an error.
+    fn with_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C;
+}
+
+impl<T, E> Context<T> for result::Result<T, E>
+where
+    E: Into<Error>,
+{
+    fn context(self, context: impl Into<String>) -> Result<T> {
+        self.map_err(|e| e.into().context(context))
+    }
+
+    fn with_context<C, F>(self, f: F) -> Result<T>
+    where
+        C: Into<String>,
+        F: FnOnce() -> C,
+    {
+        self.map_err(|e| e.into().context(f()))
+    }
+}
+
+/// Maps a plugin's error type onto the error kinds reported to Lua callers. This is usually
+/// derived with `#[derive(ErrorCode)]`, which also implements `From<E>` for [`Error`].
+pub trait ErrorCode: fmt::Display {
+    /// The error kind for this value.
+    fn code(&self) -> &'static str;
+
+    /// Structured data to attach to the error, passed to Lua callers as its `data` field.
+    fn data(&self) -> Option<serde_json::Value> {
+        None
+    }
+}
+
+/// A structured error from a plugin request, returned to the Lua caller.
+///
+/// The generated Lua wrappers for request methods decode this and raise it with `error()` as a
+/// table with `kind`, `message`, `plugin`, `method` and `data` fields, so callers can inspect it
+/// with `pcall`. Converted to a string, it reads `plugin.method: message`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PluginError {
+    /// The error kind - see [`Error::kind`]
+    pub kind: String,
+    /// A human-readable description of the error
+    pub message: String,
+    /// The name of the plugin that raised the error
+    pub plugin: String,
+    /// The method that raised the error
+    pub method: String,
+    /// Optional structured data attached to the error
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+}
+
+impl PluginError {
+    /// Construct a plugin error from an error raised by `method`.
+    pub fn new(plugin: &str, method: &str, e: impl Into<Error>) -> Self {
+        let e = e.into();
+        let kind = e.kind().to_string();
+        let data = match e.root() {
+            Error::Plugin { data, .. } => data.clone(),
+            _ => None,
+        };
+        let message = match e {
+            Error::Plugin { message, .. } => message,
+            e => e.to_string(),
+        };
+        Self {
+            kind,
+            message,
+            plugin: plugin.into(),
+            method: method.into(),
+            data,
+        }
+    }
+
+    /// Construct a plugin error and encode it as an RPC error value in one step. This is the
+    /// form used by the `nvi_plugin` macro.
+    pub fn value(plugin: &str, method: &str, e: impl Into<Error>) -> Value {
+        Self::new(plugin, method, e).to_value()
+    }
+
+    /// Encode the error as an RPC error value. Neovim only passes strings through to the caller,
+    /// so this is a readable message followed by [`PLUGIN_ERROR_MARKER`] and the error as JSON.
+    pub fn to_value(&self) -> Value {
+        let json = serde_json::to_string(self).unwrap_or_default();
+        Value::from(format!("{self}\n{PLUGIN_ERROR_MARKER}{json}"))
+    }
+
+    /// Decode an error value produced by [`Self::to_value`]. Returns `None` if the value is not
+    /// an nvi plugin error.
+    pub fn from_value(v: &Value) -> Option<Self> {
+        let (_, json) = v.as_str()?.rsplit_once(PLUGIN_ERROR_MARKER)?;
+        serde_json::from_str(json).ok()
+    }
+}
+
+impl fmt::Display for PluginError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}: {}", self.plugin, self.method, self.message)
+    }
+}
+
+// The plugin and method are kept as context, so the error still says where it came from.
+impl From<PluginError> for Error {
+    fn from(e: PluginError) -> Self {
+        Self::Context {
+            context: format!("{}.{}", e.plugin, e.method),
+            source: Box::new(Self::Plugin {
+                message: e.message,
+                kind: e.kind,
+                data: e.data,
+            }),
+        }
+    }
 }
 
 impl From<serde_rmpv::Error> for Error {
     fn from(e: serde_rmpv::Error) -> Self {
         Self::Decode {
             msg: format!("serde: {e}"),
+            source: Some(Box::new(e)),
         }
     }
 }
@@ -47,6 +324,7 @@ impl From<rmp::encode::ValueWriteError> for Error {
     fn from(e: rmp::encode::ValueWriteError) -> Self {
         Self::Encode {
             msg: format!("{e}"),
+            source: Some(Box::new(e)),
         }
     }
 }
@@ -55,14 +333,17 @@ impl From<rmp::decode::ValueReadError> for Error {
     fn from(e: rmp::decode::ValueReadError) -> Self {
         Self::Decode {
             msg: format!("value read: {e}"),
+            source: Some(Box::new(e)),
         }
     }
 }
 
 impl From<rmp::decode::DecodeStringError<'_>> for Error {
     fn from(e: rmp::decode::DecodeStringError) -> Self {
+        // The error borrows from the input, so it can't be kept as the source.
         Self::Decode {
             msg: format!("rmp decode: {e}"),
+            source: None,
         }
     }
 }
@@ -71,6 +352,7 @@ impl From<rmpv::decode::Error> for Error {
     fn from(e: rmpv::decode::Error) -> Self {
         Self::Decode {
             msg: format!("rmpv decode: {e}"),
+            source: Some(Box::new(e)),
         }
     }
 }
@@ -85,6 +367,7 @@ impl From<io::Error> for Error {
     fn from(e: io::Error) -> Self {
         Self::IO {
             msg: format!("{e}"),
+            source: Some(Box::new(e)),
         }
     }
 }
@@ -92,12 +375,14 @@ impl From<io::Error> for Error {
 impl From<mrpc::RpcError> for Error {
     fn from(e: mrpc::RpcError) -> Self {
         match e {
-            mrpc::RpcError::Service(e) => Self::RemoteError(e.value),
+            mrpc::RpcError::Service(e) => RemoteError::from_value(None, &e.value).into(),
             mrpc::RpcError::Connect { source } => Self::Connect {
                 msg: source.to_string(),
+                source: Some(Box::new(source)),
             },
             mrpc::RpcError::Disconnect { source } => Self::Connect {
-                msg: format!("Disconnected: {:?}", source),
+                msg: format!("Disconnected: {source}"),
+                source: Some(Box::new(source)),
             },
             e => Self::Internal {
                 msg: format!("{e:?}"),
@@ -105,3 +390,79 @@ impl From<mrpc::RpcError> for Error {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error as _;
+
+    use super::*;
+
+    #[test]
+    fn test_remote_error() {
+        let v = Value::Array(vec![Value::from(1), Value::from("Invalid buffer id: 99")]);
+        let e = RemoteError::from_value(Some("nvim_buf_get_lines"), &v);
+        assert_eq!(e.kind, RemoteErrorKind::Validation);
+        assert_eq!(e.message, "Invalid buffer id: 99");
+        assert_eq!(
+            e.to_string(),
+            "nvim_buf_get_lines: validation: Invalid buffer id: 99"
+        );
+
+        let v = Value::Array(vec![Value::from(0), Value::from("Vim:E492")]);
+        let e = RemoteError::from_value(None, &v);
+        assert_eq!(e.kind, RemoteErrorKind::Exception);
+        assert_eq!(e.to_string(), "exception: Vim:E492");
+
+        let e = RemoteError::from_value(None, &Value::from("plain"));
+        assert_eq!(e.kind, RemoteErrorKind::Exception);
+        assert_eq!(e.message, "plain");
+    }
+
+    #[test]
+    fn test_context() {
+        let r: Result<()> = Err(io::Error::other("disk on fire")).context("while opening pane");
+        let e = r.unwrap_err();
+        assert_eq!(e.to_string(), "while opening pane: io error: disk on fire");
+        assert_eq!(e.kind(), "io");
+        assert!(matches!(e.root(), Error::IO { .. }));
+
+        // The chain runs through the context to the original io::Error
+        let io = e.source().unwrap().source().unwrap();
+        assert_eq!(io.to_string(), "disk on fire");
+        assert!(io.downcast_ref::<io::Error>().is_some());
+    }
+
+    #[test]
+    fn test_plugin_error_round_trip() {
+        let e = PluginError::new(
+            "my_plugin",
+            "open",
+            Error::Plugin {
+                kind: "not_found".into(),
+                message: "no such file".into(),
+                data: Some(serde_json::json!({"path": "/tmp/x"})),
+            },
+        );
+        let v = e.to_value();
+        assert!(
+            v.as_str()
+                .unwrap()
+                .starts_with("my_plugin.open: no such file\n")
+        );
+        assert_eq!(PluginError::from_value(&v), Some(e.clone()));
+
+        // Converting back keeps the plugin and method as context
+        let e = Error::from(e);
+        assert_eq!(e.to_string(), "my_plugin.open: no such file");
+        assert_eq!(e.kind(), "not_found");
+        assert!(matches!(e.root(), Error::Plugin { message, .. } if message == "no such file"));
+
+        let e = PluginError::new("my_plugin", "open", Error::User("bad input".into()));
+        assert_eq!(e.kind, "user");
+        assert_eq!(e.data, None);
+        assert_eq!(PluginError::from_value(&e.to_value()), Some(e));
+
+        assert_eq!(PluginError::from_value(&Value::from("plain error")), None);
+        assert_eq!(PluginError::from_value(&Value::Nil), None);
+    }
+}
@@ -0,0 +1,231 @@
+//! Typed events between plugins and Lua.
+//!
+//! Lua sends an event to a plugin with `vim.rpcnotify(chan, "my_event", data)`, and the plugin
+//! receives the decoded payloads from [`Client::subscribe`](crate::Client::subscribe). Only
+//! notifications that aren't plugin methods are delivered to subscribers. In the other direction,
+//! [`Client::emit_user_event`](crate::Client::emit_user_event) fires the `User` autocmds matching
+//! the event name, with the payload in `ev.data`.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    marker::PhantomData,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, ready},
+};
+
+use futures::Stream;
+use serde::de::DeserializeOwned;
+use tokio::sync::mpsc;
+use tracing::warn;
+
+use crate::Value;
+
+/// The subscribers to events on a connection, by event name.
+#[derive(Clone, Default)]
+pub(crate) struct Subscriptions(Arc<Mutex<HashMap<String, Vec<mpsc::UnboundedSender<Value>>>>>);
+
+impl Subscriptions {
+    /// Add a subscriber to an event.
+    pub(crate) fn add(&self, event: &str) -> mpsc::UnboundedReceiver<Value> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.0
+            .lock()
+            .unwrap()
+            .entry(event.into())
+            .or_default()
+            .push(tx);
+        rx
+    }
+
+    /// Send the payload of an event to its subscribers, dropping any that have gone away.
+    /// Returns false if the event has no subscribers. A notification with a single parameter
+    /// has that parameter as its payload; otherwise the payload is the array of parameters.
+    pub(crate) fn publish(&self, event: &str, params: &[Value]) -> bool {
+        let mut subs = self.0.lock().unwrap();
+        let Some(senders) = subs.get_mut(event) else {
+            return false;
+        };
+        let payload = match params {
+            [payload] => payload.clone(),
+            _ => Value::Array(params.to_vec()),
+        };
+        senders.retain(|tx| tx.send(payload.clone()).is_ok());
+        if senders.is_empty() {
+            subs.remove(event);
+            return false;
+        }
+        true
+    }
+
+    /// Drop every subscriber, ending their streams.
+    pub(crate) fn clear(&self) {
+        self.0.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Debug for Subscriptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let subs = self.0.lock().unwrap();
+        f.debug_tuple("Subscriptions")
+            .field(&subs.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+/// A stream of the decoded payloads of an event, returned by
+/// [`Client::subscribe`](crate::Client::subscribe). Payloads that don't decode as `T` are logged
+/// and skipped. The stream ends when the connection closes.
+pub struct Subscription<T> {
+    /// The name of the event
+    event: String,
+    /// Receives the raw payloads
+    rx: mpsc::UnboundedReceiver<Value>,
+    /// The payload type
+    _payload: PhantomData<fn() -> T>,
+}
+
+impl<T> Subscription<T> {
+    pub(crate) fn new(event: &str, rx: mpsc::UnboundedReceiver<Value>) -> Self {
+        Self {
+            event: event.into(),
+            rx,
+            _payload: PhantomData,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        loop {
+            let Some(v) = ready!(self.rx.poll_recv(cx)) else {
+                return Poll::Ready(None);
+            };
+            match serde_rmpv::from_value(&v) {
+                Ok(payload) => return Poll::Ready(Some(payload)),
+                Err(e) => warn!("skipping {} event that doesn't decode: {e}", self.event),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use futures::StreamExt;
+    use serde_derive::{Deserialize, Serialize};
+    use tokio::sync::mpsc;
+    use tracing_test::traced_test;
+
+    use super::*;
+    use crate::{Client, NviPlugin, error::Result, lua, test};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Point {
+        x: i64,
+        y: i64,
+    }
+
+    #[tokio::test]
+    async fn test_publish() {
+        let subs = Subscriptions::default();
+        assert!(!subs.publish("point", &[]));
+
+        let mut points = Subscription::<Point>::new("point", subs.add("point"));
+        let mut raw = Subscription::<Vec<i64>>::new("point", subs.add("point"));
+        let point = Value::Map(vec![
+            (Value::from("x"), Value::from(1)),
+            (Value::from("y"), Value::from(2)),
+        ]);
+        assert!(subs.publish("point", &[Value::from("nope")]));
+        assert!(subs.publish("point", &[point]));
+        assert!(subs.publish("point", &[Value::from(3), Value::from(4)]));
+        subs.clear();
+
+        assert_eq!(points.next().await, Some(Point { x: 1, y: 2 }));
+        assert_eq!(points.next().await, Some(Point { x: 3, y: 4 }));
+        assert_eq!(points.next().await, None);
+        assert_eq!(raw.next().await, Some(vec![3, 4]));
+        assert_eq!(raw.next().await, None);
+
+        // Subscribers that have gone away are dropped
+        drop(subs.add("gone"));
+        assert!(!subs.publish("gone", &[]));
+    }
+
+    struct EventPlugin {
+        /// Sends the plugin's channel ID, and then each event it receives
+        tx: mpsc::UnboundedSender<(u64, Option<Point>)>,
+    }
+
+    #[async_trait::async_trait]
+    impl NviPlugin for EventPlugin {
+        fn name(&self) -> String {
+            "event_plugin".into()
+        }
+
+        async fn connected(&mut self, client: &mut Client) -> Result<()> {
+            let mut points = client.subscribe::<Point>("point");
+            let tx = self.tx.clone();
+            tokio::spawn(async move {
+                while let Some(p) = points.next().await {
+                    tx.send((0, Some(p))).unwrap();
+                }
+            });
+            self.tx.send((client.channel_id, None)).unwrap();
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    #[traced_test]
+    async fn it_delivers_events() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let nvit = test::NviTest::builder()
+            .with_plugin(EventPlugin { tx })
+            .run()
+            .await
+            .unwrap();
+        let (chan, _) = rx.recv().await.unwrap();
+
+        // Lua to plugin
+        let _: () = nvit
+            .client
+            .nvim
+            .exec_lua(
+                "
+                local chan = ...
+                vim.rpcnotify(chan, 'point', 'nope')
+                vim.rpcnotify(chan, 'point', { x = 1, y = 2 })
+                ",
+                vec![Value::from(chan)],
+            )
+            .await
+            .unwrap();
+        assert_eq!(rx.recv().await.unwrap().1, Some(Point { x: 1, y: 2 }));
+        assert!(logs_contain("skipping point event"));
+
+        // Plugin to Lua
+        let _: () = lua!(
+            nvit.client,
+            "
+            vim.api.nvim_create_autocmd('User', {
+                pattern = 'point',
+                callback = function(ev) _G.got = ev.data end,
+            })
+            "
+        )
+        .await
+        .unwrap();
+        nvit.client
+            .emit_user_event("point", &Point { x: 3, y: 4 })
+            .await
+            .unwrap();
+        let got: Point = lua!(nvit.client, "return _G.got").await.unwrap();
+        assert_eq!(got, Point { x: 3, y: 4 });
+
+        nvit.finish().await.unwrap();
+    }
+}
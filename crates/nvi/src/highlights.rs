@@ -14,13 +14,16 @@ use crate::{
     nvim::opts::SetHl,
 };
 
-/// Create a full highlight name by joining a prefix and highlight name with validation.
+/// Create a full highlight name by joining a prefix and highlight name with an underscore, as in
+/// `Client::hl_name`.
 ///
 /// Both the prefix and name are validated according to Neovim highlight group naming rules.
 pub fn full_name(prefix: &str, name: &str) -> Result<String> {
     check_group_name(prefix)?;
     check_group_name(name)?;
-    Ok(format!("{prefix}{name}"))
+    let name = format!("{prefix}_{name}");
+    check_group_name(&name)?;
+    Ok(name)
 }
 
 impl fmt::Display for Hl {
@@ -197,20 +200,7 @@ impl Hl {
             underline: self.underline,
             reverse: self.reverse,
             strikethrough: self.strikethrough,
-            sp: None,
-            blend: None,
-            standout: None,
-            undercurl: None,
-            underdouble: None,
-            underdotted: None,
-            underdashed: None,
-            nocombine: None,
-            link: None,
-            default: None,
-            ctermfg: None,
-            ctermbg: None,
-            cterm: None,
-            force: None,
+            ..Default::default()
         }
     }
 }
@@ -225,6 +215,9 @@ pub struct Highlights {
     pub highlights: Vec<(String, Hl)>,
     /// A list of highlight links.
     pub links: Vec<(String, String)>,
+    /// Documentation for highlight groups, keyed by group name. The first line of each doc string
+    /// is used as a short description in listings.
+    pub docs: Vec<(String, String)>,
 }
 
 impl Highlights {
@@ -233,6 +226,7 @@ impl Highlights {
         Self {
             highlights: Vec::new(),
             links: Vec::new(),
+            docs: Vec::new(),
         }
     }
 
@@ -250,6 +244,14 @@ impl Highlights {
         self
     }
 
+    /// Adds a documented highlight definition to the collection. The doc string describes what the
+    /// group styles, and is included in the plugin documentation.
+    pub fn hl_doc(mut self, name: &str, h: Hl, doc: &str) -> Self {
+        self = self.hl(name, h);
+        self.docs.push((name.into(), doc.into()));
+        self
+    }
+
     /// Creates a link from one highlight group to another.
     ///
     /// Both group names are validated according to Neovim naming rules.
@@ -260,6 +262,23 @@ impl Highlights {
         self
     }
 
+    /// Creates a documented link from one highlight group to another.
+    pub fn link_doc(mut self, new_group: &str, existing_group: &str, doc: &str) -> Self {
+        self = self.link(new_group, existing_group);
+        self.docs.push((new_group.into(), doc.into()));
+        self
+    }
+
+    /// Returns the doc string for a highlight group, or an empty string if it has none.
+    pub fn doc(&self, name: &str) -> &str {
+        self.docs
+            .iter()
+            .rev()
+            .find(|(n, _)| n == name)
+            .map(|(_, d)| d.as_str())
+            .unwrap_or_default()
+    }
+
     /// Creates all highlights and links in the collection.
     ///
     /// The client's name is prepended to all highlight group names to provide namespacing.
@@ -383,7 +402,12 @@ mod tests {
         highlights.create(&test.client).await.unwrap();
 
         // Get the highlight definitions
-        let hl: HashMap<String, Value> = test.client.nvim.get_hl(0, HashMap::new()).await.unwrap();
+        let hl: HashMap<String, Value> = test
+            .client
+            .nvim
+            .get_hl(0, Default::default())
+            .await
+            .unwrap();
 
         // Check the highlight group
         let test_hl = hl.get("test_TestHl").unwrap().as_map().unwrap();
@@ -0,0 +1,401 @@
+//! Host several plugins in one process and one connection.
+//!
+//! A [`PluginHost`] is itself an [`NviPlugin`], so it runs with any of the functions in
+//! [`connect`](crate::connect), or with [`cmd::run`](crate::cmd::run):
+//!
+//! ```ignore
+//! let host = PluginHost::new("suite")
+//!     .with_plugin(Formatter::default())?
+//!     .with_plugin(Linter::default())?;
+//! nvi::cmd::run(host, None).await
+//! ```
+//!
+//! Each hosted plugin keeps its own Lua namespace, highlights and autocmds. Its functions call
+//! the host with `<plugin>.<method>`, and the host routes the call by that prefix. Hosted plugins
+//! are locked separately, so a long `&mut self` handler in one doesn't hold up the others, and
+//! failures and panics in one plugin are reported against it alone. Each plugin's `connected`
+//! method runs in its own task, so the host's connection is never held up waiting for them. The host's status request
+//! reports the status of every plugin, and each plugin also answers its own, e.g.
+//! `formatter.__nvi_status()`.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use tokio::sync::RwLock;
+use tracing::{error, warn};
+
+use crate::{
+    Client, NviPlugin, SET_LOG_LEVEL, STATUS_MESSAGE, Status, StatusReport, Value,
+    error::{Error, PluginError, Result},
+    logging, method_mutability,
+    nvim::types,
+    panics::{self, Panic},
+};
+
+/// The status of a hosted plugin, kept outside the plugin's lock.
+#[derive(Debug)]
+struct HostedState {
+    /// The status of the plugin
+    status: Mutex<Status>,
+    /// The number of panics caught in the plugin's handlers
+    panics: AtomicU64,
+}
+
+impl HostedState {
+    fn report(&self) -> StatusReport {
+        StatusReport {
+            status: *self.status.lock().unwrap(),
+            panics: self.panics.load(Ordering::Relaxed),
+            plugins: BTreeMap::new(),
+        }
+    }
+}
+
+/// A handle on the status of every plugin in a host, shared with the host's connections. Status
+/// requests read it without locking any plugin, so they are answered even while a `&mut self`
+/// handler is running.
+#[derive(Debug, Clone, Default)]
+pub struct HostStatus {
+    /// The status of each hosted plugin, by name
+    plugins: Vec<(String, Arc<HostedState>)>,
+}
+
+impl HostStatus {
+    /// The status of each hosted plugin, by name.
+    pub fn report(&self) -> BTreeMap<String, StatusReport> {
+        self.plugins
+            .iter()
+            .map(|(name, state)| (name.clone(), state.report()))
+            .collect()
+    }
+}
+
+/// A plugin in a host, with its own lock and status.
+struct Hosted {
+    /// The plugin name, which is also the prefix of its methods
+    name: String,
+    /// The plugin
+    plugin: RwLock<Box<dyn NviPlugin>>,
+    /// A map of method names to their mutability
+    methods: HashMap<String, bool>,
+    /// The status of the plugin, shared with the host's status handle
+    state: Arc<HostedState>,
+}
+
+impl Hosted {
+    /// Make a client for this plugin from the host's client.
+    fn client(&self, client: &Client) -> Client {
+        let mut client = client.clone();
+        client.name.clone_from(&self.name);
+        client.method_prefix = format!("{}.", self.name);
+        client
+    }
+
+    fn status(&self) -> Status {
+        *self.state.status.lock().unwrap()
+    }
+
+    fn set_status(&self, status: Status) {
+        *self.state.status.lock().unwrap() = status;
+    }
+
+    fn report(&self) -> StatusReport {
+        self.state.report()
+    }
+
+    /// Run the plugin's `connected` method, marking it running if it succeeds and stopped if it
+    /// fails or panics.
+    async fn connected(&self, mut client: Client) {
+        let mut plugin = self.plugin.write().await;
+        match panics::catch(plugin.connected(&mut client)).await {
+            Ok(Ok(())) => self.set_status(Status::Running),
+            Ok(Err(e)) => {
+                warn!("connected() of {} failed: {:?}", self.name, e);
+                self.set_status(Status::Stopped);
+            }
+            Err(p) => {
+                self.handle_panic("connected", &p);
+                self.set_status(Status::Stopped);
+            }
+        }
+    }
+
+    /// Record a panic caught in one of the plugin's handlers, and convert it to an error.
+    fn handle_panic(&self, method: &str, p: &Panic) -> Error {
+        self.state.panics.fetch_add(1, Ordering::Relaxed);
+        error!("{} handler {method} {p}\n{}", self.name, p.backtrace);
+        Error::Plugin {
+            kind: "panic".into(),
+            message: p.to_string(),
+            data: None,
+        }
+    }
+}
+
+/// A plugin that hosts several others over a single connection.
+pub struct PluginHost {
+    /// The name of the host
+    name: String,
+    /// The hosted plugins, in the order they were added. Each is shared with the task running its
+    /// `connected` method.
+    plugins: Vec<Arc<Hosted>>,
+}
+
+impl PluginHost {
+    /// Create an empty host. The name is used for the host's own Lua namespace and log buffer.
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.into(),
+            plugins: vec![],
+        }
+    }
+
+    /// Add a plugin to the host. Plugin names must be unique, and differ from the host's name.
+    pub fn with_plugin<T: NviPlugin>(mut self, plugin: T) -> Result<Self> {
+        let name = plugin.name();
+        if name == self.name || self.plugins.iter().any(|h| h.name == name) {
+            return Err(Error::User(format!(
+                "plugin {name} is already hosted by {}",
+                self.name
+            )));
+        }
+        self.plugins.push(Arc::new(Hosted {
+            name,
+            methods: method_mutability(&plugin),
+            plugin: RwLock::new(Box::new(plugin)),
+            state: Arc::new(HostedState {
+                status: Mutex::new(Status::Stopped),
+                panics: AtomicU64::new(0),
+            }),
+        }));
+        Ok(self)
+    }
+
+    /// The names of the hosted plugins.
+    pub fn names(&self) -> Vec<String> {
+        self.plugins.iter().map(|h| h.name.clone()).collect()
+    }
+
+    /// Find the plugin a method is addressed to, and the method name within it.
+    fn route<'a>(&self, method: &'a str) -> Option<(&Hosted, &'a str)> {
+        let (name, method) = method.split_once('.')?;
+        let hosted = self.plugins.iter().find(|h| h.name == name)?;
+        Some((hosted.as_ref(), method))
+    }
+
+    /// The error for a method that no running plugin can handle.
+    fn unroutable(&self, method: &str) -> Error {
+        match self.route(method) {
+            Some((h, _)) => Error::User(format!("plugin {} is not running", h.name)),
+            None => Error::User(format!("no plugin in {} handles {method}", self.name)),
+        }
+    }
+
+    /// Find the running plugin a method is addressed to.
+    fn route_running<'a>(&self, method: &'a str) -> Result<(&Hosted, &'a str)> {
+        self.route(method)
+            .filter(|(h, _)| h.status() != Status::Stopped)
+            .ok_or_else(|| self.unroutable(method))
+    }
+}
+
+#[async_trait]
+impl NviPlugin for PluginHost {
+    fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// Bootstrap every hosted plugin with its own client. A plugin that fails to bootstrap is
+    /// left stopped, and the others carry on.
+    async fn bootstrap(&self, client: &mut Client) -> Result<()> {
+        for h in &self.plugins {
+            let mut c = h.client(client);
+            let ret = panics::catch(Box::pin(async {
+                h.plugin.read().await.bootstrap(&mut c).await
+            }))
+            .await;
+            match ret {
+                Ok(Ok(())) => h.set_status(Status::Connected),
+                Ok(Err(e)) => warn!("bootstrap of {} failed: {:?}", h.name, e),
+                Err(p) => {
+                    h.handle_panic("bootstrap", &p);
+                }
+            }
+        }
+        client
+            .register_rpcrequest::<String>(&self.name, STATUS_MESSAGE, &[])
+            .await?;
+        client
+            .register_rpcrequest(&self.name, SET_LOG_LEVEL, &["level"])
+            .await?;
+        Ok(())
+    }
+
+    /// Start the `connected` method of every bootstrapped plugin in its own task, and return
+    /// without waiting for them. Each plugin is marked running once its method returns.
+    async fn connected(&mut self, client: &mut Client) -> Result<()> {
+        for h in &self.plugins {
+            if h.status() == Status::Connected {
+                let c = h.client(client);
+                let h = h.clone();
+                tokio::spawn(async move { h.connected(c).await });
+            }
+        }
+        Ok(())
+    }
+
+    async fn request(
+        &self,
+        client: &mut Client,
+        method: &str,
+        params: &[Value],
+    ) -> Result<Value, Value> {
+        let (h, name) = match self.route(method) {
+            Some(route) => route,
+            None => {
+                return Err(PluginError::value(
+                    &self.name,
+                    method,
+                    self.unroutable(method),
+                ));
+            }
+        };
+        if name == STATUS_MESSAGE {
+            return serde_rmpv::to_value(&h.report())
+                .map_err(|e| PluginError::value(&h.name, name, e));
+        }
+        if name == SET_LOG_LEVEL && !h.methods.contains_key(name) {
+            let level = params.first().and_then(Value::as_str).unwrap_or_default();
+            return logging::set_level(level)
+                .map(|()| Value::Nil)
+                .map_err(|e| PluginError::value(&h.name, name, e));
+        }
+        let (h, name) = self
+            .route_running(method)
+            .map_err(|e| PluginError::value(&self.name, method, e))?;
+
+        let mut c = h.client(client);
+        let ret = if h.methods.get(name).copied().unwrap_or(false) {
+            let mut plugin = h.plugin.write().await;
+            panics::catch(plugin.request_mut(&mut c, name, params)).await
+        } else {
+            let plugin = h.plugin.read().await;
+            panics::catch(plugin.request(&mut c, name, params)).await
+        };
+        match ret {
+            Ok(ret) => ret,
+            Err(p) => Err(PluginError::value(&h.name, name, h.handle_panic(name, &p))),
+        }
+    }
+
+    async fn notify(&self, client: &mut Client, method: &str, params: &[Value]) -> Result<()> {
+        let (h, name) = self.route_running(method)?;
+        let mut c = h.client(client);
+        let ret = if h.methods.get(name).copied().unwrap_or(false) {
+            let mut plugin = h.plugin.write().await;
+            panics::catch(plugin.notify_mut(&mut c, name, params)).await
+        } else {
+            let plugin = h.plugin.read().await;
+            panics::catch(plugin.notify(&mut c, name, params)).await
+        };
+        ret.unwrap_or_else(|p| Err(h.handle_panic(name, &p)))
+    }
+
+    /// Neovim's error events can't be traced to a plugin, so each running plugin gets them all.
+    async fn error_event(&self, client: &mut Client, error: types::ErrorEvent) -> Result<()> {
+        for h in &self.plugins {
+            if h.status() == Status::Stopped {
+                continue;
+            }
+            let mut c = h.client(client);
+            let plugin = h.plugin.read().await;
+            match panics::catch(plugin.error_event(&mut c, error.clone())).await {
+                Ok(Ok(())) => (),
+                Ok(Err(e)) => warn!("error_event() of {} failed: {:?}", h.name, e),
+                Err(p) => {
+                    h.handle_panic("error_event", &p);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Ask the plugin that raised the error, falling back to the default if it's busy.
+    fn notify_error(&self, error: &PluginError, request: bool) -> bool {
+        let hosted = self
+            .plugins
+            .iter()
+            .find(|h| h.name == error.plugin)
+            .map(AsRef::as_ref)
+            .or_else(|| self.route(&error.method).map(|(h, _)| h));
+        match hosted.and_then(|h| h.plugin.try_read().ok()) {
+            Some(plugin) => plugin.notify_error(error, request),
+            None => !request,
+        }
+    }
+
+    fn hosted_status(&self) -> HostStatus {
+        HostStatus {
+            plugins: self
+                .plugins
+                .iter()
+                .map(|h| (h.name.clone(), h.state.clone()))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A plugin that does nothing, named `name`.
+    struct Named(&'static str);
+
+    #[async_trait]
+    impl NviPlugin for Named {
+        fn name(&self) -> String {
+            self.0.into()
+        }
+    }
+
+    #[test]
+    fn test_routing() {
+        let host = PluginHost::new("suite")
+            .with_plugin(Named("alpha"))
+            .unwrap()
+            .with_plugin(Named("beta"))
+            .unwrap();
+        assert_eq!(host.names(), vec!["alpha", "beta"]);
+
+        let (h, method) = host.route("beta.ping").unwrap();
+        assert_eq!((h.name.as_str(), method), ("beta", "ping"));
+        assert!(host.route("gamma.ping").is_none());
+        assert!(host.route("ping").is_none());
+
+        // Plugins are stopped until they bootstrap
+        assert!(host.route_running("beta.ping").is_err());
+        h.set_status(Status::Running);
+        assert!(host.route_running("beta.ping").is_ok());
+
+        let status = host.hosted_status().report();
+        assert_eq!(status["alpha"].status, Status::Stopped);
+        assert_eq!(status["beta"].status, Status::Running);
+
+        let dup = PluginHost::new("suite")
+            .with_plugin(Named("alpha"))
+            .unwrap();
+        assert!(dup.with_plugin(Named("alpha")).is_err());
+        assert!(
+            PluginHost::new("suite")
+                .with_plugin(Named("suite"))
+                .is_err()
+        );
+    }
+}
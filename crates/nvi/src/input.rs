@@ -398,6 +398,24 @@ impl KeyPress {
     }
 }
 
+/// Translate raw key bytes to Vim's key notation with `keytrans()`.
+async fn keytrans(client: &Client, bytes: &[u8]) -> Result<String, Error> {
+    // Pass the raw bytes directly to keytrans
+    let lua_keytrans = format!(
+        "return vim.fn.keytrans('{}')",
+        bytes.iter().fold(String::new(), |mut acc, &b| {
+            let _ = write!(acc, "\\x{b:02x}");
+            acc
+        })
+    );
+    match lua!(client, &lua_keytrans).await? {
+        Value::String(s) => s
+            .into_str()
+            .ok_or_else(|| Error::User("keytrans returned invalid UTF-8".to_string())),
+        _ => Err(Error::User("keytrans did not return a string".to_string())),
+    }
+}
+
 /// Get a single keypress from the client.
 pub async fn get_keypress(client: &Client) -> Result<KeyPress, Error> {
     let lua_code = r#"
@@ -417,27 +435,16 @@ pub async fn get_keypress(client: &Client) -> Result<KeyPress, Error> {
                 };
 
                 match &arr[1] {
-                    Value::String(s) => KeyPress::from_lua_with_mods(
-                        modifiers,
-                        s.as_str().expect("Lua string conversion failed"),
-                    ),
+                    Value::String(s) => match s.as_str() {
+                        Some(s) => KeyPress::from_lua_with_mods(modifiers, s),
+                        // Special keys come through as strings that aren't valid UTF-8
+                        None => KeyPress::from_lua_with_mods(
+                            modifiers,
+                            &keytrans(client, s.as_bytes()).await?,
+                        ),
+                    },
                     Value::Binary(bytes) => {
-                        // For binary data, we need to use vim.fn.keytrans to get the key notation
-                        // Pass the raw bytes directly to keytrans
-                        let lua_keytrans = format!(
-                            "return vim.fn.keytrans('{}')",
-                            bytes.iter().fold(String::new(), |mut acc, &b| {
-                                let _ = write!(acc, "\\x{b:02x}");
-                                acc
-                            })
-                        );
-                        match lua!(client, &lua_keytrans).await? {
-                            Value::String(s) => KeyPress::from_lua_with_mods(
-                                modifiers,
-                                s.as_str().expect("Lua string conversion failed"),
-                            ),
-                            _ => Err(Error::User("keytrans did not return a string".to_string())),
-                        }
+                        KeyPress::from_lua_with_mods(modifiers, &keytrans(client, bytes).await?)
                     }
                     _ => Err(Error::User(
                         "Unexpected type for keypress value".to_string(),
@@ -5,25 +5,32 @@
 #![allow(missing_docs)]
 
 mod client;
+mod panics;
 mod process;
 mod service;
 
+pub mod auth;
 pub mod cmd;
 pub mod connect;
 pub mod demo;
 pub mod docs;
 pub mod error;
+pub mod events;
 pub mod highlights;
+pub mod host;
 pub mod input;
+pub mod logging;
 pub mod lua;
+pub mod manifest;
 pub mod nvim;
 pub mod test;
 pub mod ui;
+pub mod vimhelp;
 
 // Re-export, because we use this in our derive code
 #[doc(hidden)]
 pub use async_trait;
-pub use client::Client;
+pub use client::{Client, ConnectionId, Peers};
 // Re-export consistent color names
 pub use colornames::Color;
 pub use macro_types;
@@ -32,5 +39,7 @@ pub use nvi_macros;
 // AutocmdEvent is special, because it's used in the user event API
 pub use nvim::types::AutocmdEvent;
 #[doc(hidden)]
+pub use serde_json;
+#[doc(hidden)]
 pub use serde_rmpv;
 pub use service::*;
@@ -0,0 +1,626 @@
+//! Forward plugin logs into Neovim.
+//!
+//! A plugin runs headless under the editor, so logging to stdout is of little use. [`NvimLayer`]
+//! is a `tracing_subscriber` layer that collects the plugin's log events instead. Once the plugin
+//! connects, every event is appended to a log buffer named `nvi://<plugin>/log`, and events at or
+//! above the notify level are also shown with `vim.notify`, rate-limited so that a noisy plugin
+//! can't flood the editor. The `:<Plugin>Log` command opens the log buffer in a split, and takes
+//! modifiers like `:vertical`.
+//!
+//! Logs are also written to `stdpath('log')/nvi/<plugin>.log`, rotated by size. The level is
+//! set with an env-filter directive in `NVI_LOG` (e.g. `NVI_LOG=debug` or
+//! `NVI_LOG=my_plugin=trace`), and can be changed in a running session with the built-in
+//! `set_log_level` request, e.g. `:lua my_plugin.set_log_level('debug')`. The filter belongs to
+//! the process, so in a plugin host, changing the level through one plugin changes it for all of
+//! them; use target directives like `my_plugin=debug` to narrow it.
+//!
+//! The standard command line calls [`init`] for `connect` and `run`. To install only the Neovim
+//! layer yourself:
+//!
+//! ```ignore
+//! tracing_subscriber::registry()
+//!     .with(nvi::logging::Logging::default().notify_level(Level::ERROR).layer())
+//!     .init();
+//! ```
+
+use std::{
+    env, fmt,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    mem,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock},
+    time::{Duration, Instant},
+};
+
+use derive_setters::Setters;
+use tokio::sync::{mpsc, watch};
+use tracing::{
+    Event, Level, Subscriber,
+    field::{Field, Visit},
+    warn,
+};
+use tracing_subscriber::{
+    EnvFilter, Registry, filter::LevelFilter, fmt as fmt_layer, layer, prelude::*, reload,
+};
+
+use crate::{
+    Client,
+    error::{Error, Result},
+    nvim::{
+        options::Opt,
+        opts,
+        types::{Buffer, LogLevel},
+    },
+};
+
+/// The environment variable holding the log filter, in `tracing_subscriber` env-filter syntax.
+pub const ENV_VAR: &str = "NVI_LOG";
+
+/// The forwarder for the most recently created layer, waiting for the plugin to connect.
+static PENDING: Mutex<Option<Forwarder>> = Mutex::new(None);
+
+/// The handle used to change the log filter at runtime, set by [`init`].
+static FILTER: OnceLock<reload::Handle<EnvFilter, Registry>> = OnceLock::new();
+
+tokio::task_local! {
+    /// Set while the forwarder runs, so that events from its own API calls aren't forwarded.
+    static FORWARDING: bool;
+}
+
+/// Configuration for plugin logging.
+#[derive(Debug, Clone, Setters)]
+pub struct Logging {
+    /// The level logged when `NVI_LOG` is not set. Default: `INFO`.
+    pub level: LevelFilter,
+    /// The size at which the log file is rotated. Default: 10 MiB.
+    pub max_file_size: u64,
+    /// The number of rotated log files kept, as `<plugin>.log.1` to `<plugin>.log.N`. Default: 3.
+    pub max_files: usize,
+    /// Events at or above this level are shown with `vim.notify`. Default: `WARN`.
+    pub notify_level: Level,
+    /// The maximum number of notifications shown per `notify_interval`. Default: 5.
+    pub notify_burst: u32,
+    /// The window over which notifications are rate-limited. Default: 1 second.
+    pub notify_interval: Duration,
+    /// The maximum number of lines kept in the log buffer. Default: 10000.
+    pub max_lines: usize,
+    /// Also write events to stderr. Default: false.
+    pub stderr: bool,
+}
+
+impl Default for Logging {
+    fn default() -> Self {
+        Self {
+            level: LevelFilter::INFO,
+            max_file_size: 10 * 1024 * 1024,
+            max_files: 3,
+            notify_level: Level::WARN,
+            notify_burst: 5,
+            notify_interval: Duration::from_secs(1),
+            max_lines: 10_000,
+            stderr: false,
+        }
+    }
+}
+
+impl Logging {
+    /// Create the tracing layer. Events are queued until the plugin connects.
+    pub fn layer(self) -> NvimLayer {
+        let (tx, rx) = mpsc::unbounded_channel();
+        PENDING.lock().unwrap().replace(Forwarder {
+            rx,
+            unsent: vec![],
+            config: self,
+        });
+        NvimLayer { tx }
+    }
+
+    /// Install the global tracing subscriber for a plugin: a rotated log file in
+    /// `stdpath('log')/nvi/<plugin>.log`, the Neovim layer and, if enabled, stderr. The filter
+    /// comes from `NVI_LOG` if it is set, and can be changed later with [`set_level`]. If the log
+    /// file can't be opened, the other layers are still installed and a warning is logged.
+    pub fn init(self, plugin: &str) -> Result<()> {
+        let file = log_dir()
+            .ok_or_else(|| Error::Internal {
+                msg: "can't find the Neovim log directory".into(),
+            })
+            .and_then(|dir| {
+                RotatingFile::open(
+                    dir.join("nvi").join(format!("{plugin}.log")),
+                    self.max_file_size,
+                    self.max_files,
+                )
+                .map_err(Error::from)
+            });
+        let filter = match env::var(ENV_VAR) {
+            Ok(directives) => EnvFilter::try_new(directives)
+                .map_err(|e| Error::User(format!("invalid {ENV_VAR} filter: {e}")))?,
+            Err(_) => EnvFilter::default().add_directive(self.level.into()),
+        };
+        let (filter, handle) = reload::Layer::new(filter);
+        let (file, file_err) = match file {
+            Ok(f) => (Some(f), None),
+            Err(e) => (None, Some(e)),
+        };
+        let stderr = self.stderr.then(|| {
+            fmt_layer::layer()
+                .without_time()
+                .with_target(true)
+                .with_writer(io::stderr)
+        });
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(file.map(|f| {
+                fmt_layer::layer()
+                    .with_ansi(false)
+                    .with_writer(Mutex::new(f))
+            }))
+            .with(stderr)
+            .with(self.layer())
+            .try_init()
+            .map_err(|e| Error::Internal {
+                msg: format!("error installing the log subscriber: {e}"),
+            })?;
+        // The subscriber can only be installed once, so neither can the handle.
+        let _ = FILTER.set(handle);
+        if let Some(e) = file_err {
+            warn!("not writing a log file: {e}");
+        }
+        Ok(())
+    }
+}
+
+/// Create a tracing layer with the default configuration.
+pub fn layer() -> NvimLayer {
+    Logging::default().layer()
+}
+
+/// Install the global tracing subscriber for a plugin with the default configuration. See
+/// [`Logging::init`].
+pub fn init(plugin: &str) -> Result<()> {
+    Logging::default().init(plugin)
+}
+
+/// Change the log filter of a running plugin. This takes a level like `debug`, or any
+/// env-filter directives, like `NVI_LOG`. Fails if logging wasn't set up with [`init`]. The filter
+/// is shared by everything in the process, including every plugin in a plugin host.
+pub fn set_level(directives: &str) -> Result<()> {
+    let handle = FILTER
+        .get()
+        .ok_or_else(|| Error::User("logging was not initialized with nvi::logging::init".into()))?;
+    let filter = EnvFilter::try_new(directives)
+        .map_err(|e| Error::User(format!("invalid log filter {directives:?}: {e}")))?;
+    handle.reload(filter).map_err(|e| Error::Internal {
+        msg: format!("error changing the log filter: {e}"),
+    })
+}
+
+/// The directory that Neovim's `stdpath('log')` points to. This is worked out from the
+/// environment rather than asked of Neovim, because logging starts before the plugin connects.
+pub fn log_dir() -> Option<PathBuf> {
+    let app = env::var("NVIM_APPNAME").unwrap_or_else(|_| "nvim".into());
+    let non_empty = |v: &String| !v.is_empty();
+    if cfg!(windows) {
+        let base = env::var("LOCALAPPDATA").ok().filter(non_empty)?;
+        return Some(PathBuf::from(base).join(format!("{app}-data")));
+    }
+    let base = match env::var("XDG_STATE_HOME").ok().filter(non_empty) {
+        Some(d) => PathBuf::from(d),
+        None => PathBuf::from(env::var("HOME").ok().filter(non_empty)?)
+            .join(".local")
+            .join("state"),
+    };
+    Some(base.join(app))
+}
+
+/// A log file that is rotated when it reaches a maximum size. Rotated files are named by
+/// appending `.1`, `.2` and so on, with `.1` the most recent.
+#[derive(Debug)]
+pub struct RotatingFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    max_size: u64,
+    max_files: usize,
+}
+
+impl RotatingFile {
+    /// Open a log file for appending, creating it and its directory if needed.
+    pub fn open(path: impl AsRef<Path>, max_size: u64, max_files: usize) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        let size = file.metadata()?.len();
+        Ok(Self {
+            path,
+            file,
+            size,
+            max_size,
+            max_files,
+        })
+    }
+
+    /// The path of the `n`th rotated file.
+    fn rotated(&self, n: usize) -> PathBuf {
+        let mut p = self.path.clone().into_os_string();
+        p.push(format!(".{n}"));
+        p.into()
+    }
+
+    /// Shift the rotated files up by one, drop the oldest, and start a new file.
+    fn rotate(&mut self) -> io::Result<()> {
+        if self.max_files > 0 {
+            for n in (1..self.max_files).rev() {
+                let from = self.rotated(n);
+                if from.exists() {
+                    fs::rename(&from, self.rotated(n + 1))?;
+                }
+            }
+            fs::rename(&self.path, self.rotated(1))?;
+        }
+        self.file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .truncate(true)
+            .open(&self.path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+impl Write for RotatingFile {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if self.size > 0 && self.size + buf.len() as u64 > self.max_size {
+            self.rotate()?;
+        }
+        let n = self.file.write(buf)?;
+        self.size += n as u64;
+        Ok(n)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.file.flush()
+    }
+}
+
+/// The name of the log buffer for a plugin.
+pub fn buffer_name(plugin: &str) -> String {
+    format!("nvi://{plugin}/log")
+}
+
+/// The name of the command that opens a plugin's log buffer: the plugin name in CamelCase,
+/// followed by `Log`, e.g. `:MyPluginLog` for `my_plugin`.
+pub fn command_name(plugin: &str) -> String {
+    let mut name: String = plugin
+        .split(['_', '-'])
+        .map(|w| {
+            let mut c = w.chars();
+            match c.next() {
+                Some(first) => first.to_uppercase().chain(c).collect(),
+                None => String::new(),
+            }
+        })
+        .collect();
+    name.push_str("Log");
+    name
+}
+
+/// A single log event.
+#[derive(Debug, Clone)]
+struct Record {
+    level: Level,
+    target: String,
+    message: String,
+}
+
+impl Record {
+    /// The lines this record occupies in the log buffer.
+    fn lines(&self) -> Vec<String> {
+        format!("{:>5} {}: {}", self.level, self.target, self.message)
+            .lines()
+            .map(String::from)
+            .collect()
+    }
+}
+
+/// Collects the message and fields of an event.
+#[derive(Default)]
+struct MessageVisitor {
+    message: String,
+    fields: String,
+}
+
+impl Visit for MessageVisitor {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.fields.push_str(&format!(" {}={value}", field.name()));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            self.message.push_str(&format!("{value:?}"));
+        } else {
+            self.fields
+                .push_str(&format!(" {}={value:?}", field.name()));
+        }
+    }
+}
+
+/// A tracing layer that forwards events to Neovim. Events from the RPC transport and from the
+/// forwarder itself are skipped, so that forwarding can't feed back into itself.
+#[derive(Debug)]
+pub struct NvimLayer {
+    tx: mpsc::UnboundedSender<Record>,
+}
+
+impl<S: Subscriber> layer::Layer<S> for NvimLayer {
+    fn on_event(&self, event: &Event<'_>, _ctx: layer::Context<'_, S>) {
+        let meta = event.metadata();
+        if meta.target().starts_with("mrpc") || FORWARDING.try_with(|f| *f).unwrap_or(false) {
+            return;
+        }
+        let mut v = MessageVisitor::default();
+        event.record(&mut v);
+        let _ = self.tx.send(Record {
+            level: *meta.level(),
+            target: meta.target().to_string(),
+            message: v.message + &v.fields,
+        });
+    }
+}
+
+/// Limits notifications to a burst per interval, counting the ones that were dropped.
+#[derive(Debug)]
+struct RateLimiter {
+    burst: u32,
+    interval: Duration,
+    window_start: Option<Instant>,
+    count: u32,
+    suppressed: u32,
+}
+
+impl RateLimiter {
+    fn new(burst: u32, interval: Duration) -> Self {
+        Self {
+            burst,
+            interval,
+            window_start: None,
+            count: 0,
+            suppressed: 0,
+        }
+    }
+
+    /// Check whether a notification may be sent at `now`. If so, returns the number of
+    /// notifications suppressed since the last one that was sent.
+    fn allow(&mut self, now: Instant) -> Option<u32> {
+        if self
+            .window_start
+            .is_none_or(|s| now.duration_since(s) >= self.interval)
+        {
+            self.window_start = Some(now);
+            self.count = 0;
+        }
+        if self.count < self.burst {
+            self.count += 1;
+            Some(mem::take(&mut self.suppressed))
+        } else {
+            self.suppressed += 1;
+            None
+        }
+    }
+}
+
+/// Convert a tracing level to a Neovim log level.
+fn log_level(level: Level) -> LogLevel {
+    match level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warn,
+        Level::INFO => LogLevel::Info,
+        Level::DEBUG => LogLevel::Debug,
+        Level::TRACE => LogLevel::Trace,
+    }
+}
+
+/// Receives events from the layer and writes them to Neovim.
+struct Forwarder {
+    rx: mpsc::UnboundedReceiver<Record>,
+    /// Events that a failed write didn't get into the buffer, written first on the next
+    /// connection
+    unsent: Vec<Record>,
+    config: Logging,
+}
+
+impl Forwarder {
+    /// Forward events until the connection closes or a write fails, then hand the forwarder
+    /// back so that it can be attached to the next connection. `line_count` is the number of
+    /// lines already in the buffer, or 0 for a new buffer, whose single empty line the first
+    /// write replaces.
+    async fn run(
+        mut self,
+        client: Client,
+        buffer: Buffer,
+        mut line_count: usize,
+        mut closed: watch::Receiver<()>,
+    ) -> Self {
+        let mut limiter = RateLimiter::new(self.config.notify_burst, self.config.notify_interval);
+        let command = command_name(&client.name);
+        loop {
+            let mut batch = mem::take(&mut self.unsent);
+            if batch.is_empty() {
+                tokio::select! {
+                    biased;
+                    // Only ever errors, once the connection is dropped.
+                    _ = closed.changed() => return self,
+                    r = self.rx.recv() => match r {
+                        Some(r) => batch.push(r),
+                        None => return self,
+                    },
+                }
+            }
+            while let Ok(r) = self.rx.try_recv() {
+                batch.push(r);
+            }
+
+            let lines: Vec<String> = batch.iter().flat_map(Record::lines).collect();
+            let start = if line_count == 0 { 0 } else { -1 };
+            let ret = client
+                .nvim
+                .buf_set_lines(
+                    &buffer,
+                    start,
+                    -1,
+                    false,
+                    lines.iter().cloned().map(Into::into).collect(),
+                )
+                .await;
+            if ret.is_err() {
+                self.unsent = batch;
+                return self;
+            }
+            line_count += lines.len();
+            if line_count > self.config.max_lines {
+                let excess = line_count - self.config.max_lines;
+                if client
+                    .nvim
+                    .buf_set_lines(&buffer, 0, excess as i64, false, vec![])
+                    .await
+                    .is_err()
+                {
+                    return self;
+                }
+                line_count = self.config.max_lines;
+            }
+
+            for r in batch.iter().filter(|r| r.level <= self.config.notify_level) {
+                let Some(suppressed) = limiter.allow(Instant::now()) else {
+                    continue;
+                };
+                let mut msg = format!("{}: {}", client.name, r.message);
+                if suppressed > 0 {
+                    msg.push_str(&format!(
+                        "\n({suppressed} earlier messages suppressed, see :{command})"
+                    ));
+                }
+                if client.notify(log_level(r.level), &msg).await.is_err() {
+                    return self;
+                }
+            }
+        }
+    }
+}
+
+/// Start forwarding logs to a connected Neovim, creating the log buffer and the `:<Plugin>Log`
+/// command. Does nothing if no [`NvimLayer`] has been created, or if logs are already being
+/// forwarded. When `closed` signals that the connection has closed, or a write to it fails, the
+/// forwarder waits to be attached to the next connection, keeping any events it couldn't write.
+pub(crate) async fn attach(client: &Client, closed: watch::Receiver<()>) -> Result<()> {
+    let Some(forwarder) = PENDING.lock().unwrap().take() else {
+        return Ok(());
+    };
+    match create_log_buffer(client).await {
+        Ok((buffer, line_count)) => {
+            let client = client.clone();
+            tokio::spawn(async move {
+                let forwarder = FORWARDING
+                    .scope(true, forwarder.run(client, buffer, line_count, closed))
+                    .await;
+                PENDING.lock().unwrap().get_or_insert(forwarder);
+            });
+            Ok(())
+        }
+        Err(e) => {
+            PENDING.lock().unwrap().replace(forwarder);
+            Err(e)
+        }
+    }
+}
+
+/// Create the log buffer and the command that opens it, returning the buffer and the number of
+/// lines already in it. A buffer left by an earlier connection to the same Neovim is reused.
+async fn create_log_buffer(client: &Client) -> Result<(Buffer, usize)> {
+    let name = buffer_name(&client.name);
+    let existing = client.fn_().bufnr(Some(name.clone().into()), None).await?;
+    let (buffer, line_count) = if existing > 0 {
+        let buffer = Buffer::from(existing as u64);
+        let count = client.nvim.buf_line_count(&buffer).await?;
+        (buffer, count as usize)
+    } else {
+        let buffer = client.nvim.create_buf(false, true).await?;
+        client.nvim.buf_set_name(&buffer, &name).await?;
+        buffer.set_opt(client, Opt::Filetype, "log").await?;
+        (buffer, 0)
+    };
+    client
+        .nvim
+        .create_user_command(
+            &command_name(&client.name),
+            format!("<mods> sbuffer {}", u64::from(buffer.clone())),
+            opts::UserCommand::default()
+                .desc(format!("Show the log for {}", client.name).into())
+                .force(true),
+        )
+        .await?;
+    Ok((buffer, line_count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_name() {
+        assert_eq!(command_name("my_plugin"), "MyPluginLog");
+        assert_eq!(command_name("stacks"), "StacksLog");
+        assert_eq!(command_name("nvi-demo"), "NviDemoLog");
+    }
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut l = RateLimiter::new(2, Duration::from_secs(1));
+        let t = Instant::now();
+        assert_eq!(l.allow(t), Some(0));
+        assert_eq!(l.allow(t), Some(0));
+        assert_eq!(l.allow(t), None);
+        assert_eq!(l.allow(t + Duration::from_millis(500)), None);
+        // A new window reports what was dropped in the last one
+        assert_eq!(l.allow(t + Duration::from_secs(1)), Some(2));
+        assert_eq!(l.allow(t + Duration::from_secs(1)), Some(0));
+    }
+
+    #[test]
+    fn test_rotating_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("logs").join("p.log");
+        let mut f = RotatingFile::open(&path, 10, 2).unwrap();
+        for line in ["aaaaaaaa\n", "bbbbbbbb\n", "cccccccc\n", "dddddddd\n"] {
+            f.write_all(line.as_bytes()).unwrap();
+        }
+        f.flush().unwrap();
+        let read = |p: &str| fs::read_to_string(dir.path().join("logs").join(p)).unwrap();
+        assert_eq!(read("p.log"), "dddddddd\n");
+        assert_eq!(read("p.log.1"), "cccccccc\n");
+        assert_eq!(read("p.log.2"), "bbbbbbbb\n");
+        assert!(!dir.path().join("logs").join("p.log.3").exists());
+
+        // Reopening appends to the existing file
+        let mut f = RotatingFile::open(&path, 100, 2).unwrap();
+        f.write_all(b"e\n").unwrap();
+        assert_eq!(read("p.log"), "dddddddd\ne\n");
+    }
+
+    #[test]
+    fn test_record_lines() {
+        let r = Record {
+            level: Level::WARN,
+            target: "my_plugin".into(),
+            message: "one\ntwo".into(),
+        };
+        assert_eq!(r.lines(), vec![" WARN my_plugin: one", "two"]);
+    }
+}
@@ -0,0 +1,195 @@
+//! A machine-readable description of a plugin.
+//!
+//! The manifest is emitted as JSON by `inspect --json`, and is meant to be consumed by external
+//! tooling - plugin managers, editors and code generators. The schema is versioned with
+//! `MANIFEST_VERSION`, which is bumped whenever a field is removed or changes meaning. Adding
+//! fields is not considered a breaking change.
+
+use macro_types::{Method, MethodType};
+use serde_derive::{Deserialize, Serialize};
+
+use crate::{
+    error::{Error, Result},
+    highlights::full_name,
+    service::{NviPlugin, PROTOCOL_VERSION},
+};
+
+/// The version of the manifest schema.
+pub const MANIFEST_VERSION: u64 = 1;
+
+/// A highlight group defined by the plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Highlight {
+    /// The full name of the group, with the plugin name prepended, as created in Neovim.
+    pub name: String,
+    /// A description of the highlight definition, if this is not a link.
+    pub definition: Option<String>,
+    /// The group this group links to, if this is a link.
+    pub link: Option<String>,
+    /// The doc string for the group, describing what it styles.
+    pub docs: String,
+}
+
+/// A machine-readable description of a plugin.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the manifest schema.
+    pub manifest_version: u64,
+    /// The version of the nvi plugin protocol.
+    pub protocol_version: u64,
+    /// The plugin name.
+    pub name: String,
+    /// The plugin version.
+    pub version: String,
+    /// The plugin doc string.
+    pub docs: String,
+    /// The methods exposed over RPC, including their autocommands.
+    pub methods: Vec<Method>,
+    /// The highlight groups defined by the plugin.
+    pub highlights: Vec<Highlight>,
+}
+
+impl Manifest {
+    /// Construct a manifest for a plugin.
+    pub fn new<T: NviPlugin>(plugin: &T) -> Result<Self> {
+        let name = plugin.name();
+        let hl = plugin.highlights()?;
+        let mut highlights = vec![];
+        for (n, h) in &hl.highlights {
+            highlights.push(Highlight {
+                name: full_name(&name, n)?,
+                definition: Some(h.to_string()),
+                link: None,
+                docs: hl.doc(n).into(),
+            });
+        }
+        for (n, dst) in &hl.links {
+            highlights.push(Highlight {
+                name: full_name(&name, n)?,
+                definition: None,
+                link: Some(dst.clone()),
+                docs: hl.doc(n).into(),
+            });
+        }
+        Ok(Self {
+            manifest_version: MANIFEST_VERSION,
+            protocol_version: PROTOCOL_VERSION,
+            version: plugin.version(),
+            docs: plugin.docs()?,
+            methods: plugin
+                .inspect()
+                .into_iter()
+                .filter(|m| matches!(m.method_type, MethodType::Request | MethodType::Notify))
+                .collect(),
+            highlights,
+            name,
+        })
+    }
+
+    /// Render the manifest as pretty-printed JSON.
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| Error::Internal {
+            msg: format!("could not serialize manifest: {e}"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use macro_types::{Arg, AutoCmd, Return};
+    use serde_json::json;
+
+    use super::*;
+    use crate::highlights::{Highlights, Hl};
+
+    struct TestPlugin;
+
+    impl NviPlugin for TestPlugin {
+        fn name(&self) -> String {
+            "test".into()
+        }
+
+        fn version(&self) -> String {
+            "1.2.3".into()
+        }
+
+        fn docs(&self) -> Result<String> {
+            Ok("A test plugin.".into())
+        }
+
+        fn highlights(&self) -> Result<Highlights> {
+            Ok(Highlights::new()
+                .hl_doc("Normal", Hl::new().bold(true), "Normal text.")
+                .link("Title", "Normal"))
+        }
+
+        fn inspect(&self) -> Vec<Method> {
+            vec![
+                Method {
+                    name: "greet".into(),
+                    docs: "Say hello.".into(),
+                    ret: Return::Result("String".into()),
+                    method_type: MethodType::Request,
+                    args: vec![Arg {
+                        name: "name".into(),
+                        typ: "String".into(),
+                    }],
+                    autocmd: Some(AutoCmd {
+                        events: vec!["BufEnter".into()],
+                        patterns: vec!["*.rs".into()],
+                        group: None,
+                        nested: false,
+                        docs: "Greet on enter.".into(),
+                    }),
+                    is_mut: false,
+                },
+                Method {
+                    name: "connected".into(),
+                    docs: "".into(),
+                    ret: Return::ResultVoid,
+                    method_type: MethodType::Connected,
+                    args: vec![],
+                    autocmd: None,
+                    is_mut: true,
+                },
+            ]
+        }
+    }
+
+    #[test]
+    fn test_manifest_json() {
+        let m = Manifest::new(&TestPlugin).unwrap();
+        let v: serde_json::Value = serde_json::from_str(&m.to_json().unwrap()).unwrap();
+        assert_eq!(
+            v,
+            json!({
+                "manifest_version": MANIFEST_VERSION,
+                "protocol_version": PROTOCOL_VERSION,
+                "name": "test",
+                "version": "1.2.3",
+                "docs": "A test plugin.",
+                "methods": [{
+                    "name": "greet",
+                    "docs": "Say hello.",
+                    "ret": {"kind": "result", "type": "String"},
+                    "method_type": "request",
+                    "args": [{"name": "name", "type": "String"}],
+                    "autocmd": {
+                        "events": ["BufEnter"],
+                        "patterns": ["*.rs"],
+                        "group": null,
+                        "nested": false,
+                        "docs": "Greet on enter."
+                    },
+                    "is_mut": false
+                }],
+                "highlights": [
+                    {"name": "test_Normal", "definition": "bold", "link": null, "docs": "Normal text."},
+                    {"name": "test_Title", "definition": null, "link": "Normal", "docs": ""}
+                ]
+            })
+        );
+        let back: Manifest = serde_json::from_value(v).unwrap();
+        assert_eq!(back, m);
+    }
+}
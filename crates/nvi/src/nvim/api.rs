@@ -1,41 +1,85 @@
 #![allow(clippy::needless_question_mark)]
 #![allow(clippy::needless_borrow)]
 #![allow(clippy::doc_lazy_continuation)]
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, OnceLock},
+};
 
 use mrpc::Value;
 use serde::{Serialize, de::DeserializeOwned};
 use tracing::trace;
 
 use super::{opts, types::*};
-use crate::error::Result;
+use crate::error::{Error, Result};
 const NO_PARAMS: [(); 0] = [];
+/// The MessagePack extension type code for Buffer handles
+pub const BUFFER_EXT_TYPE: i8 = 0;
+/// The MessagePack extension type code for Window handles
+pub const WINDOW_EXT_TYPE: i8 = 1;
+/// The MessagePack extension type code for Tabpage handles
+pub const TABPAGE_EXT_TYPE: i8 = 2;
 #[derive(Clone, Debug)]
 /// Generated bindings for Neovim's MessagePack-RPC API.
 pub struct NvimApi {
     pub(crate) rpc_sender: mrpc::RpcSender,
+    /// The API level of the connected Neovim, once detected.
+    pub(crate) api_level: Arc<OnceLock<u64>>,
 }
 impl NvimApi {
-    /// Make a typed request over the MessagePack-RPC protocol.
-    pub async fn rpc_call<Req, Resp>(&self, method: &str, req: Req) -> Result<Resp, mrpc::RpcError>
+    /// Create an API handle for an RPC connection.
+    pub(crate) fn new(rpc_sender: mrpc::RpcSender) -> Self {
+        Self {
+            rpc_sender,
+            api_level: Arc::new(OnceLock::new()),
+        }
+    }
+    /// Query the connected Neovim for its API level. Once the level is known, calling a
+    /// function that is newer than the connected Neovim fails with `Error::Unsupported`,
+    /// rather than an opaque remote error.
+    pub async fn detect_api_level(&self) -> Result<u64> {
+        let (_, info) = self.get_api_info().await?;
+        Ok(*self.api_level.get_or_init(|| info.version.api_level))
+    }
+    /// Check that the connected Neovim supports a function introduced at `required_level`.
+    /// Always succeeds if the API level has not been detected.
+    pub(crate) fn require(&self, function: &str, required_level: u64) -> Result<()> {
+        match self.api_level.get() {
+            Some(level) if *level < required_level => Err(Error::Unsupported {
+                function: function.into(),
+                required_level,
+            }),
+            _ => Ok(()),
+        }
+    }
+    /// Make a typed request over the MessagePack-RPC protocol. Errors record the method that
+    /// failed.
+    pub async fn rpc_call<Req, Resp>(&self, method: &str, req: Req) -> Result<Resp>
     where
         Req: Serialize,
         Resp: DeserializeOwned,
     {
-        let params = mrpc::serialize_params(&req)?;
+        let params = mrpc::serialize_params(&req).map_err(|e| Error::rpc(method, e))?;
         trace!("send request: {:?} {:?}", method, params);
-        let ret = self.rpc_sender.send_request(method, &params).await?;
+        let ret = self
+            .rpc_sender
+            .send_request(method, &params)
+            .await
+            .map_err(|e| Error::rpc(method, e))?;
         trace!("got response for {:?}: {:?}", method, ret);
-        mrpc::deserialize_response(&ret)
+        mrpc::deserialize_response(&ret).map_err(|e| Error::rpc(method, e))
     }
     /// Send a typed notification over the MessagePack-RPC protocol.
-    pub async fn rpc_notify<Req>(&self, method: &str, req: Req) -> Result<(), mrpc::RpcError>
+    pub async fn rpc_notify<Req>(&self, method: &str, req: Req) -> Result<()>
     where
         Req: Serialize,
     {
-        let params = mrpc::serialize_params(&req)?;
+        let params = mrpc::serialize_params(&req).map_err(|e| Error::rpc(method, e))?;
         trace!("send notification: {:?} {:?}", method, params);
-        self.rpc_sender.send_notification(method, &params).await
+        self.rpc_sender
+            .send_notification(method, &params)
+            .await
+            .map_err(|e| Error::rpc(method, e))
     }
     /// Make a raw request over the MessagePack-RPC protocol.
     pub async fn raw_request(
@@ -65,10 +109,11 @@ impl NvimApi {
     ///
     /// NOTE: When multiple patterns or events are provided, it will find all the
     /// autocommands that match any combination of them.
-    pub async fn get_autocmds<T>(&self, opts: HashMap<String, Value>) -> Result<T>
+    pub async fn get_autocmds<T>(&self, opts: opts::GetAutocmds) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_autocmds", 9)?;
         #[allow(unused_variables)]
         let req = opts;
         #[allow(clippy::needless_question_mark)]
@@ -81,33 +126,81 @@ impl NvimApi {
     /// Note: pattern is NOT automatically expanded (unlike with :autocmd),
     /// thus names like $HOME and ~ must be expanded explicitly.
     pub async fn create_autocmd(&self, event: &[Event], opts: opts::CreateAutocmd) -> Result<i64> {
+        self.require("nvim_create_autocmd", 9)?;
         #[allow(unused_variables)]
         let req = (event, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_create_autocmd", req).await?)
     }
+    /// Send [`Self::create_autocmd`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn create_autocmd_notify(
+        &self,
+        event: &[Event],
+        opts: opts::CreateAutocmd,
+    ) -> Result<()> {
+        self.require("nvim_create_autocmd", 9)?;
+        #[allow(unused_variables)]
+        let req = (event, opts);
+        Ok(self.rpc_notify("nvim_create_autocmd", req).await?)
+    }
     /// Deletes an autocommand by id.
     pub async fn del_autocmd(&self, id: i64) -> Result<()> {
+        self.require("nvim_del_autocmd", 9)?;
         #[allow(unused_variables)]
         let req = id;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_autocmd", req).await?)
     }
+    /// Send [`Self::del_autocmd`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_autocmd_notify(&self, id: i64) -> Result<()> {
+        self.require("nvim_del_autocmd", 9)?;
+        #[allow(unused_variables)]
+        let req = id;
+        Ok(self.rpc_notify("nvim_del_autocmd", req).await?)
+    }
     /// Clears all autocommands selected by {opts}. To delete autocmds see
     /// `nvim_del_autocmd()`.
     pub async fn clear_autocmds(&self, opts: opts::ClearAutocmds) -> Result<()> {
+        self.require("nvim_clear_autocmds", 9)?;
         #[allow(unused_variables)]
         let req = opts;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_clear_autocmds", req).await?)
     }
+    /// Send [`Self::clear_autocmds`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn clear_autocmds_notify(&self, opts: opts::ClearAutocmds) -> Result<()> {
+        self.require("nvim_clear_autocmds", 9)?;
+        #[allow(unused_variables)]
+        let req = opts;
+        Ok(self.rpc_notify("nvim_clear_autocmds", req).await?)
+    }
     /// Create or get an autocommand group autocmd-groups.
-    pub async fn create_augroup(&self, name: &str, opts: HashMap<String, Value>) -> Result<i64> {
+    pub async fn create_augroup(&self, name: &str, opts: opts::CreateAugroup) -> Result<i64> {
+        self.require("nvim_create_augroup", 9)?;
         #[allow(unused_variables)]
         let req = (name, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_create_augroup", req).await?)
     }
+    /// Send [`Self::create_augroup`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn create_augroup_notify(&self, name: &str, opts: opts::CreateAugroup) -> Result<()> {
+        self.require("nvim_create_augroup", 9)?;
+        #[allow(unused_variables)]
+        let req = (name, opts);
+        Ok(self.rpc_notify("nvim_create_augroup", req).await?)
+    }
     /// Delete an autocommand group by id.
     ///
     /// To get a group id one can use nvim_get_autocmds().
@@ -116,56 +209,131 @@ impl NvimApi {
     /// autocommands contained in this group will also be deleted and cleared.
     /// This group will no longer exist.
     pub async fn del_augroup_by_id(&self, id: i64) -> Result<()> {
+        self.require("nvim_del_augroup_by_id", 9)?;
         #[allow(unused_variables)]
         let req = id;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_augroup_by_id", req).await?)
     }
+    /// Send [`Self::del_augroup_by_id`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_augroup_by_id_notify(&self, id: i64) -> Result<()> {
+        self.require("nvim_del_augroup_by_id", 9)?;
+        #[allow(unused_variables)]
+        let req = id;
+        Ok(self.rpc_notify("nvim_del_augroup_by_id", req).await?)
+    }
     /// Delete an autocommand group by name.
     ///
     /// NOTE: behavior differs from :augroup-delete. When deleting a group,
     /// autocommands contained in this group will also be deleted and cleared.
     /// This group will no longer exist.
     pub async fn del_augroup_by_name(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_augroup_by_name", 9)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_augroup_by_name", req).await?)
     }
+    /// Send [`Self::del_augroup_by_name`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_augroup_by_name_notify(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_augroup_by_name", 9)?;
+        #[allow(unused_variables)]
+        let req = name;
+        Ok(self.rpc_notify("nvim_del_augroup_by_name", req).await?)
+    }
     /// Execute all autocommands for {event} that match the corresponding {opts}
     /// `autocmd-execute`.
     pub async fn exec_autocmds(&self, event: &[Event], opts: opts::ExecAutocmds) -> Result<()> {
+        self.require("nvim_exec_autocmds", 9)?;
         #[allow(unused_variables)]
         let req = (event, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_exec_autocmds", req).await?)
     }
+    /// Send [`Self::exec_autocmds`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn exec_autocmds_notify(
+        &self,
+        event: &[Event],
+        opts: opts::ExecAutocmds,
+    ) -> Result<()> {
+        self.require("nvim_exec_autocmds", 9)?;
+        #[allow(unused_variables)]
+        let req = (event, opts);
+        Ok(self.rpc_notify("nvim_exec_autocmds", req).await?)
+    }
     /// Returns the number of lines in the given buffer.
     pub async fn buf_line_count(&self, buf: &Buffer) -> Result<i64> {
+        self.require("nvim_buf_line_count", 1)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_line_count", req).await?)
     }
+    /// Send [`Self::buf_line_count`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_line_count_notify(&self, buf: &Buffer) -> Result<()> {
+        self.require("nvim_buf_line_count", 1)?;
+        #[allow(unused_variables)]
+        let req = buf;
+        Ok(self.rpc_notify("nvim_buf_line_count", req).await?)
+    }
     /// Activates buffer-update events on a channel, or as Lua callbacks.
     pub async fn buf_attach(
         &self,
         buf: &Buffer,
         send_buffer: bool,
-        opts: HashMap<String, Value>,
+        opts: opts::BufAttach,
     ) -> Result<bool> {
+        self.require("nvim_buf_attach", 4)?;
         #[allow(unused_variables)]
         let req = (buf, send_buffer, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_attach", req).await?)
     }
+    /// Send [`Self::buf_attach`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_attach_notify(
+        &self,
+        buf: &Buffer,
+        send_buffer: bool,
+        opts: opts::BufAttach,
+    ) -> Result<()> {
+        self.require("nvim_buf_attach", 4)?;
+        #[allow(unused_variables)]
+        let req = (buf, send_buffer, opts);
+        Ok(self.rpc_notify("nvim_buf_attach", req).await?)
+    }
     /// Deactivates buffer-update events on the channel.
     pub async fn buf_detach(&self, buf: &Buffer) -> Result<bool> {
+        self.require("nvim_buf_detach", 4)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_detach", req).await?)
     }
+    /// Send [`Self::buf_detach`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_detach_notify(&self, buf: &Buffer) -> Result<()> {
+        self.require("nvim_buf_detach", 4)?;
+        #[allow(unused_variables)]
+        let req = buf;
+        Ok(self.rpc_notify("nvim_buf_detach", req).await?)
+    }
     /// Gets a line-range from the buffer.
     ///
     /// Indexing is zero-based, end-exclusive. Negative indices are interpreted as
@@ -174,16 +342,14 @@ impl NvimApi {
     ///
     /// Out-of-bounds indices are clamped to the nearest valid value, unless
     /// strict_indexing is set.
-    pub async fn buf_get_lines<T>(
+    pub async fn buf_get_lines(
         &self,
         buf: &Buffer,
         start: i64,
         end: i64,
         strict_indexing: bool,
-    ) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
+    ) -> Result<Vec<NvimString>> {
+        self.require("nvim_buf_get_lines", 1)?;
         #[allow(unused_variables)]
         let req = (buf, start, end, strict_indexing);
         #[allow(clippy::needless_question_mark)]
@@ -206,13 +372,31 @@ impl NvimApi {
         start: i64,
         end: i64,
         strict_indexing: bool,
-        replacement: Vec<String>,
+        replacement: Vec<NvimString>,
     ) -> Result<()> {
+        self.require("nvim_buf_set_lines", 1)?;
         #[allow(unused_variables)]
         let req = (buf, start, end, strict_indexing, replacement);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_lines", req).await?)
     }
+    /// Send [`Self::buf_set_lines`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_lines_notify(
+        &self,
+        buf: &Buffer,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        self.require("nvim_buf_set_lines", 1)?;
+        #[allow(unused_variables)]
+        let req = (buf, start, end, strict_indexing, replacement);
+        Ok(self.rpc_notify("nvim_buf_set_lines", req).await?)
+    }
     /// Sets (replaces) a range in the buffer
     ///
     /// This is recommended over nvim_buf_set_lines() when only modifying parts
@@ -237,13 +421,32 @@ impl NvimApi {
         start_col: i64,
         end_row: i64,
         end_col: i64,
-        replacement: Vec<String>,
+        replacement: Vec<NvimString>,
     ) -> Result<()> {
+        self.require("nvim_buf_set_text", 7)?;
         #[allow(unused_variables)]
         let req = (buf, start_row, start_col, end_row, end_col, replacement);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_text", req).await?)
     }
+    /// Send [`Self::buf_set_text`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_text_notify(
+        &self,
+        buf: &Buffer,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        self.require("nvim_buf_set_text", 7)?;
+        #[allow(unused_variables)]
+        let req = (buf, start_row, start_col, end_row, end_col, replacement);
+        Ok(self.rpc_notify("nvim_buf_set_text", req).await?)
+    }
     /// Gets a range from the buffer.
     ///
     /// This differs from |nvim_buf_get_lines()| in that it allows retrieving only
@@ -253,18 +456,16 @@ impl NvimApi {
     /// are end-exclusive.
     ///
     /// Prefer |nvim_buf_get_lines()| when retrieving entire lines.
-    pub async fn buf_get_text<T>(
+    pub async fn buf_get_text(
         &self,
         buf: &Buffer,
         start_row: i64,
         start_col: i64,
         end_row: i64,
         end_col: i64,
-        opts: HashMap<String, Value>,
-    ) -> Result<T>
-    where
-        T: serde::de::DeserializeOwned,
-    {
+        opts: opts::Empty,
+    ) -> Result<Vec<NvimString>> {
+        self.require("nvim_buf_get_text", 9)?;
         #[allow(unused_variables)]
         let req = (buf, start_row, start_col, end_row, end_col, opts);
         #[allow(clippy::needless_question_mark)]
@@ -280,6 +481,7 @@ impl NvimApi {
     /// Unlike |line2byte()|, throws error for out-of-bounds indexing. Returns -1
     /// for unloaded buffer.
     pub async fn buf_get_offset(&self, buf: &Buffer, index: i64) -> Result<i64> {
+        self.require("nvim_buf_get_offset", 5)?;
         #[allow(unused_variables)]
         let req = (buf, index);
         #[allow(clippy::needless_question_mark)]
@@ -290,6 +492,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_buf_get_var", 1)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
@@ -297,6 +500,7 @@ impl NvimApi {
     }
     /// Gets a changed tick of a buffer
     pub async fn buf_get_changedtick(&self, buf: &Buffer) -> Result<i64> {
+        self.require("nvim_buf_get_changedtick", 2)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
@@ -307,6 +511,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_buf_get_keymap", 3)?;
         #[allow(unused_variables)]
         let req = (buf, mode);
         #[allow(clippy::needless_question_mark)]
@@ -319,39 +524,94 @@ impl NvimApi {
         mode: &str,
         lhs: &str,
         rhs: &str,
-        opts: HashMap<String, Value>,
+        opts: opts::Keymap,
     ) -> Result<()> {
+        self.require("nvim_buf_set_keymap", 6)?;
         #[allow(unused_variables)]
         let req = (buf, mode, lhs, rhs, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_keymap", req).await?)
     }
+    /// Send [`Self::buf_set_keymap`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_keymap_notify(
+        &self,
+        buf: &Buffer,
+        mode: &str,
+        lhs: &str,
+        rhs: &str,
+        opts: opts::Keymap,
+    ) -> Result<()> {
+        self.require("nvim_buf_set_keymap", 6)?;
+        #[allow(unused_variables)]
+        let req = (buf, mode, lhs, rhs, opts);
+        Ok(self.rpc_notify("nvim_buf_set_keymap", req).await?)
+    }
     /// Unmaps a buffer-local |mapping| for the given mode.
     pub async fn buf_del_keymap(&self, buf: &Buffer, mode: &str, lhs: &str) -> Result<()> {
+        self.require("nvim_buf_del_keymap", 6)?;
         #[allow(unused_variables)]
         let req = (buf, mode, lhs);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_del_keymap", req).await?)
     }
+    /// Send [`Self::buf_del_keymap`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_del_keymap_notify(&self, buf: &Buffer, mode: &str, lhs: &str) -> Result<()> {
+        self.require("nvim_buf_del_keymap", 6)?;
+        #[allow(unused_variables)]
+        let req = (buf, mode, lhs);
+        Ok(self.rpc_notify("nvim_buf_del_keymap", req).await?)
+    }
     /// Sets a buffer-scoped (b:) variable
     pub async fn buf_set_var<T>(&self, buf: &Buffer, name: &str, value: T) -> Result<()>
     where
         T: Serialize,
     {
+        self.require("nvim_buf_set_var", 1)?;
         #[allow(unused_variables)]
         let req = (buf, name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_var", req).await?)
     }
+    /// Send [`Self::buf_set_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_var_notify<T>(&self, buf: &Buffer, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_buf_set_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (buf, name, value);
+        Ok(self.rpc_notify("nvim_buf_set_var", req).await?)
+    }
     /// Removes a buffer-scoped (b:) variable
     pub async fn buf_del_var(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_del_var", 1)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_del_var", req).await?)
     }
+    /// Send [`Self::buf_del_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_del_var_notify(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_del_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (buf, name);
+        Ok(self.rpc_notify("nvim_buf_del_var", req).await?)
+    }
     /// Gets the full file name for the buffer
-    pub async fn buf_get_name(&self, buf: &Buffer) -> Result<String> {
+    pub async fn buf_get_name(&self, buf: &Buffer) -> Result<NvimString> {
+        self.require("nvim_buf_get_name", 1)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
@@ -359,14 +619,26 @@ impl NvimApi {
     }
     /// Sets the full file name for a buffer, like :file_f
     pub async fn buf_set_name(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_set_name", 1)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_name", req).await?)
     }
+    /// Send [`Self::buf_set_name`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_name_notify(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_set_name", 1)?;
+        #[allow(unused_variables)]
+        let req = (buf, name);
+        Ok(self.rpc_notify("nvim_buf_set_name", req).await?)
+    }
     /// Checks if a buffer is valid and loaded. See |api-buffer| for more info
     /// about unloaded buffers.
     pub async fn buf_is_loaded(&self, buf: &Buffer) -> Result<bool> {
+        self.require("nvim_buf_is_loaded", 5)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
@@ -374,16 +646,28 @@ impl NvimApi {
     }
     /// Deletes the buffer. See |:bwipeout|
     pub async fn buf_delete(&self, buf: &Buffer, opts: opts::BufDelete) -> Result<()> {
+        self.require("nvim_buf_delete", 7)?;
         #[allow(unused_variables)]
         let req = (buf, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_delete", req).await?)
     }
+    /// Send [`Self::buf_delete`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_delete_notify(&self, buf: &Buffer, opts: opts::BufDelete) -> Result<()> {
+        self.require("nvim_buf_delete", 7)?;
+        #[allow(unused_variables)]
+        let req = (buf, opts);
+        Ok(self.rpc_notify("nvim_buf_delete", req).await?)
+    }
     /// Checks if a buffer is valid.
     ///
     /// Note: Even if a buffer is valid it may have been unloaded. See |api-buffer|
     /// for more info about unloaded buffers.
     pub async fn buf_is_valid(&self, buf: &Buffer) -> Result<bool> {
+        self.require("nvim_buf_is_valid", 1)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
@@ -394,11 +678,22 @@ impl NvimApi {
     /// Note: only deletes marks set in the buffer, if the mark is not set in the
     /// buffer it will return false.
     pub async fn buf_del_mark(&self, buf: &Buffer, name: &str) -> Result<bool> {
+        self.require("nvim_buf_del_mark", 8)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_del_mark", req).await?)
     }
+    /// Send [`Self::buf_del_mark`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_del_mark_notify(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_del_mark", 8)?;
+        #[allow(unused_variables)]
+        let req = (buf, name);
+        Ok(self.rpc_notify("nvim_buf_del_mark", req).await?)
+    }
     /// Sets a named mark in the given buffer, all marks are allowed
     /// file/uppercase, visual, last change, etc. See mark-motions.
     ///
@@ -411,19 +706,38 @@ impl NvimApi {
         name: &str,
         line: i64,
         col: i64,
-        opts: HashMap<String, Value>,
+        opts: opts::Empty,
     ) -> Result<bool> {
+        self.require("nvim_buf_set_mark", 8)?;
         #[allow(unused_variables)]
         let req = (buf, name, line, col, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_mark", req).await?)
     }
+    /// Send [`Self::buf_set_mark`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_mark_notify(
+        &self,
+        buf: &Buffer,
+        name: &str,
+        line: i64,
+        col: i64,
+        opts: opts::Empty,
+    ) -> Result<()> {
+        self.require("nvim_buf_set_mark", 8)?;
+        #[allow(unused_variables)]
+        let req = (buf, name, line, col, opts);
+        Ok(self.rpc_notify("nvim_buf_set_mark", req).await?)
+    }
     /// Returns a (row,col) tuple representing the position of the named mark.
     /// End of line column position is returned as |v:maxcol| (big number).
     /// See |mark-motions|.
     ///
     /// Marks are (1,0)-indexed. |api-indexing|
     pub async fn buf_get_mark(&self, buf: &Buffer, name: &str) -> Result<(i64, i64)> {
+        self.require("nvim_buf_get_mark", 8)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
@@ -432,16 +746,23 @@ impl NvimApi {
     /// Parse command line.
     ///
     /// Does not check the validity of command arguments.
-    pub async fn parse_cmd(
-        &self,
-        str: &str,
-        opts: HashMap<String, Value>,
-    ) -> Result<HashMap<String, Value>> {
+    pub async fn parse_cmd(&self, str: &str, opts: opts::Empty) -> Result<HashMap<String, Value>> {
+        self.require("nvim_parse_cmd", 10)?;
         #[allow(unused_variables)]
         let req = (str, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_parse_cmd", req).await?)
     }
+    /// Send [`Self::parse_cmd`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn parse_cmd_notify(&self, str: &str, opts: opts::Empty) -> Result<()> {
+        self.require("nvim_parse_cmd", 10)?;
+        #[allow(unused_variables)]
+        let req = (str, opts);
+        Ok(self.rpc_notify("nvim_parse_cmd", req).await?)
+    }
     /// Executes an Ex command.
     ///
     /// Unlike `nvim_command()` this command takes a structured Dict instead of a
@@ -456,71 +777,137 @@ impl NvimApi {
     /// `vim.cmd.bdelete{ count = 2 }`, you may do `vim.cmd.bdelete(2)`.
     ///
     /// On execution error: fails with Vimscript error, updates v:errmsg.
-    pub async fn cmd(
-        &self,
-        cmd: HashMap<String, Value>,
-        opts: HashMap<String, Value>,
-    ) -> Result<String> {
+    pub async fn cmd(&self, cmd: opts::Cmd, opts: opts::CmdOpts) -> Result<NvimString> {
+        self.require("nvim_cmd", 10)?;
         #[allow(unused_variables)]
         let req = (cmd, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_cmd", req).await?)
     }
+    /// Send [`Self::cmd`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn cmd_notify(&self, cmd: opts::Cmd, opts: opts::CmdOpts) -> Result<()> {
+        self.require("nvim_cmd", 10)?;
+        #[allow(unused_variables)]
+        let req = (cmd, opts);
+        Ok(self.rpc_notify("nvim_cmd", req).await?)
+    }
     /// Creates a global user-commands command.
     pub async fn create_user_command<T>(
         &self,
         name: &str,
         cmd: T,
-        opts: HashMap<String, Value>,
+        opts: opts::UserCommand,
     ) -> Result<()>
     where
         T: Serialize,
     {
+        self.require("nvim_create_user_command", 9)?;
         #[allow(unused_variables)]
         let req = (name, cmd, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_create_user_command", req).await?)
     }
+    /// Send [`Self::create_user_command`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn create_user_command_notify<T>(
+        &self,
+        name: &str,
+        cmd: T,
+        opts: opts::UserCommand,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_create_user_command", 9)?;
+        #[allow(unused_variables)]
+        let req = (name, cmd, opts);
+        Ok(self.rpc_notify("nvim_create_user_command", req).await?)
+    }
     /// Delete a user-defined command.
     pub async fn del_user_command(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_user_command", 9)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_user_command", req).await?)
     }
+    /// Send [`Self::del_user_command`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_user_command_notify(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_user_command", 9)?;
+        #[allow(unused_variables)]
+        let req = name;
+        Ok(self.rpc_notify("nvim_del_user_command", req).await?)
+    }
     /// Creates a buffer-local command `user-commands`.
     pub async fn buf_create_user_command<T>(
         &self,
         buf: &Buffer,
         name: &str,
         cmd: T,
-        opts: HashMap<String, Value>,
+        opts: opts::UserCommand,
     ) -> Result<()>
     where
         T: Serialize,
     {
+        self.require("nvim_buf_create_user_command", 9)?;
         #[allow(unused_variables)]
         let req = (buf, name, cmd, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_create_user_command", req).await?)
     }
+    /// Send [`Self::buf_create_user_command`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_create_user_command_notify<T>(
+        &self,
+        buf: &Buffer,
+        name: &str,
+        cmd: T,
+        opts: opts::UserCommand,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_buf_create_user_command", 9)?;
+        #[allow(unused_variables)]
+        let req = (buf, name, cmd, opts);
+        Ok(self.rpc_notify("nvim_buf_create_user_command", req).await?)
+    }
     /// Delete a buffer-local user-defined command.
     ///
     /// Only commands created with `:command-buffer` or
     /// `nvim_buf_create_user_command()` can be deleted with this function.
     pub async fn buf_del_user_command(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_del_user_command", 9)?;
         #[allow(unused_variables)]
         let req = (buf, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_del_user_command", req).await?)
     }
+    /// Send [`Self::buf_del_user_command`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_del_user_command_notify(&self, buf: &Buffer, name: &str) -> Result<()> {
+        self.require("nvim_buf_del_user_command", 9)?;
+        #[allow(unused_variables)]
+        let req = (buf, name);
+        Ok(self.rpc_notify("nvim_buf_del_user_command", req).await?)
+    }
     /// Gets a map of global (non-buffer-local) Ex commands.
     ///
     /// Currently only |user-commands| are supported, not builtin Ex commands.
-    pub async fn get_commands(
-        &self,
-        opts: HashMap<String, Value>,
-    ) -> Result<HashMap<String, Value>> {
+    pub async fn get_commands(&self, opts: opts::GetCommands) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_commands", 4)?;
         #[allow(unused_variables)]
         let req = opts;
         #[allow(clippy::needless_question_mark)]
@@ -530,8 +917,9 @@ impl NvimApi {
     pub async fn buf_get_commands(
         &self,
         buf: &Buffer,
-        opts: HashMap<String, Value>,
+        opts: opts::GetCommands,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_buf_get_commands", 4)?;
         #[allow(unused_variables)]
         let req = (buf, opts);
         #[allow(clippy::needless_question_mark)]
@@ -543,15 +931,31 @@ impl NvimApi {
     /// * termresponse: The terminal sent an OSC or DCS response sequence to
     /// Nvim. The payload is the received response. Sets |v:termresponse| and
     /// fires |TermResponse|.
+    #[cfg(feature = "nvim-0-10")]
     pub async fn ui_term_event<T>(&self, event: &str, value: T) -> Result<()>
     where
         T: Serialize,
     {
+        self.require("nvim_ui_term_event", 12)?;
         #[allow(unused_variables)]
         let req = (event, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_term_event", req).await?)
     }
+    /// Send [`Self::ui_term_event`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn ui_term_event_notify<T>(&self, event: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_ui_term_event", 12)?;
+        #[allow(unused_variables)]
+        let req = (event, value);
+        Ok(self.rpc_notify("nvim_ui_term_event", req).await?)
+    }
     /// Creates a new namespace or gets an existing one.
     ///
     /// Namespaces are used for buffer highlights and virtual text, see
@@ -561,13 +965,25 @@ impl NvimApi {
     /// namespace, the associated id is returned. If name is an empty string a
     /// new, anonymous namespace is created.
     pub async fn create_namespace(&self, name: &str) -> Result<i64> {
+        self.require("nvim_create_namespace", 5)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_create_namespace", req).await?)
     }
+    /// Send [`Self::create_namespace`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn create_namespace_notify(&self, name: &str) -> Result<()> {
+        self.require("nvim_create_namespace", 5)?;
+        #[allow(unused_variables)]
+        let req = name;
+        Ok(self.rpc_notify("nvim_create_namespace", req).await?)
+    }
     /// Gets existing, non-anonymous |namespace|s.
     pub async fn get_namespaces(&self) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_namespaces", 5)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -579,11 +995,12 @@ impl NvimApi {
         buf: &Buffer,
         ns_id: i64,
         id: i64,
-        opts: HashMap<String, Value>,
+        opts: opts::GetExtmark,
     ) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_buf_get_extmark_by_id", 7)?;
         #[allow(unused_variables)]
         let req = (buf, ns_id, id, opts);
         #[allow(clippy::needless_question_mark)]
@@ -612,13 +1029,14 @@ impl NvimApi {
         ns_id: i64,
         start: T,
         end: U,
-        opts: HashMap<String, Value>,
+        opts: opts::GetExtmarks,
     ) -> Result<V>
     where
         T: Serialize,
         U: Serialize,
         V: serde::de::DeserializeOwned,
     {
+        self.require("nvim_buf_get_extmarks", 7)?;
         #[allow(unused_variables)]
         let req = (buf, ns_id, start, end, opts);
         #[allow(clippy::needless_question_mark)]
@@ -645,20 +1063,49 @@ impl NvimApi {
         ns_id: i64,
         line: i64,
         col: i64,
-        opts: HashMap<String, Value>,
+        opts: opts::SetExtmark,
     ) -> Result<i64> {
+        self.require("nvim_buf_set_extmark", 7)?;
         #[allow(unused_variables)]
         let req = (buf, ns_id, line, col, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_set_extmark", req).await?)
     }
+    /// Send [`Self::buf_set_extmark`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_set_extmark_notify(
+        &self,
+        buf: &Buffer,
+        ns_id: i64,
+        line: i64,
+        col: i64,
+        opts: opts::SetExtmark,
+    ) -> Result<()> {
+        self.require("nvim_buf_set_extmark", 7)?;
+        #[allow(unused_variables)]
+        let req = (buf, ns_id, line, col, opts);
+        Ok(self.rpc_notify("nvim_buf_set_extmark", req).await?)
+    }
     /// Removes an extmark.
     pub async fn buf_del_extmark(&self, buf: &Buffer, ns_id: i64, id: i64) -> Result<bool> {
+        self.require("nvim_buf_del_extmark", 7)?;
         #[allow(unused_variables)]
         let req = (buf, ns_id, id);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_del_extmark", req).await?)
     }
+    /// Send [`Self::buf_del_extmark`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_del_extmark_notify(&self, buf: &Buffer, ns_id: i64, id: i64) -> Result<()> {
+        self.require("nvim_buf_del_extmark", 7)?;
+        #[allow(unused_variables)]
+        let req = (buf, ns_id, id);
+        Ok(self.rpc_notify("nvim_buf_del_extmark", req).await?)
+    }
     /// Clears namespaced objects (highlights, extmarks, virtual text) from a
     /// region.
     ///
@@ -671,57 +1118,37 @@ impl NvimApi {
         line_start: i64,
         line_end: i64,
     ) -> Result<()> {
+        self.require("nvim_buf_clear_namespace", 5)?;
         #[allow(unused_variables)]
         let req = (buf, ns_id, line_start, line_end);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_buf_clear_namespace", req).await?)
     }
-    /// Set or change decoration provider for a |namespace|
-    ///
-    /// This is a very general purpose interface for having Lua callbacks being
-    /// triggered during the redraw code.
-    ///
-    /// The expected usage is to set |extmarks| for the currently redrawn buffer.
-    /// |nvim_buf_set_extmark()| can be called to add marks on a per-window or
-    /// per-lines basis. Use the ephemeral key to only use the mark for the
-    /// current screen redraw (the callback will be called again for the next
-    /// redraw).
-    ///
-    /// Note: this function should not be called often. Rather, the callbacks
-    /// themselves can be used to throttle unneeded callbacks. the on_start
-    /// callback can return false to disable the provider until the next redraw.
-    /// Similarly, return false in on_win will skip the on_line calls for
-    /// that window (but any extmarks set in on_win will still be used). A
-    /// plugin managing multiple sources of decoration should ideally only set one
-    /// provider, and merge the sources internally. You can use multiple ns_id
-    /// for the extmarks set/modified inside the callback anyway.
-    ///
-    /// Note: doing anything other than setting extmarks is considered
-    /// experimental. Doing things like changing options are not explicitly
-    /// forbidden, but is likely to have unexpected consequences (such as 100% CPU
-    /// consumption). Doing vim.rpcnotify should be OK, but vim.rpcrequest is
-    /// quite dubious for the moment.
-    ///
-    /// Note: It is not allowed to remove or update extmarks in on_line
-    /// callbacks.
-    pub async fn set_decoration_provider(
+    /// Send [`Self::buf_clear_namespace`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn buf_clear_namespace_notify(
         &self,
+        buf: &Buffer,
         ns_id: i64,
-        opts: HashMap<String, Value>,
+        line_start: i64,
+        line_end: i64,
     ) -> Result<()> {
+        self.require("nvim_buf_clear_namespace", 5)?;
         #[allow(unused_variables)]
-        let req = (ns_id, opts);
-        #[allow(clippy::needless_question_mark)]
-        Ok(self.rpc_call("nvim_set_decoration_provider", req).await?)
+        let req = (buf, ns_id, line_start, line_end);
+        Ok(self.rpc_notify("nvim_buf_clear_namespace", req).await?)
     }
     /// Gets the value of an option. The behavior of this function matches that of
     /// |:set|: the local value of an option is returned if it exists; otherwise,
     /// the global value is returned. Local values always correspond to the
     /// current buffer or window, unless buf or win is set in {opts}.
-    pub async fn get_option_value<T>(&self, name: &str, opts: HashMap<String, Value>) -> Result<T>
+    pub async fn get_option_value<T>(&self, name: &str, opts: opts::SetOptionValue) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_option_value", 9)?;
         #[allow(unused_variables)]
         let req = (name, opts);
         #[allow(clippy::needless_question_mark)]
@@ -741,16 +1168,36 @@ impl NvimApi {
     where
         T: Serialize,
     {
+        self.require("nvim_set_option_value", 9)?;
         #[allow(unused_variables)]
         let req = (name, value, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_option_value", req).await?)
     }
+    /// Send [`Self::set_option_value`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_option_value_notify<T>(
+        &self,
+        name: &str,
+        value: T,
+        opts: opts::SetOptionValue,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_set_option_value", 9)?;
+        #[allow(unused_variables)]
+        let req = (name, value, opts);
+        Ok(self.rpc_notify("nvim_set_option_value", req).await?)
+    }
     /// Gets the option information for all options.
     ///
     /// The dict has the full option names as keys and option metadata dicts as
     /// detailed at |nvim_get_option_info2()|.
     pub async fn get_all_options_info(&self) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_all_options_info", 7)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -760,8 +1207,9 @@ impl NvimApi {
     pub async fn get_option_info2(
         &self,
         name: &str,
-        opts: HashMap<String, Value>,
+        opts: opts::SetOptionValue,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_option_info2", 11)?;
         #[allow(unused_variables)]
         let req = (name, opts);
         #[allow(clippy::needless_question_mark)]
@@ -769,6 +1217,7 @@ impl NvimApi {
     }
     /// Gets the windows in a tabpage
     pub async fn tabpage_list_wins(&self, tabpage: &TabPage) -> Result<Vec<Window>> {
+        self.require("nvim_tabpage_list_wins", 1)?;
         #[allow(unused_variables)]
         let req = tabpage;
         #[allow(clippy::needless_question_mark)]
@@ -779,6 +1228,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_tabpage_get_var", 1)?;
         #[allow(unused_variables)]
         let req = (tabpage, name);
         #[allow(clippy::needless_question_mark)]
@@ -789,34 +1239,79 @@ impl NvimApi {
     where
         T: Serialize,
     {
+        self.require("nvim_tabpage_set_var", 1)?;
         #[allow(unused_variables)]
         let req = (tabpage, name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_tabpage_set_var", req).await?)
     }
+    /// Send [`Self::tabpage_set_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn tabpage_set_var_notify<T>(
+        &self,
+        tabpage: &TabPage,
+        name: &str,
+        value: T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_tabpage_set_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (tabpage, name, value);
+        Ok(self.rpc_notify("nvim_tabpage_set_var", req).await?)
+    }
     /// Removes a tab-scoped (t:) variable
     pub async fn tabpage_del_var(&self, tabpage: &TabPage, name: &str) -> Result<()> {
+        self.require("nvim_tabpage_del_var", 1)?;
         #[allow(unused_variables)]
         let req = (tabpage, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_tabpage_del_var", req).await?)
     }
+    /// Send [`Self::tabpage_del_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn tabpage_del_var_notify(&self, tabpage: &TabPage, name: &str) -> Result<()> {
+        self.require("nvim_tabpage_del_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (tabpage, name);
+        Ok(self.rpc_notify("nvim_tabpage_del_var", req).await?)
+    }
     /// Gets the current window in a tabpage
     pub async fn tabpage_get_win(&self, tabpage: &TabPage) -> Result<Window> {
+        self.require("nvim_tabpage_get_win", 1)?;
         #[allow(unused_variables)]
         let req = tabpage;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_tabpage_get_win", req).await?)
     }
     /// Sets the current window in a tabpage
+    #[cfg(feature = "nvim-0-10")]
     pub async fn tabpage_set_win(&self, tabpage: &TabPage, win: &Window) -> Result<()> {
+        self.require("nvim_tabpage_set_win", 12)?;
         #[allow(unused_variables)]
         let req = (tabpage, win);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_tabpage_set_win", req).await?)
     }
+    /// Send [`Self::tabpage_set_win`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn tabpage_set_win_notify(&self, tabpage: &TabPage, win: &Window) -> Result<()> {
+        self.require("nvim_tabpage_set_win", 12)?;
+        #[allow(unused_variables)]
+        let req = (tabpage, win);
+        Ok(self.rpc_notify("nvim_tabpage_set_win", req).await?)
+    }
     /// Gets the tabpage number
     pub async fn tabpage_get_number(&self, tabpage: &TabPage) -> Result<i64> {
+        self.require("nvim_tabpage_get_number", 1)?;
         #[allow(unused_variables)]
         let req = tabpage;
         #[allow(clippy::needless_question_mark)]
@@ -824,22 +1319,41 @@ impl NvimApi {
     }
     /// Checks if a tabpage is valid
     pub async fn tabpage_is_valid(&self, tabpage: &TabPage) -> Result<bool> {
+        self.require("nvim_tabpage_is_valid", 1)?;
         #[allow(unused_variables)]
         let req = tabpage;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_tabpage_is_valid", req).await?)
     }
+    #[cfg(feature = "nvim-0-12")]
     pub async fn open_tabpage(
         &self,
         buf: &Buffer,
         enter: bool,
         config: HashMap<String, Value>,
     ) -> Result<TabPage> {
+        self.require("nvim_open_tabpage", 14)?;
         #[allow(unused_variables)]
         let req = (buf, enter, config);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_open_tabpage", req).await?)
     }
+    /// Send [`Self::open_tabpage`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    #[cfg(feature = "nvim-0-12")]
+    pub async fn open_tabpage_notify(
+        &self,
+        buf: &Buffer,
+        enter: bool,
+        config: HashMap<String, Value>,
+    ) -> Result<()> {
+        self.require("nvim_open_tabpage", 14)?;
+        #[allow(unused_variables)]
+        let req = (buf, enter, config);
+        Ok(self.rpc_notify("nvim_open_tabpage", req).await?)
+    }
     /// Activates UI events on the channel.
     ///
     /// Entry point of all UI clients. Allows |--embed| to continue startup.
@@ -855,62 +1369,152 @@ impl NvimApi {
         height: i64,
         options: HashMap<String, Value>,
     ) -> Result<()> {
+        self.require("nvim_ui_attach", 1)?;
         #[allow(unused_variables)]
         let req = (width, height, options);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_attach", req).await?)
     }
+    /// Send [`Self::ui_attach`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_attach_notify(
+        &self,
+        width: i64,
+        height: i64,
+        options: HashMap<String, Value>,
+    ) -> Result<()> {
+        self.require("nvim_ui_attach", 1)?;
+        #[allow(unused_variables)]
+        let req = (width, height, options);
+        Ok(self.rpc_notify("nvim_ui_attach", req).await?)
+    }
     /// Tells the nvim server if focus was gained or lost by the GUI
     pub async fn ui_set_focus(&self, gained: bool) -> Result<()> {
+        self.require("nvim_ui_set_focus", 11)?;
         #[allow(unused_variables)]
         let req = gained;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_set_focus", req).await?)
     }
+    /// Send [`Self::ui_set_focus`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_set_focus_notify(&self, gained: bool) -> Result<()> {
+        self.require("nvim_ui_set_focus", 11)?;
+        #[allow(unused_variables)]
+        let req = gained;
+        Ok(self.rpc_notify("nvim_ui_set_focus", req).await?)
+    }
     /// Deactivates UI events on the channel.
     ///
     /// Removes the client from the list of UIs. |nvim_list_uis()|
     pub async fn ui_detach(&self) -> Result<()> {
+        self.require("nvim_ui_detach", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_detach", req).await?)
     }
+    /// Send [`Self::ui_detach`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_detach_notify(&self) -> Result<()> {
+        self.require("nvim_ui_detach", 1)?;
+        #[allow(unused_variables)]
+        let req = NO_PARAMS;
+        Ok(self.rpc_notify("nvim_ui_detach", req).await?)
+    }
     /// Try to resize the UI.
     pub async fn ui_try_resize(&self, width: i64, height: i64) -> Result<()> {
+        self.require("nvim_ui_try_resize", 1)?;
         #[allow(unused_variables)]
         let req = (width, height);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_try_resize", req).await?)
     }
+    /// Send [`Self::ui_try_resize`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_try_resize_notify(&self, width: i64, height: i64) -> Result<()> {
+        self.require("nvim_ui_try_resize", 1)?;
+        #[allow(unused_variables)]
+        let req = (width, height);
+        Ok(self.rpc_notify("nvim_ui_try_resize", req).await?)
+    }
     /// Set a UI option.
     pub async fn ui_set_option<T>(&self, name: &str, value: T) -> Result<()>
     where
         T: Serialize,
     {
+        self.require("nvim_ui_set_option", 1)?;
         #[allow(unused_variables)]
         let req = (name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_set_option", req).await?)
     }
+    /// Send [`Self::ui_set_option`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_set_option_notify<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_ui_set_option", 1)?;
+        #[allow(unused_variables)]
+        let req = (name, value);
+        Ok(self.rpc_notify("nvim_ui_set_option", req).await?)
+    }
     /// Tell Nvim to resize a grid. Triggers a grid_resize event with the
     /// requested grid size or the maximum size if it exceeds size limits.
     ///
     /// On invalid grid handle, fails with error.
     pub async fn ui_try_resize_grid(&self, grid: i64, width: i64, height: i64) -> Result<()> {
+        self.require("nvim_ui_try_resize_grid", 6)?;
         #[allow(unused_variables)]
         let req = (grid, width, height);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_try_resize_grid", req).await?)
     }
+    /// Send [`Self::ui_try_resize_grid`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_try_resize_grid_notify(
+        &self,
+        grid: i64,
+        width: i64,
+        height: i64,
+    ) -> Result<()> {
+        self.require("nvim_ui_try_resize_grid", 6)?;
+        #[allow(unused_variables)]
+        let req = (grid, width, height);
+        Ok(self.rpc_notify("nvim_ui_try_resize_grid", req).await?)
+    }
     /// Tells Nvim the number of elements displaying in the popupmenu, to decide
     /// <PageUp> and <PageDown> movement.
     pub async fn ui_pum_set_height(&self, height: i64) -> Result<()> {
+        self.require("nvim_ui_pum_set_height", 6)?;
         #[allow(unused_variables)]
         let req = height;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_pum_set_height", req).await?)
     }
+    /// Send [`Self::ui_pum_set_height`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_pum_set_height_notify(&self, height: i64) -> Result<()> {
+        self.require("nvim_ui_pum_set_height", 6)?;
+        #[allow(unused_variables)]
+        let req = height;
+        Ok(self.rpc_notify("nvim_ui_pum_set_height", req).await?)
+    }
     /// Tells Nvim the geometry of the popupmenu, to align floating windows with
     /// an external popup menu.
     ///
@@ -927,21 +1531,52 @@ impl NvimApi {
         row: f64,
         col: f64,
     ) -> Result<()> {
+        self.require("nvim_ui_pum_set_bounds", 7)?;
         #[allow(unused_variables)]
         let req = (width, height, row, col);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_pum_set_bounds", req).await?)
     }
+    /// Send [`Self::ui_pum_set_bounds`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn ui_pum_set_bounds_notify(
+        &self,
+        width: f64,
+        height: f64,
+        row: f64,
+        col: f64,
+    ) -> Result<()> {
+        self.require("nvim_ui_pum_set_bounds", 7)?;
+        #[allow(unused_variables)]
+        let req = (width, height, row, col);
+        Ok(self.rpc_notify("nvim_ui_pum_set_bounds", req).await?)
+    }
+    #[cfg(feature = "nvim-0-12")]
     pub async fn ui_send(&self, content: &str) -> Result<()> {
+        self.require("nvim_ui_send", 14)?;
         #[allow(unused_variables)]
         let req = content;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_ui_send", req).await?)
     }
+    /// Send [`Self::ui_send`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    #[cfg(feature = "nvim-0-12")]
+    pub async fn ui_send_notify(&self, content: &str) -> Result<()> {
+        self.require("nvim_ui_send", 14)?;
+        #[allow(unused_variables)]
+        let req = content;
+        Ok(self.rpc_notify("nvim_ui_send", req).await?)
+    }
     /// Gets a highlight group by name
     ///
     /// similar to |hlID()|, but allocates a new ID if not present.
     pub async fn get_hl_id_by_name(&self, name: &str) -> Result<i64> {
+        self.require("nvim_get_hl_id_by_name", 7)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
@@ -954,8 +1589,9 @@ impl NvimApi {
     pub async fn get_hl(
         &self,
         ns_id: i64,
-        opts: HashMap<String, Value>,
+        opts: opts::GetHighlight,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_hl", 11)?;
         #[allow(unused_variables)]
         let req = (ns_id, opts);
         #[allow(clippy::needless_question_mark)]
@@ -975,13 +1611,26 @@ impl NvimApi {
     /// Note: If link is used in combination with other attributes; only the
     /// link will take effect (see |:hi-link|).
     pub async fn set_hl(&self, ns_id: i64, name: &str, val: opts::SetHl) -> Result<()> {
+        self.require("nvim_set_hl", 7)?;
         #[allow(unused_variables)]
         let req = (ns_id, name, val);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_hl", req).await?)
     }
+    /// Send [`Self::set_hl`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_hl_notify(&self, ns_id: i64, name: &str, val: opts::SetHl) -> Result<()> {
+        self.require("nvim_set_hl", 7)?;
+        #[allow(unused_variables)]
+        let req = (ns_id, name, val);
+        Ok(self.rpc_notify("nvim_set_hl", req).await?)
+    }
     /// Gets the active highlight namespace.
-    pub async fn get_hl_ns(&self, opts: HashMap<String, Value>) -> Result<i64> {
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn get_hl_ns(&self, opts: opts::GetNs) -> Result<i64> {
+        self.require("nvim_get_hl_ns", 12)?;
         #[allow(unused_variables)]
         let req = opts;
         #[allow(clippy::needless_question_mark)]
@@ -990,11 +1639,22 @@ impl NvimApi {
     /// Set active namespace for highlights defined with |nvim_set_hl()|. This can
     /// be set for a single window, see |nvim_win_set_hl_ns()|.
     pub async fn set_hl_ns(&self, ns_id: i64) -> Result<()> {
+        self.require("nvim_set_hl_ns", 10)?;
         #[allow(unused_variables)]
         let req = ns_id;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_hl_ns", req).await?)
     }
+    /// Send [`Self::set_hl_ns`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_hl_ns_notify(&self, ns_id: i64) -> Result<()> {
+        self.require("nvim_set_hl_ns", 10)?;
+        #[allow(unused_variables)]
+        let req = ns_id;
+        Ok(self.rpc_notify("nvim_set_hl_ns", req).await?)
+    }
     /// Set active namespace for highlights defined with |nvim_set_hl()| while
     /// redrawing.
     ///
@@ -1002,11 +1662,22 @@ impl NvimApi {
     /// |nvim_set_decoration_provider()| on_win and on_line callbacks, which are
     /// allowed to change the namespace during a redraw cycle.
     pub async fn set_hl_ns_fast(&self, ns_id: i64) -> Result<()> {
+        self.require("nvim_set_hl_ns_fast", 10)?;
         #[allow(unused_variables)]
         let req = ns_id;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_hl_ns_fast", req).await?)
     }
+    /// Send [`Self::set_hl_ns_fast`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_hl_ns_fast_notify(&self, ns_id: i64) -> Result<()> {
+        self.require("nvim_set_hl_ns_fast", 10)?;
+        #[allow(unused_variables)]
+        let req = ns_id;
+        Ok(self.rpc_notify("nvim_set_hl_ns_fast", req).await?)
+    }
     /// Sends input-keys to Nvim, subject to various quirks controlled by mode
     /// flags. This is a blocking call, unlike |nvim_input()|.
     ///
@@ -1016,11 +1687,22 @@ impl NvimApi {
     /// with escape_ks=false) to replace |keycodes|, then pass the result to
     /// nvim_feedkeys().
     pub async fn feedkeys(&self, keys: &str, mode: &str, escape_ks: bool) -> Result<()> {
+        self.require("nvim_feedkeys", 1)?;
         #[allow(unused_variables)]
         let req = (keys, mode, escape_ks);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_feedkeys", req).await?)
     }
+    /// Send [`Self::feedkeys`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn feedkeys_notify(&self, keys: &str, mode: &str, escape_ks: bool) -> Result<()> {
+        self.require("nvim_feedkeys", 1)?;
+        #[allow(unused_variables)]
+        let req = (keys, mode, escape_ks);
+        Ok(self.rpc_notify("nvim_feedkeys", req).await?)
+    }
     /// Queues raw user-input. Unlike |nvim_feedkeys()|, this uses a low-level
     /// input buffer and the call is non-blocking (input is processed
     /// asynchronously by the eventloop).
@@ -1036,11 +1718,22 @@ impl NvimApi {
     /// Note: For mouse events use |nvim_input_mouse()|. The pseudokey form
     /// <LeftMouse><col,row> is deprecated since |api-level| 6.
     pub async fn input(&self, keys: &str) -> Result<i64> {
+        self.require("nvim_input", 1)?;
         #[allow(unused_variables)]
         let req = keys;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_input", req).await?)
     }
+    /// Send [`Self::input`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn input_notify(&self, keys: &str) -> Result<()> {
+        self.require("nvim_input", 1)?;
+        #[allow(unused_variables)]
+        let req = keys;
+        Ok(self.rpc_notify("nvim_input", req).await?)
+    }
     /// Send mouse event from GUI.
     ///
     /// Non-blocking: does not wait on any result, but queues the event to be
@@ -1060,11 +1753,30 @@ impl NvimApi {
         row: i64,
         col: i64,
     ) -> Result<()> {
+        self.require("nvim_input_mouse", 6)?;
         #[allow(unused_variables)]
         let req = (button, action, modifier, grid, row, col);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_input_mouse", req).await?)
     }
+    /// Send [`Self::input_mouse`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn input_mouse_notify(
+        &self,
+        button: &str,
+        action: &str,
+        modifier: &str,
+        grid: i64,
+        row: i64,
+        col: i64,
+    ) -> Result<()> {
+        self.require("nvim_input_mouse", 6)?;
+        #[allow(unused_variables)]
+        let req = (button, action, modifier, grid, row, col);
+        Ok(self.rpc_notify("nvim_input_mouse", req).await?)
+    }
     /// Replaces terminal codes and |keycodes| (<CR>, <Esc>, ...) in a string with
     /// the internal representation.
     pub async fn replace_termcodes(
@@ -1073,12 +1785,29 @@ impl NvimApi {
         from_part: bool,
         do_lt: bool,
         special: bool,
-    ) -> Result<String> {
+    ) -> Result<NvimString> {
+        self.require("nvim_replace_termcodes", 1)?;
         #[allow(unused_variables)]
         let req = (str, from_part, do_lt, special);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_replace_termcodes", req).await?)
     }
+    /// Send [`Self::replace_termcodes`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn replace_termcodes_notify(
+        &self,
+        str: &str,
+        from_part: bool,
+        do_lt: bool,
+        special: bool,
+    ) -> Result<()> {
+        self.require("nvim_replace_termcodes", 1)?;
+        #[allow(unused_variables)]
+        let req = (str, from_part, do_lt, special);
+        Ok(self.rpc_notify("nvim_replace_termcodes", req).await?)
+    }
     /// Execute Lua code. Parameters (if any) are available as ... inside the
     /// chunk. The chunk can return a value.
     ///
@@ -1088,24 +1817,47 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_exec_lua", 7)?;
         #[allow(unused_variables)]
         let req = (code, args);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_exec_lua", req).await?)
     }
+    /// Send [`Self::exec_lua`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn exec_lua_notify(&self, code: &str, args: Vec<Value>) -> Result<()> {
+        self.require("nvim_exec_lua", 7)?;
+        #[allow(unused_variables)]
+        let req = (code, args);
+        Ok(self.rpc_notify("nvim_exec_lua", req).await?)
+    }
     /// Calculates the number of display cells occupied by text. Control
     /// characters including <Tab> count as one cell.
     pub async fn strwidth(&self, text: &str) -> Result<i64> {
+        self.require("nvim_strwidth", 1)?;
         #[allow(unused_variables)]
         let req = text;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_strwidth", req).await?)
     }
+    /// Send [`Self::strwidth`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn strwidth_notify(&self, text: &str) -> Result<()> {
+        self.require("nvim_strwidth", 1)?;
+        #[allow(unused_variables)]
+        let req = text;
+        Ok(self.rpc_notify("nvim_strwidth", req).await?)
+    }
     /// Gets the paths contained in |runtime-search-path|.
     pub async fn list_runtime_paths<T>(&self) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_list_runtime_paths", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1123,6 +1875,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_runtime_file", 7)?;
         #[allow(unused_variables)]
         let req = (name, all);
         #[allow(clippy::needless_question_mark)]
@@ -1130,13 +1883,25 @@ impl NvimApi {
     }
     /// Changes the global working directory.
     pub async fn set_current_dir(&self, dir: &str) -> Result<()> {
+        self.require("nvim_set_current_dir", 1)?;
         #[allow(unused_variables)]
         let req = dir;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_current_dir", req).await?)
     }
+    /// Send [`Self::set_current_dir`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_current_dir_notify(&self, dir: &str) -> Result<()> {
+        self.require("nvim_set_current_dir", 1)?;
+        #[allow(unused_variables)]
+        let req = dir;
+        Ok(self.rpc_notify("nvim_set_current_dir", req).await?)
+    }
     /// Gets the current line.
-    pub async fn get_current_line(&self) -> Result<String> {
+    pub async fn get_current_line(&self) -> Result<NvimString> {
+        self.require("nvim_get_current_line", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1144,23 +1909,46 @@ impl NvimApi {
     }
     /// Sets the current line.
     pub async fn set_current_line(&self, line: &str) -> Result<()> {
+        self.require("nvim_set_current_line", 1)?;
         #[allow(unused_variables)]
         let req = line;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_current_line", req).await?)
     }
+    /// Send [`Self::set_current_line`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_current_line_notify(&self, line: &str) -> Result<()> {
+        self.require("nvim_set_current_line", 1)?;
+        #[allow(unused_variables)]
+        let req = line;
+        Ok(self.rpc_notify("nvim_set_current_line", req).await?)
+    }
     /// Deletes the current line.
     pub async fn del_current_line(&self) -> Result<()> {
+        self.require("nvim_del_current_line", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_current_line", req).await?)
     }
+    /// Send [`Self::del_current_line`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_current_line_notify(&self) -> Result<()> {
+        self.require("nvim_del_current_line", 1)?;
+        #[allow(unused_variables)]
+        let req = NO_PARAMS;
+        Ok(self.rpc_notify("nvim_del_current_line", req).await?)
+    }
     /// Gets a global (g:) variable.
     pub async fn get_var<T>(&self, name: &str) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_var", 1)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
@@ -1171,23 +1959,49 @@ impl NvimApi {
     where
         T: Serialize,
     {
+        self.require("nvim_set_var", 1)?;
         #[allow(unused_variables)]
         let req = (name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_var", req).await?)
     }
+    /// Send [`Self::set_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_var_notify<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_set_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (name, value);
+        Ok(self.rpc_notify("nvim_set_var", req).await?)
+    }
     /// Removes a global (g:) variable.
     pub async fn del_var(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_var", 1)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_var", req).await?)
     }
+    /// Send [`Self::del_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_var_notify(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_var", 1)?;
+        #[allow(unused_variables)]
+        let req = name;
+        Ok(self.rpc_notify("nvim_del_var", req).await?)
+    }
     /// Gets a v: variable.
     pub async fn get_vvar<T>(&self, name: &str) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_vvar", 1)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
@@ -1198,31 +2012,62 @@ impl NvimApi {
     where
         T: Serialize,
     {
+        self.require("nvim_set_vvar", 6)?;
         #[allow(unused_variables)]
         let req = (name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_vvar", req).await?)
     }
+    /// Send [`Self::set_vvar`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_vvar_notify<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_set_vvar", 6)?;
+        #[allow(unused_variables)]
+        let req = (name, value);
+        Ok(self.rpc_notify("nvim_set_vvar", req).await?)
+    }
     /// Echo a message.
     pub async fn echo<T>(
         &self,
         chunks: Vec<Value>,
         history: bool,
-        opts: HashMap<String, Value>,
+        opts: opts::EchoOpts,
     ) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_echo", 7)?;
         #[allow(unused_variables)]
         let req = (chunks, history, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_echo", req).await?)
     }
+    /// Send [`Self::echo`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn echo_notify(
+        &self,
+        chunks: Vec<Value>,
+        history: bool,
+        opts: opts::EchoOpts,
+    ) -> Result<()> {
+        self.require("nvim_echo", 7)?;
+        #[allow(unused_variables)]
+        let req = (chunks, history, opts);
+        Ok(self.rpc_notify("nvim_echo", req).await?)
+    }
     /// Gets the current list of buffer handles
     ///
     /// Includes unlisted (unloaded/deleted) buffers, like :ls!. Use
     /// |nvim_buf_is_loaded()| to check if a buffer is loaded.
     pub async fn list_bufs(&self) -> Result<Vec<Buffer>> {
+        self.require("nvim_list_bufs", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1230,6 +2075,7 @@ impl NvimApi {
     }
     /// Gets the current buffer.
     pub async fn get_current_buf(&self) -> Result<Buffer> {
+        self.require("nvim_get_current_buf", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1237,13 +2083,25 @@ impl NvimApi {
     }
     /// Sets the current buffer.
     pub async fn set_current_buf(&self, buf: &Buffer) -> Result<()> {
+        self.require("nvim_set_current_buf", 1)?;
         #[allow(unused_variables)]
         let req = buf;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_current_buf", req).await?)
     }
+    /// Send [`Self::set_current_buf`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_current_buf_notify(&self, buf: &Buffer) -> Result<()> {
+        self.require("nvim_set_current_buf", 1)?;
+        #[allow(unused_variables)]
+        let req = buf;
+        Ok(self.rpc_notify("nvim_set_current_buf", req).await?)
+    }
     /// Gets the current list of window handles.
     pub async fn list_wins(&self) -> Result<Vec<Window>> {
+        self.require("nvim_list_wins", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1251,6 +2109,7 @@ impl NvimApi {
     }
     /// Gets the current window.
     pub async fn get_current_win(&self) -> Result<Window> {
+        self.require("nvim_get_current_win", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1258,18 +2117,40 @@ impl NvimApi {
     }
     /// Sets the current window.
     pub async fn set_current_win(&self, win: &Window) -> Result<()> {
+        self.require("nvim_set_current_win", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_current_win", req).await?)
     }
+    /// Send [`Self::set_current_win`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_current_win_notify(&self, win: &Window) -> Result<()> {
+        self.require("nvim_set_current_win", 1)?;
+        #[allow(unused_variables)]
+        let req = win;
+        Ok(self.rpc_notify("nvim_set_current_win", req).await?)
+    }
     /// Creates a new, empty, unnamed buffer.
     pub async fn create_buf(&self, listed: bool, scratch: bool) -> Result<Buffer> {
+        self.require("nvim_create_buf", 6)?;
         #[allow(unused_variables)]
         let req = (listed, scratch);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_create_buf", req).await?)
     }
+    /// Send [`Self::create_buf`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn create_buf_notify(&self, listed: bool, scratch: bool) -> Result<()> {
+        self.require("nvim_create_buf", 6)?;
+        #[allow(unused_variables)]
+        let req = (listed, scratch);
+        Ok(self.rpc_notify("nvim_create_buf", req).await?)
+    }
     /// Open a terminal instance in a buffer
     ///
     /// By default (and currently the only option) the terminal will not be
@@ -1283,12 +2164,23 @@ impl NvimApi {
     /// then display it using |nvim_open_win()|, and then call this function. Then
     /// |nvim_chan_send()| can be called immediately to process sequences in a
     /// virtual terminal having the intended size.
-    pub async fn open_term(&self, buf: &Buffer, opts: HashMap<String, Value>) -> Result<i64> {
+    pub async fn open_term(&self, buf: &Buffer, opts: opts::OpenTerm) -> Result<i64> {
+        self.require("nvim_open_term", 7)?;
         #[allow(unused_variables)]
         let req = (buf, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_open_term", req).await?)
     }
+    /// Send [`Self::open_term`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn open_term_notify(&self, buf: &Buffer, opts: opts::OpenTerm) -> Result<()> {
+        self.require("nvim_open_term", 7)?;
+        #[allow(unused_variables)]
+        let req = (buf, opts);
+        Ok(self.rpc_notify("nvim_open_term", req).await?)
+    }
     /// Send data to channel. For a job, it writes it to the stdin of the
     /// process. For the stdio channel `channel-stdio`, it writes to Nvim's
     /// stdout. For an internal terminal instance (`nvim_open_term()`) it writes
@@ -1298,13 +2190,25 @@ impl NvimApi {
     /// created with rpc=true then the channel expects RPC messages, use
     /// `vim.rpcnotify()` and `vim.rpcrequest()` instead.
     pub async fn chan_send(&self, chan: i64, data: &str) -> Result<()> {
+        self.require("nvim_chan_send", 7)?;
         #[allow(unused_variables)]
         let req = (chan, data);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_chan_send", req).await?)
     }
+    /// Send [`Self::chan_send`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn chan_send_notify(&self, chan: i64, data: &str) -> Result<()> {
+        self.require("nvim_chan_send", 7)?;
+        #[allow(unused_variables)]
+        let req = (chan, data);
+        Ok(self.rpc_notify("nvim_chan_send", req).await?)
+    }
     /// Gets the current list of tabpage handles.
     pub async fn list_tabpages(&self) -> Result<Vec<TabPage>> {
+        self.require("nvim_list_tabpages", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1312,6 +2216,7 @@ impl NvimApi {
     }
     /// Gets the current tabpage.
     pub async fn get_current_tabpage(&self) -> Result<TabPage> {
+        self.require("nvim_get_current_tabpage", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1319,11 +2224,22 @@ impl NvimApi {
     }
     /// Sets the current tabpage.
     pub async fn set_current_tabpage(&self, tabpage: &TabPage) -> Result<()> {
+        self.require("nvim_set_current_tabpage", 1)?;
         #[allow(unused_variables)]
         let req = tabpage;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_current_tabpage", req).await?)
     }
+    /// Send [`Self::set_current_tabpage`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_current_tabpage_notify(&self, tabpage: &TabPage) -> Result<()> {
+        self.require("nvim_set_current_tabpage", 1)?;
+        #[allow(unused_variables)]
+        let req = tabpage;
+        Ok(self.rpc_notify("nvim_set_current_tabpage", req).await?)
+    }
     /// Pastes at cursor (in any mode), and sets redo so dot (|.|) will repeat
     /// the input. UIs call this to implement paste, but it is also intended for
     /// use by scripts to input large, dot-repeatable blocks of text (as opposed
@@ -1337,30 +2253,59 @@ impl NvimApi {
     /// vim.paste()). On error or cancel, subsequent calls are ignored
     /// (drained) until the next paste is initiated (phase 1 or -1).
     pub async fn paste(&self, data: &str, crlf: bool, phase: i64) -> Result<bool> {
+        self.require("nvim_paste", 6)?;
         #[allow(unused_variables)]
         let req = (data, crlf, phase);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_paste", req).await?)
     }
+    /// Send [`Self::paste`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn paste_notify(&self, data: &str, crlf: bool, phase: i64) -> Result<()> {
+        self.require("nvim_paste", 6)?;
+        #[allow(unused_variables)]
+        let req = (data, crlf, phase);
+        Ok(self.rpc_notify("nvim_paste", req).await?)
+    }
     /// Puts text at cursor, in any mode. For dot-repeatable input, use
     /// |nvim_paste()|.
     ///
     /// Compare |:put| and |p| which are always linewise.
     pub async fn put(
         &self,
-        lines: Vec<String>,
+        lines: Vec<NvimString>,
         typ: &str,
         after: bool,
         follow: bool,
     ) -> Result<()> {
+        self.require("nvim_put", 6)?;
         #[allow(unused_variables)]
         let req = (lines, typ, after, follow);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_put", req).await?)
     }
+    /// Send [`Self::put`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn put_notify(
+        &self,
+        lines: Vec<NvimString>,
+        typ: &str,
+        after: bool,
+        follow: bool,
+    ) -> Result<()> {
+        self.require("nvim_put", 6)?;
+        #[allow(unused_variables)]
+        let req = (lines, typ, after, follow);
+        Ok(self.rpc_notify("nvim_put", req).await?)
+    }
     /// Returns the 24-bit RGB value of a |nvim_get_color_map()| color name or
     /// #rrggbb hexadecimal string.
     pub async fn get_color_by_name(&self, name: &str) -> Result<i64> {
+        self.require("nvim_get_color_by_name", 1)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
@@ -1371,16 +2316,15 @@ impl NvimApi {
     /// Keys are color names (e.g. Aqua) and values are 24-bit RGB color values
     /// (e.g. 65535).
     pub async fn get_color_map(&self) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_color_map", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_get_color_map", req).await?)
     }
     /// Gets a map of the current editor state.
-    pub async fn get_context(
-        &self,
-        opts: HashMap<String, Value>,
-    ) -> Result<HashMap<String, Value>> {
+    pub async fn get_context(&self, opts: opts::Context) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_context", 6)?;
         #[allow(unused_variables)]
         let req = opts;
         #[allow(clippy::needless_question_mark)]
@@ -1391,14 +2335,26 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_load_context", 6)?;
         #[allow(unused_variables)]
         let req = dict;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_load_context", req).await?)
     }
+    /// Send [`Self::load_context`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn load_context_notify(&self, dict: HashMap<String, Value>) -> Result<()> {
+        self.require("nvim_load_context", 6)?;
+        #[allow(unused_variables)]
+        let req = dict;
+        Ok(self.rpc_notify("nvim_load_context", req).await?)
+    }
     /// Gets the current mode. |mode()| blocking is true if Nvim is waiting for
     /// input.
     pub async fn get_mode(&self) -> Result<HashMap<String, Value>> {
+        self.require("nvim_get_mode", 2)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1409,6 +2365,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_keymap", 3)?;
         #[allow(unused_variables)]
         let req = mode;
         #[allow(clippy::needless_question_mark)]
@@ -1425,25 +2382,54 @@ impl NvimApi {
         mode: &str,
         lhs: &str,
         rhs: &str,
-        opts: HashMap<String, Value>,
+        opts: opts::Keymap,
     ) -> Result<()> {
+        self.require("nvim_set_keymap", 6)?;
         #[allow(unused_variables)]
         let req = (mode, lhs, rhs, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_keymap", req).await?)
     }
+    /// Send [`Self::set_keymap`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_keymap_notify(
+        &self,
+        mode: &str,
+        lhs: &str,
+        rhs: &str,
+        opts: opts::Keymap,
+    ) -> Result<()> {
+        self.require("nvim_set_keymap", 6)?;
+        #[allow(unused_variables)]
+        let req = (mode, lhs, rhs, opts);
+        Ok(self.rpc_notify("nvim_set_keymap", req).await?)
+    }
     /// Unmaps a global mapping for the given mode.
     ///
     /// To unmap a buffer-local mapping, use nvim_buf_del_keymap().
     pub async fn del_keymap(&self, mode: &str, lhs: &str) -> Result<()> {
+        self.require("nvim_del_keymap", 6)?;
         #[allow(unused_variables)]
         let req = (mode, lhs);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_keymap", req).await?)
     }
+    /// Send [`Self::del_keymap`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_keymap_notify(&self, mode: &str, lhs: &str) -> Result<()> {
+        self.require("nvim_del_keymap", 6)?;
+        #[allow(unused_variables)]
+        let req = (mode, lhs);
+        Ok(self.rpc_notify("nvim_del_keymap", req).await?)
+    }
     /// Returns a 2-tuple (Array), where item 0 is the current channel id and item
     /// 1 is the |api-metadata| map (Dict).
     pub async fn get_api_info(&self) -> Result<(u64, ApiInfo)> {
+        self.require("nvim_get_api_info", 1)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1469,13 +2455,32 @@ impl NvimApi {
         methods: HashMap<String, Value>,
         attributes: HashMap<String, Value>,
     ) -> Result<()> {
+        self.require("nvim_set_client_info", 4)?;
         #[allow(unused_variables)]
         let req = (name, version, typ, methods, attributes);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_set_client_info", req).await?)
     }
+    /// Send [`Self::set_client_info`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn set_client_info_notify(
+        &self,
+        name: &str,
+        version: HashMap<String, Value>,
+        typ: &str,
+        methods: HashMap<String, Value>,
+        attributes: HashMap<String, Value>,
+    ) -> Result<()> {
+        self.require("nvim_set_client_info", 4)?;
+        #[allow(unused_variables)]
+        let req = (name, version, typ, methods, attributes);
+        Ok(self.rpc_notify("nvim_set_client_info", req).await?)
+    }
     /// Gets information about a channel.
     pub async fn get_chan_info(&self, chan: i64) -> Result<ChanInfo> {
+        self.require("nvim_get_chan_info", 4)?;
         #[allow(unused_variables)]
         let req = chan;
         #[allow(clippy::needless_question_mark)]
@@ -1483,6 +2488,7 @@ impl NvimApi {
     }
     /// Get information about all open channels.
     pub async fn list_chans(&self) -> Result<Vec<ChanInfo>> {
+        self.require("nvim_list_chans", 4)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1493,6 +2499,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_list_uis", 4)?;
         #[allow(unused_variables)]
         let req = NO_PARAMS;
         #[allow(clippy::needless_question_mark)]
@@ -1500,6 +2507,7 @@ impl NvimApi {
     }
     /// Gets the immediate children of process pid.
     pub async fn get_proc_children(&self, pid: i64) -> Result<Vec<i64>> {
+        self.require("nvim_get_proc_children", 4)?;
         #[allow(unused_variables)]
         let req = pid;
         #[allow(clippy::needless_question_mark)]
@@ -1510,6 +2518,7 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_get_proc", 4)?;
         #[allow(unused_variables)]
         let req = pid;
         #[allow(clippy::needless_question_mark)]
@@ -1527,22 +2536,50 @@ impl NvimApi {
         item: i64,
         insert: bool,
         finish: bool,
-        opts: HashMap<String, Value>,
+        opts: opts::Empty,
     ) -> Result<()> {
+        self.require("nvim_select_popupmenu_item", 6)?;
         #[allow(unused_variables)]
         let req = (item, insert, finish, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_select_popupmenu_item", req).await?)
     }
+    /// Send [`Self::select_popupmenu_item`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn select_popupmenu_item_notify(
+        &self,
+        item: i64,
+        insert: bool,
+        finish: bool,
+        opts: opts::Empty,
+    ) -> Result<()> {
+        self.require("nvim_select_popupmenu_item", 6)?;
+        #[allow(unused_variables)]
+        let req = (item, insert, finish, opts);
+        Ok(self.rpc_notify("nvim_select_popupmenu_item", req).await?)
+    }
     /// Deletes an uppercase/file named mark. See mark-motions.
     ///
     /// Note: Lowercase name (or other buffer-local mark) is an error.
     pub async fn del_mark(&self, name: &str) -> Result<bool> {
+        self.require("nvim_del_mark", 8)?;
         #[allow(unused_variables)]
         let req = name;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_del_mark", req).await?)
     }
+    /// Send [`Self::del_mark`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn del_mark_notify(&self, name: &str) -> Result<()> {
+        self.require("nvim_del_mark", 8)?;
+        #[allow(unused_variables)]
+        let req = name;
+        Ok(self.rpc_notify("nvim_del_mark", req).await?)
+    }
     /// Returns a (row, col, buffer, buffername) tuple representing the position
     /// of the uppercase/file named mark. End of line column position is
     /// returned as |v:maxcol| (big number). See |mark-motions|.
@@ -1553,8 +2590,9 @@ impl NvimApi {
     pub async fn get_mark(
         &self,
         name: &str,
-        opts: HashMap<String, Value>,
-    ) -> Result<(i64, i64, i64, String)> {
+        opts: opts::Empty,
+    ) -> Result<(i64, i64, i64, NvimString)> {
+        self.require("nvim_get_mark", 8)?;
         #[allow(unused_variables)]
         let req = (name, opts);
         #[allow(clippy::needless_question_mark)]
@@ -1564,13 +2602,28 @@ impl NvimApi {
     pub async fn eval_statusline(
         &self,
         str: &str,
-        opts: HashMap<String, Value>,
+        opts: opts::EvalStatusline,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_eval_statusline", 8)?;
         #[allow(unused_variables)]
         let req = (str, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_eval_statusline", req).await?)
     }
+    /// Send [`Self::eval_statusline`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn eval_statusline_notify(
+        &self,
+        str: &str,
+        opts: opts::EvalStatusline,
+    ) -> Result<()> {
+        self.require("nvim_eval_statusline", 8)?;
+        #[allow(unused_variables)]
+        let req = (str, opts);
+        Ok(self.rpc_notify("nvim_eval_statusline", req).await?)
+    }
     /// Executes Vimscript (multiline block of Ex commands), like anonymous
     /// |:source|.
     ///
@@ -1578,16 +2631,23 @@ impl NvimApi {
     /// (s:), etc.
     ///
     /// On execution error: fails with Vimscript error, updates v:errmsg.
-    pub async fn exec2(
-        &self,
-        src: &str,
-        opts: HashMap<String, Value>,
-    ) -> Result<HashMap<String, Value>> {
+    pub async fn exec2(&self, src: &str, opts: opts::ExecOpts) -> Result<HashMap<String, Value>> {
+        self.require("nvim_exec2", 11)?;
         #[allow(unused_variables)]
         let req = (src, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_exec2", req).await?)
     }
+    /// Send [`Self::exec2`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn exec2_notify(&self, src: &str, opts: opts::ExecOpts) -> Result<()> {
+        self.require("nvim_exec2", 11)?;
+        #[allow(unused_variables)]
+        let req = (src, opts);
+        Ok(self.rpc_notify("nvim_exec2", req).await?)
+    }
     /// Executes an Ex command.
     ///
     /// On execution error: fails with Vimscript error, updates v:errmsg.
@@ -1596,11 +2656,22 @@ impl NvimApi {
     /// a structured way before executing it, modify the result of
     /// `nvim_parse_cmd()` then pass it to `nvim_cmd()`.
     pub async fn command(&self, cmd: &str) -> Result<()> {
+        self.require("nvim_command", 1)?;
         #[allow(unused_variables)]
         let req = cmd;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_command", req).await?)
     }
+    /// Send [`Self::command`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn command_notify(&self, cmd: &str) -> Result<()> {
+        self.require("nvim_command", 1)?;
+        #[allow(unused_variables)]
+        let req = cmd;
+        Ok(self.rpc_notify("nvim_command", req).await?)
+    }
     /// Evaluates a Vimscript expression. Dicts and Lists are recursively
     /// expanded.
     ///
@@ -1609,11 +2680,22 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_eval", 1)?;
         #[allow(unused_variables)]
         let req = expr;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_eval", req).await?)
     }
+    /// Send [`Self::eval`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn eval_notify(&self, expr: &str) -> Result<()> {
+        self.require("nvim_eval", 1)?;
+        #[allow(unused_variables)]
+        let req = expr;
+        Ok(self.rpc_notify("nvim_eval", req).await?)
+    }
     /// Calls a Vimscript function with the given arguments.
     ///
     /// On execution error: fails with Vimscript error, updates v:errmsg.
@@ -1621,11 +2703,22 @@ impl NvimApi {
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_call_function", 1)?;
         #[allow(unused_variables)]
         let req = (func, args);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_call_function", req).await?)
     }
+    /// Send [`Self::call_function`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn call_function_notify(&self, func: &str, args: Vec<Value>) -> Result<()> {
+        self.require("nvim_call_function", 1)?;
+        #[allow(unused_variables)]
+        let req = (func, args);
+        Ok(self.rpc_notify("nvim_call_function", req).await?)
+    }
     /// Calls a Vimscript `Dictionary-function` with the given arguments.
     ///
     /// On execution error: fails with Vimscript error, updates v:errmsg.
@@ -1634,11 +2727,30 @@ impl NvimApi {
         T: Serialize,
         U: serde::de::DeserializeOwned,
     {
+        self.require("nvim_call_dict_function", 4)?;
         #[allow(unused_variables)]
         let req = (dict, func, args);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_call_dict_function", req).await?)
     }
+    /// Send [`Self::call_dict_function`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn call_dict_function_notify<T>(
+        &self,
+        dict: T,
+        func: &str,
+        args: Vec<Value>,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_call_dict_function", 4)?;
+        #[allow(unused_variables)]
+        let req = (dict, func, args);
+        Ok(self.rpc_notify("nvim_call_dict_function", req).await?)
+    }
     /// Parse a Vimscript expression.
     pub async fn parse_expression(
         &self,
@@ -1646,11 +2758,22 @@ impl NvimApi {
         flags: &str,
         hl: bool,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_parse_expression", 4)?;
         #[allow(unused_variables)]
         let req = (expr, flags, hl);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_parse_expression", req).await?)
     }
+    /// Send [`Self::parse_expression`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn parse_expression_notify(&self, expr: &str, flags: &str, hl: bool) -> Result<()> {
+        self.require("nvim_parse_expression", 4)?;
+        #[allow(unused_variables)]
+        let req = (expr, flags, hl);
+        Ok(self.rpc_notify("nvim_parse_expression", req).await?)
+    }
     /// Opens a new split window, or a floating window if relative is specified,
     /// or an external window (managed by the UI) if external is specified.
     ///
@@ -1685,28 +2808,56 @@ impl NvimApi {
     /// could let floats hover outside of the main window like a tooltip, but this
     /// should not be used to specify arbitrary WM screen positions.
     pub async fn open_win(&self, buf: &Buffer, enter: bool, config: WindowConf) -> Result<Window> {
+        self.require("nvim_open_win", 6)?;
         #[allow(unused_variables)]
         let req = (buf, enter, config);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_open_win", req).await?)
     }
+    /// Send [`Self::open_win`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn open_win_notify(
+        &self,
+        buf: &Buffer,
+        enter: bool,
+        config: WindowConf,
+    ) -> Result<()> {
+        self.require("nvim_open_win", 6)?;
+        #[allow(unused_variables)]
+        let req = (buf, enter, config);
+        Ok(self.rpc_notify("nvim_open_win", req).await?)
+    }
     /// Configures window layout. Cannot be used to move the last window in a
     /// tabpage to a different one.
     ///
     /// When reconfiguring a window, absent option keys will not be changed.
     /// row/col and relative must be reconfigured together.
     pub async fn win_set_config(&self, win: &Window, config: WindowConf) -> Result<()> {
+        self.require("nvim_win_set_config", 6)?;
         #[allow(unused_variables)]
         let req = (win, config);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_config", req).await?)
     }
+    /// Send [`Self::win_set_config`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_config_notify(&self, win: &Window, config: WindowConf) -> Result<()> {
+        self.require("nvim_win_set_config", 6)?;
+        #[allow(unused_variables)]
+        let req = (win, config);
+        Ok(self.rpc_notify("nvim_win_set_config", req).await?)
+    }
     /// Gets window configuration.
     ///
     /// The returned value may be given to `nvim_open_win()`.
     ///
     /// relative is empty for normal windows.
     pub async fn win_get_config(&self, win: &Window) -> Result<WindowConf> {
+        self.require("nvim_win_get_config", 6)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1714,6 +2865,7 @@ impl NvimApi {
     }
     /// Gets the current buffer in a window
     pub async fn win_get_buf(&self, win: &Window) -> Result<Buffer> {
+        self.require("nvim_win_get_buf", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1721,15 +2873,27 @@ impl NvimApi {
     }
     /// Sets the current buffer in a window, without side effects
     pub async fn win_set_buf(&self, win: &Window, buf: &Buffer) -> Result<()> {
+        self.require("nvim_win_set_buf", 5)?;
         #[allow(unused_variables)]
         let req = (win, buf);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_buf", req).await?)
     }
+    /// Send [`Self::win_set_buf`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_buf_notify(&self, win: &Window, buf: &Buffer) -> Result<()> {
+        self.require("nvim_win_set_buf", 5)?;
+        #[allow(unused_variables)]
+        let req = (win, buf);
+        Ok(self.rpc_notify("nvim_win_set_buf", req).await?)
+    }
     /// Gets the (1,0)-indexed, buffer-relative cursor position for a given window
     /// (different windows showing the same buffer have independent cursor
     /// positions).
     pub async fn win_get_cursor(&self, win: &Window) -> Result<(i64, i64)> {
+        self.require("nvim_win_get_cursor", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1738,13 +2902,25 @@ impl NvimApi {
     /// Sets the (1,0)-indexed cursor position in the window. This scrolls the
     /// window even if it is not the current one.
     pub async fn win_set_cursor(&self, win: &Window, pos: (i64, i64)) -> Result<()> {
+        self.require("nvim_win_set_cursor", 1)?;
         #[allow(unused_variables)]
         let req = (win, pos);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_cursor", req).await?)
     }
+    /// Send [`Self::win_set_cursor`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_cursor_notify(&self, win: &Window, pos: (i64, i64)) -> Result<()> {
+        self.require("nvim_win_set_cursor", 1)?;
+        #[allow(unused_variables)]
+        let req = (win, pos);
+        Ok(self.rpc_notify("nvim_win_set_cursor", req).await?)
+    }
     /// Gets the window height
     pub async fn win_get_height(&self, win: &Window) -> Result<i64> {
+        self.require("nvim_win_get_height", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1752,13 +2928,25 @@ impl NvimApi {
     }
     /// Sets the window height.
     pub async fn win_set_height(&self, win: &Window, height: i64) -> Result<()> {
+        self.require("nvim_win_set_height", 1)?;
         #[allow(unused_variables)]
         let req = (win, height);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_height", req).await?)
     }
+    /// Send [`Self::win_set_height`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_height_notify(&self, win: &Window, height: i64) -> Result<()> {
+        self.require("nvim_win_set_height", 1)?;
+        #[allow(unused_variables)]
+        let req = (win, height);
+        Ok(self.rpc_notify("nvim_win_set_height", req).await?)
+    }
     /// Gets the window width
     pub async fn win_get_width(&self, win: &Window) -> Result<i64> {
+        self.require("nvim_win_get_width", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1767,16 +2955,28 @@ impl NvimApi {
     /// Sets the window width. This will only succeed if the screen is split
     /// vertically.
     pub async fn win_set_width(&self, win: &Window, width: i64) -> Result<()> {
+        self.require("nvim_win_set_width", 1)?;
         #[allow(unused_variables)]
         let req = (win, width);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_width", req).await?)
     }
+    /// Send [`Self::win_set_width`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_width_notify(&self, win: &Window, width: i64) -> Result<()> {
+        self.require("nvim_win_set_width", 1)?;
+        #[allow(unused_variables)]
+        let req = (win, width);
+        Ok(self.rpc_notify("nvim_win_set_width", req).await?)
+    }
     /// Gets a window-scoped (w:) variable
     pub async fn win_get_var<T>(&self, win: &Window, name: &str) -> Result<T>
     where
         T: serde::de::DeserializeOwned,
     {
+        self.require("nvim_win_get_var", 1)?;
         #[allow(unused_variables)]
         let req = (win, name);
         #[allow(clippy::needless_question_mark)]
@@ -1787,20 +2987,46 @@ impl NvimApi {
     where
         T: Serialize,
     {
+        self.require("nvim_win_set_var", 1)?;
         #[allow(unused_variables)]
         let req = (win, name, value);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_var", req).await?)
     }
+    /// Send [`Self::win_set_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_var_notify<T>(&self, win: &Window, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.require("nvim_win_set_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (win, name, value);
+        Ok(self.rpc_notify("nvim_win_set_var", req).await?)
+    }
     /// Removes a window-scoped (w:) variable
     pub async fn win_del_var(&self, win: &Window, name: &str) -> Result<()> {
+        self.require("nvim_win_del_var", 1)?;
         #[allow(unused_variables)]
         let req = (win, name);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_del_var", req).await?)
     }
+    /// Send [`Self::win_del_var`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_del_var_notify(&self, win: &Window, name: &str) -> Result<()> {
+        self.require("nvim_win_del_var", 1)?;
+        #[allow(unused_variables)]
+        let req = (win, name);
+        Ok(self.rpc_notify("nvim_win_del_var", req).await?)
+    }
     /// Gets the window position in display cells. First position is zero.
     pub async fn win_get_position(&self, win: &Window) -> Result<(i64, i64)> {
+        self.require("nvim_win_get_position", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1808,6 +3034,7 @@ impl NvimApi {
     }
     /// Gets the window tabpage
     pub async fn win_get_tabpage(&self, win: &Window) -> Result<TabPage> {
+        self.require("nvim_win_get_tabpage", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1815,6 +3042,7 @@ impl NvimApi {
     }
     /// Gets the window number
     pub async fn win_get_number(&self, win: &Window) -> Result<i64> {
+        self.require("nvim_win_get_number", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1822,6 +3050,7 @@ impl NvimApi {
     }
     /// Checks if a window is valid
     pub async fn win_is_valid(&self, win: &Window) -> Result<bool> {
+        self.require("nvim_win_is_valid", 1)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
@@ -1834,29 +3063,62 @@ impl NvimApi {
     /// it, or bufhidden is unload, delete or wipe as opposed to |:close|
     /// or |nvim_win_close()|, which will close the buffer.
     pub async fn win_hide(&self, win: &Window) -> Result<()> {
+        self.require("nvim_win_hide", 7)?;
         #[allow(unused_variables)]
         let req = win;
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_hide", req).await?)
     }
+    /// Send [`Self::win_hide`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_hide_notify(&self, win: &Window) -> Result<()> {
+        self.require("nvim_win_hide", 7)?;
+        #[allow(unused_variables)]
+        let req = win;
+        Ok(self.rpc_notify("nvim_win_hide", req).await?)
+    }
     /// Closes the window (like |:close| with a |window-ID|).
     pub async fn win_close(&self, win: &Window, force: bool) -> Result<()> {
+        self.require("nvim_win_close", 6)?;
         #[allow(unused_variables)]
         let req = (win, force);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_close", req).await?)
     }
+    /// Send [`Self::win_close`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_close_notify(&self, win: &Window, force: bool) -> Result<()> {
+        self.require("nvim_win_close", 6)?;
+        #[allow(unused_variables)]
+        let req = (win, force);
+        Ok(self.rpc_notify("nvim_win_close", req).await?)
+    }
     /// Set highlight namespace for a window. This will use highlights defined
     /// with |nvim_set_hl()| for this namespace, but fall back to global
     /// highlights (ns=0) when missing.
     ///
     /// This takes precedence over the winhighlight option.
     pub async fn win_set_hl_ns(&self, win: &Window, ns_id: i64) -> Result<()> {
+        self.require("nvim_win_set_hl_ns", 10)?;
         #[allow(unused_variables)]
         let req = (win, ns_id);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_set_hl_ns", req).await?)
     }
+    /// Send [`Self::win_set_hl_ns`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    pub async fn win_set_hl_ns_notify(&self, win: &Window, ns_id: i64) -> Result<()> {
+        self.require("nvim_win_set_hl_ns", 10)?;
+        #[allow(unused_variables)]
+        let req = (win, ns_id);
+        Ok(self.rpc_notify("nvim_win_set_hl_ns", req).await?)
+    }
     /// Computes the number of screen lines occupied by a range of text in a given
     /// window. Works for off-screen text and takes folds into account.
     ///
@@ -1867,14 +3129,31 @@ impl NvimApi {
     /// result when end_row is omitted.
     ///
     /// Line indexing is similar to `nvim_buf_get_text()`.
+    #[cfg(feature = "nvim-0-10")]
     pub async fn win_text_height(
         &self,
         win: &Window,
-        opts: HashMap<String, Value>,
+        opts: opts::WinTextHeight,
     ) -> Result<HashMap<String, Value>> {
+        self.require("nvim_win_text_height", 12)?;
         #[allow(unused_variables)]
         let req = (win, opts);
         #[allow(clippy::needless_question_mark)]
         Ok(self.rpc_call("nvim_win_text_height", req).await?)
     }
+    /// Send [`Self::win_text_height`] as a notification, without waiting for a response.
+    ///
+    /// Errors are reported asynchronously through `nvim_error_event`, which plugins receive in
+    /// `NviPlugin::error_event`.
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn win_text_height_notify(
+        &self,
+        win: &Window,
+        opts: opts::WinTextHeight,
+    ) -> Result<()> {
+        self.require("nvim_win_text_height", 12)?;
+        #[allow(unused_variables)]
+        let req = (win, opts);
+        Ok(self.rpc_notify("nvim_win_text_height", req).await?)
+    }
 }
@@ -0,0 +1,1090 @@
+//! Methods on the `Buffer`, `Window` and `TabPage` handles, and bound handles that carry
+//! their client. Generated from the `nvim_buf_`, `nvim_win_` and `nvim_tabpage_` functions
+//! in the API definition by `protoc handles`.
+#![allow(clippy::doc_lazy_continuation)]
+#![allow(deprecated)]
+#![allow(clippy::multiple_inherent_impl)]
+use std::collections::HashMap;
+
+use mrpc::Value;
+use serde::Serialize;
+
+use super::{opts, types::*};
+use crate::{client::Client, error::Result};
+impl Buffer {
+    /// Bind this buffer to a client.
+    pub fn bind<'c>(&self, c: &'c Client) -> BufferRef<'c> {
+        BufferRef::new(self.clone(), c)
+    }
+    /// Returns the number of lines in the given buffer.
+    ///
+    /// See [`NvimApi::buf_line_count`](super::NvimApi::buf_line_count).
+    pub async fn line_count(&self, c: &Client) -> Result<i64> {
+        c.nvim.buf_line_count(self).await
+    }
+    /// Activates buffer-update events on a channel, or as Lua callbacks.
+    ///
+    /// See [`NvimApi::buf_attach`](super::NvimApi::buf_attach).
+    pub async fn attach(
+        &self,
+        c: &Client,
+        send_buffer: bool,
+        opts: opts::BufAttach,
+    ) -> Result<bool> {
+        c.nvim.buf_attach(self, send_buffer, opts).await
+    }
+    /// Deactivates buffer-update events on the channel.
+    ///
+    /// See [`NvimApi::buf_detach`](super::NvimApi::buf_detach).
+    pub async fn detach(&self, c: &Client) -> Result<bool> {
+        c.nvim.buf_detach(self).await
+    }
+    /// Gets a line-range from the buffer.
+    ///
+    /// See [`NvimApi::buf_get_lines`](super::NvimApi::buf_get_lines).
+    pub async fn lines(
+        &self,
+        c: &Client,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+    ) -> Result<Vec<NvimString>> {
+        c.nvim
+            .buf_get_lines(self, start, end, strict_indexing)
+            .await
+    }
+    /// Sets (replaces) a line-range in the buffer.
+    ///
+    /// See [`NvimApi::buf_set_lines`](super::NvimApi::buf_set_lines).
+    pub async fn set_lines(
+        &self,
+        c: &Client,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        c.nvim
+            .buf_set_lines(self, start, end, strict_indexing, replacement)
+            .await
+    }
+    /// Sets (replaces) a range in the buffer
+    ///
+    /// See [`NvimApi::buf_set_text`](super::NvimApi::buf_set_text).
+    pub async fn set_text(
+        &self,
+        c: &Client,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        c.nvim
+            .buf_set_text(self, start_row, start_col, end_row, end_col, replacement)
+            .await
+    }
+    /// Gets a range from the buffer.
+    ///
+    /// See [`NvimApi::buf_get_text`](super::NvimApi::buf_get_text).
+    pub async fn text(
+        &self,
+        c: &Client,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        opts: opts::Empty,
+    ) -> Result<Vec<NvimString>> {
+        c.nvim
+            .buf_get_text(self, start_row, start_col, end_row, end_col, opts)
+            .await
+    }
+    /// Returns the byte offset of a line (0-indexed). |api-indexing|
+    ///
+    /// See [`NvimApi::buf_get_offset`](super::NvimApi::buf_get_offset).
+    pub async fn offset(&self, c: &Client, index: i64) -> Result<i64> {
+        c.nvim.buf_get_offset(self, index).await
+    }
+    /// Gets a buffer-scoped (b:) variable.
+    ///
+    /// See [`NvimApi::buf_get_var`](super::NvimApi::buf_get_var).
+    pub async fn var<T>(&self, c: &Client, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        c.nvim.buf_get_var(self, name).await
+    }
+    /// Gets a changed tick of a buffer
+    ///
+    /// See [`NvimApi::buf_get_changedtick`](super::NvimApi::buf_get_changedtick).
+    pub async fn changedtick(&self, c: &Client) -> Result<i64> {
+        c.nvim.buf_get_changedtick(self).await
+    }
+    /// Gets a list of buffer-local |mapping| definitions.
+    ///
+    /// See [`NvimApi::buf_get_keymap`](super::NvimApi::buf_get_keymap).
+    pub async fn keymap<T>(&self, c: &Client, mode: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        c.nvim.buf_get_keymap(self, mode).await
+    }
+    /// Sets a buffer-local |mapping| for the given mode.
+    ///
+    /// See [`NvimApi::buf_set_keymap`](super::NvimApi::buf_set_keymap).
+    pub async fn set_keymap(
+        &self,
+        c: &Client,
+        mode: &str,
+        lhs: &str,
+        rhs: &str,
+        opts: opts::Keymap,
+    ) -> Result<()> {
+        c.nvim.buf_set_keymap(self, mode, lhs, rhs, opts).await
+    }
+    /// Unmaps a buffer-local |mapping| for the given mode.
+    ///
+    /// See [`NvimApi::buf_del_keymap`](super::NvimApi::buf_del_keymap).
+    pub async fn del_keymap(&self, c: &Client, mode: &str, lhs: &str) -> Result<()> {
+        c.nvim.buf_del_keymap(self, mode, lhs).await
+    }
+    /// Sets a buffer-scoped (b:) variable
+    ///
+    /// See [`NvimApi::buf_set_var`](super::NvimApi::buf_set_var).
+    pub async fn set_var<T>(&self, c: &Client, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        c.nvim.buf_set_var(self, name, value).await
+    }
+    /// Removes a buffer-scoped (b:) variable
+    ///
+    /// See [`NvimApi::buf_del_var`](super::NvimApi::buf_del_var).
+    pub async fn del_var(&self, c: &Client, name: &str) -> Result<()> {
+        c.nvim.buf_del_var(self, name).await
+    }
+    /// Gets the full file name for the buffer
+    ///
+    /// See [`NvimApi::buf_get_name`](super::NvimApi::buf_get_name).
+    pub async fn name(&self, c: &Client) -> Result<NvimString> {
+        c.nvim.buf_get_name(self).await
+    }
+    /// Sets the full file name for a buffer, like :file_f
+    ///
+    /// See [`NvimApi::buf_set_name`](super::NvimApi::buf_set_name).
+    pub async fn set_name(&self, c: &Client, name: &str) -> Result<()> {
+        c.nvim.buf_set_name(self, name).await
+    }
+    /// Checks if a buffer is valid and loaded. See |api-buffer| for more info
+    /// about unloaded buffers.
+    ///
+    /// See [`NvimApi::buf_is_loaded`](super::NvimApi::buf_is_loaded).
+    pub async fn is_loaded(&self, c: &Client) -> Result<bool> {
+        c.nvim.buf_is_loaded(self).await
+    }
+    /// Deletes the buffer. See |:bwipeout|
+    ///
+    /// See [`NvimApi::buf_delete`](super::NvimApi::buf_delete).
+    pub async fn delete(&self, c: &Client, opts: opts::BufDelete) -> Result<()> {
+        c.nvim.buf_delete(self, opts).await
+    }
+    /// Checks if a buffer is valid.
+    ///
+    /// See [`NvimApi::buf_is_valid`](super::NvimApi::buf_is_valid).
+    pub async fn is_valid(&self, c: &Client) -> Result<bool> {
+        c.nvim.buf_is_valid(self).await
+    }
+    /// Deletes a named mark in the buffer. See |mark-motions|.
+    ///
+    /// See [`NvimApi::buf_del_mark`](super::NvimApi::buf_del_mark).
+    pub async fn del_mark(&self, c: &Client, name: &str) -> Result<bool> {
+        c.nvim.buf_del_mark(self, name).await
+    }
+    /// Sets a named mark in the given buffer, all marks are allowed
+    /// file/uppercase, visual, last change, etc. See mark-motions.
+    ///
+    /// See [`NvimApi::buf_set_mark`](super::NvimApi::buf_set_mark).
+    pub async fn set_mark(
+        &self,
+        c: &Client,
+        name: &str,
+        line: i64,
+        col: i64,
+        opts: opts::Empty,
+    ) -> Result<bool> {
+        c.nvim.buf_set_mark(self, name, line, col, opts).await
+    }
+    /// Returns a (row,col) tuple representing the position of the named mark.
+    /// End of line column position is returned as |v:maxcol| (big number).
+    /// See |mark-motions|.
+    ///
+    /// See [`NvimApi::buf_get_mark`](super::NvimApi::buf_get_mark).
+    pub async fn mark(&self, c: &Client, name: &str) -> Result<(i64, i64)> {
+        c.nvim.buf_get_mark(self, name).await
+    }
+    /// Creates a buffer-local command `user-commands`.
+    ///
+    /// See [`NvimApi::buf_create_user_command`](super::NvimApi::buf_create_user_command).
+    pub async fn create_user_command<T>(
+        &self,
+        c: &Client,
+        name: &str,
+        cmd: T,
+        opts: opts::UserCommand,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        c.nvim.buf_create_user_command(self, name, cmd, opts).await
+    }
+    /// Delete a buffer-local user-defined command.
+    ///
+    /// See [`NvimApi::buf_del_user_command`](super::NvimApi::buf_del_user_command).
+    pub async fn del_user_command(&self, c: &Client, name: &str) -> Result<()> {
+        c.nvim.buf_del_user_command(self, name).await
+    }
+    /// Gets a map of buffer-local |user-commands|.
+    ///
+    /// See [`NvimApi::buf_get_commands`](super::NvimApi::buf_get_commands).
+    pub async fn commands(
+        &self,
+        c: &Client,
+        opts: opts::GetCommands,
+    ) -> Result<HashMap<String, Value>> {
+        c.nvim.buf_get_commands(self, opts).await
+    }
+    /// Gets the position (0-indexed) of an |extmark|.
+    ///
+    /// See [`NvimApi::buf_get_extmark_by_id`](super::NvimApi::buf_get_extmark_by_id).
+    pub async fn extmark_by_id<T>(
+        &self,
+        c: &Client,
+        ns_id: i64,
+        id: i64,
+        opts: opts::GetExtmark,
+    ) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        c.nvim.buf_get_extmark_by_id(self, ns_id, id, opts).await
+    }
+    /// Gets |extmarks| in traversal order from a |charwise| region defined by
+    /// buffer positions (inclusive, 0-indexed |api-indexing|).
+    ///
+    /// See [`NvimApi::buf_get_extmarks`](super::NvimApi::buf_get_extmarks).
+    pub async fn extmarks<T, U, V>(
+        &self,
+        c: &Client,
+        ns_id: i64,
+        start: T,
+        end: U,
+        opts: opts::GetExtmarks,
+    ) -> Result<V>
+    where
+        T: Serialize,
+        U: Serialize,
+        V: serde::de::DeserializeOwned,
+    {
+        c.nvim.buf_get_extmarks(self, ns_id, start, end, opts).await
+    }
+    /// Creates or updates an extmark.
+    ///
+    /// See [`NvimApi::buf_set_extmark`](super::NvimApi::buf_set_extmark).
+    pub async fn set_extmark(
+        &self,
+        c: &Client,
+        ns_id: i64,
+        line: i64,
+        col: i64,
+        opts: opts::SetExtmark,
+    ) -> Result<i64> {
+        c.nvim.buf_set_extmark(self, ns_id, line, col, opts).await
+    }
+    /// Removes an extmark.
+    ///
+    /// See [`NvimApi::buf_del_extmark`](super::NvimApi::buf_del_extmark).
+    pub async fn del_extmark(&self, c: &Client, ns_id: i64, id: i64) -> Result<bool> {
+        c.nvim.buf_del_extmark(self, ns_id, id).await
+    }
+    /// Clears namespaced objects (highlights, extmarks, virtual text) from a
+    /// region.
+    ///
+    /// See [`NvimApi::buf_clear_namespace`](super::NvimApi::buf_clear_namespace).
+    pub async fn clear_namespace(
+        &self,
+        c: &Client,
+        ns_id: i64,
+        line_start: i64,
+        line_end: i64,
+    ) -> Result<()> {
+        c.nvim
+            .buf_clear_namespace(self, ns_id, line_start, line_end)
+            .await
+    }
+}
+/// A buffer bound to a client, so that methods don't need the client passed in.
+#[derive(Debug, Clone)]
+pub struct BufferRef<'c> {
+    handle: Buffer,
+    client: &'c Client,
+}
+impl<'c> BufferRef<'c> {
+    /// Bind a handle to a client.
+    pub fn new(handle: Buffer, client: &'c Client) -> Self {
+        Self { handle, client }
+    }
+    /// The underlying buffer handle.
+    pub fn handle(&self) -> &Buffer {
+        &self.handle
+    }
+    /// The client this handle is bound to.
+    pub fn client(&self) -> &'c Client {
+        self.client
+    }
+    /// Returns the number of lines in the given buffer.
+    ///
+    /// See [`NvimApi::buf_line_count`](super::NvimApi::buf_line_count).
+    pub async fn line_count(&self) -> Result<i64> {
+        self.handle.line_count(self.client).await
+    }
+    /// Activates buffer-update events on a channel, or as Lua callbacks.
+    ///
+    /// See [`NvimApi::buf_attach`](super::NvimApi::buf_attach).
+    pub async fn attach(&self, send_buffer: bool, opts: opts::BufAttach) -> Result<bool> {
+        self.handle.attach(self.client, send_buffer, opts).await
+    }
+    /// Deactivates buffer-update events on the channel.
+    ///
+    /// See [`NvimApi::buf_detach`](super::NvimApi::buf_detach).
+    pub async fn detach(&self) -> Result<bool> {
+        self.handle.detach(self.client).await
+    }
+    /// Gets a line-range from the buffer.
+    ///
+    /// See [`NvimApi::buf_get_lines`](super::NvimApi::buf_get_lines).
+    pub async fn lines(
+        &self,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+    ) -> Result<Vec<NvimString>> {
+        self.handle
+            .lines(self.client, start, end, strict_indexing)
+            .await
+    }
+    /// Sets (replaces) a line-range in the buffer.
+    ///
+    /// See [`NvimApi::buf_set_lines`](super::NvimApi::buf_set_lines).
+    pub async fn set_lines(
+        &self,
+        start: i64,
+        end: i64,
+        strict_indexing: bool,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        self.handle
+            .set_lines(self.client, start, end, strict_indexing, replacement)
+            .await
+    }
+    /// Sets (replaces) a range in the buffer
+    ///
+    /// See [`NvimApi::buf_set_text`](super::NvimApi::buf_set_text).
+    pub async fn set_text(
+        &self,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        replacement: Vec<NvimString>,
+    ) -> Result<()> {
+        self.handle
+            .set_text(
+                self.client,
+                start_row,
+                start_col,
+                end_row,
+                end_col,
+                replacement,
+            )
+            .await
+    }
+    /// Gets a range from the buffer.
+    ///
+    /// See [`NvimApi::buf_get_text`](super::NvimApi::buf_get_text).
+    pub async fn text(
+        &self,
+        start_row: i64,
+        start_col: i64,
+        end_row: i64,
+        end_col: i64,
+        opts: opts::Empty,
+    ) -> Result<Vec<NvimString>> {
+        self.handle
+            .text(self.client, start_row, start_col, end_row, end_col, opts)
+            .await
+    }
+    /// Returns the byte offset of a line (0-indexed). |api-indexing|
+    ///
+    /// See [`NvimApi::buf_get_offset`](super::NvimApi::buf_get_offset).
+    pub async fn offset(&self, index: i64) -> Result<i64> {
+        self.handle.offset(self.client, index).await
+    }
+    /// Gets a buffer-scoped (b:) variable.
+    ///
+    /// See [`NvimApi::buf_get_var`](super::NvimApi::buf_get_var).
+    pub async fn var<T>(&self, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.handle.var(self.client, name).await
+    }
+    /// Gets a changed tick of a buffer
+    ///
+    /// See [`NvimApi::buf_get_changedtick`](super::NvimApi::buf_get_changedtick).
+    pub async fn changedtick(&self) -> Result<i64> {
+        self.handle.changedtick(self.client).await
+    }
+    /// Gets a list of buffer-local |mapping| definitions.
+    ///
+    /// See [`NvimApi::buf_get_keymap`](super::NvimApi::buf_get_keymap).
+    pub async fn keymap<T>(&self, mode: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.handle.keymap(self.client, mode).await
+    }
+    /// Sets a buffer-local |mapping| for the given mode.
+    ///
+    /// See [`NvimApi::buf_set_keymap`](super::NvimApi::buf_set_keymap).
+    pub async fn set_keymap(
+        &self,
+        mode: &str,
+        lhs: &str,
+        rhs: &str,
+        opts: opts::Keymap,
+    ) -> Result<()> {
+        self.handle
+            .set_keymap(self.client, mode, lhs, rhs, opts)
+            .await
+    }
+    /// Unmaps a buffer-local |mapping| for the given mode.
+    ///
+    /// See [`NvimApi::buf_del_keymap`](super::NvimApi::buf_del_keymap).
+    pub async fn del_keymap(&self, mode: &str, lhs: &str) -> Result<()> {
+        self.handle.del_keymap(self.client, mode, lhs).await
+    }
+    /// Sets a buffer-scoped (b:) variable
+    ///
+    /// See [`NvimApi::buf_set_var`](super::NvimApi::buf_set_var).
+    pub async fn set_var<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.handle.set_var(self.client, name, value).await
+    }
+    /// Removes a buffer-scoped (b:) variable
+    ///
+    /// See [`NvimApi::buf_del_var`](super::NvimApi::buf_del_var).
+    pub async fn del_var(&self, name: &str) -> Result<()> {
+        self.handle.del_var(self.client, name).await
+    }
+    /// Gets the full file name for the buffer
+    ///
+    /// See [`NvimApi::buf_get_name`](super::NvimApi::buf_get_name).
+    pub async fn name(&self) -> Result<NvimString> {
+        self.handle.name(self.client).await
+    }
+    /// Sets the full file name for a buffer, like :file_f
+    ///
+    /// See [`NvimApi::buf_set_name`](super::NvimApi::buf_set_name).
+    pub async fn set_name(&self, name: &str) -> Result<()> {
+        self.handle.set_name(self.client, name).await
+    }
+    /// Checks if a buffer is valid and loaded. See |api-buffer| for more info
+    /// about unloaded buffers.
+    ///
+    /// See [`NvimApi::buf_is_loaded`](super::NvimApi::buf_is_loaded).
+    pub async fn is_loaded(&self) -> Result<bool> {
+        self.handle.is_loaded(self.client).await
+    }
+    /// Deletes the buffer. See |:bwipeout|
+    ///
+    /// See [`NvimApi::buf_delete`](super::NvimApi::buf_delete).
+    pub async fn delete(&self, opts: opts::BufDelete) -> Result<()> {
+        self.handle.delete(self.client, opts).await
+    }
+    /// Checks if a buffer is valid.
+    ///
+    /// See [`NvimApi::buf_is_valid`](super::NvimApi::buf_is_valid).
+    pub async fn is_valid(&self) -> Result<bool> {
+        self.handle.is_valid(self.client).await
+    }
+    /// Deletes a named mark in the buffer. See |mark-motions|.
+    ///
+    /// See [`NvimApi::buf_del_mark`](super::NvimApi::buf_del_mark).
+    pub async fn del_mark(&self, name: &str) -> Result<bool> {
+        self.handle.del_mark(self.client, name).await
+    }
+    /// Sets a named mark in the given buffer, all marks are allowed
+    /// file/uppercase, visual, last change, etc. See mark-motions.
+    ///
+    /// See [`NvimApi::buf_set_mark`](super::NvimApi::buf_set_mark).
+    pub async fn set_mark(
+        &self,
+        name: &str,
+        line: i64,
+        col: i64,
+        opts: opts::Empty,
+    ) -> Result<bool> {
+        self.handle
+            .set_mark(self.client, name, line, col, opts)
+            .await
+    }
+    /// Returns a (row,col) tuple representing the position of the named mark.
+    /// End of line column position is returned as |v:maxcol| (big number).
+    /// See |mark-motions|.
+    ///
+    /// See [`NvimApi::buf_get_mark`](super::NvimApi::buf_get_mark).
+    pub async fn mark(&self, name: &str) -> Result<(i64, i64)> {
+        self.handle.mark(self.client, name).await
+    }
+    /// Creates a buffer-local command `user-commands`.
+    ///
+    /// See [`NvimApi::buf_create_user_command`](super::NvimApi::buf_create_user_command).
+    pub async fn create_user_command<T>(
+        &self,
+        name: &str,
+        cmd: T,
+        opts: opts::UserCommand,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.handle
+            .create_user_command(self.client, name, cmd, opts)
+            .await
+    }
+    /// Delete a buffer-local user-defined command.
+    ///
+    /// See [`NvimApi::buf_del_user_command`](super::NvimApi::buf_del_user_command).
+    pub async fn del_user_command(&self, name: &str) -> Result<()> {
+        self.handle.del_user_command(self.client, name).await
+    }
+    /// Gets a map of buffer-local |user-commands|.
+    ///
+    /// See [`NvimApi::buf_get_commands`](super::NvimApi::buf_get_commands).
+    pub async fn commands(&self, opts: opts::GetCommands) -> Result<HashMap<String, Value>> {
+        self.handle.commands(self.client, opts).await
+    }
+    /// Gets the position (0-indexed) of an |extmark|.
+    ///
+    /// See [`NvimApi::buf_get_extmark_by_id`](super::NvimApi::buf_get_extmark_by_id).
+    pub async fn extmark_by_id<T>(&self, ns_id: i64, id: i64, opts: opts::GetExtmark) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.handle
+            .extmark_by_id(self.client, ns_id, id, opts)
+            .await
+    }
+    /// Gets |extmarks| in traversal order from a |charwise| region defined by
+    /// buffer positions (inclusive, 0-indexed |api-indexing|).
+    ///
+    /// See [`NvimApi::buf_get_extmarks`](super::NvimApi::buf_get_extmarks).
+    pub async fn extmarks<T, U, V>(
+        &self,
+        ns_id: i64,
+        start: T,
+        end: U,
+        opts: opts::GetExtmarks,
+    ) -> Result<V>
+    where
+        T: Serialize,
+        U: Serialize,
+        V: serde::de::DeserializeOwned,
+    {
+        self.handle
+            .extmarks(self.client, ns_id, start, end, opts)
+            .await
+    }
+    /// Creates or updates an extmark.
+    ///
+    /// See [`NvimApi::buf_set_extmark`](super::NvimApi::buf_set_extmark).
+    pub async fn set_extmark(
+        &self,
+        ns_id: i64,
+        line: i64,
+        col: i64,
+        opts: opts::SetExtmark,
+    ) -> Result<i64> {
+        self.handle
+            .set_extmark(self.client, ns_id, line, col, opts)
+            .await
+    }
+    /// Removes an extmark.
+    ///
+    /// See [`NvimApi::buf_del_extmark`](super::NvimApi::buf_del_extmark).
+    pub async fn del_extmark(&self, ns_id: i64, id: i64) -> Result<bool> {
+        self.handle.del_extmark(self.client, ns_id, id).await
+    }
+    /// Clears namespaced objects (highlights, extmarks, virtual text) from a
+    /// region.
+    ///
+    /// See [`NvimApi::buf_clear_namespace`](super::NvimApi::buf_clear_namespace).
+    pub async fn clear_namespace(&self, ns_id: i64, line_start: i64, line_end: i64) -> Result<()> {
+        self.handle
+            .clear_namespace(self.client, ns_id, line_start, line_end)
+            .await
+    }
+}
+impl Window {
+    /// Bind this window to a client.
+    pub fn bind<'c>(&self, c: &'c Client) -> WindowRef<'c> {
+        WindowRef::new(self.clone(), c)
+    }
+    /// Configures window layout. Cannot be used to move the last window in a
+    /// tabpage to a different one.
+    ///
+    /// See [`NvimApi::win_set_config`](super::NvimApi::win_set_config).
+    pub async fn set_config(&self, c: &Client, config: WindowConf) -> Result<()> {
+        c.nvim.win_set_config(self, config).await
+    }
+    /// Gets window configuration.
+    ///
+    /// See [`NvimApi::win_get_config`](super::NvimApi::win_get_config).
+    pub async fn config(&self, c: &Client) -> Result<WindowConf> {
+        c.nvim.win_get_config(self).await
+    }
+    /// Gets the current buffer in a window
+    ///
+    /// See [`NvimApi::win_get_buf`](super::NvimApi::win_get_buf).
+    pub async fn buf(&self, c: &Client) -> Result<Buffer> {
+        c.nvim.win_get_buf(self).await
+    }
+    /// Sets the current buffer in a window, without side effects
+    ///
+    /// See [`NvimApi::win_set_buf`](super::NvimApi::win_set_buf).
+    pub async fn set_buf(&self, c: &Client, buf: &Buffer) -> Result<()> {
+        c.nvim.win_set_buf(self, buf).await
+    }
+    /// Gets the (1,0)-indexed, buffer-relative cursor position for a given window
+    /// (different windows showing the same buffer have independent cursor
+    /// positions).
+    ///
+    /// See [`NvimApi::win_get_cursor`](super::NvimApi::win_get_cursor).
+    pub async fn cursor(&self, c: &Client) -> Result<(i64, i64)> {
+        c.nvim.win_get_cursor(self).await
+    }
+    /// Sets the (1,0)-indexed cursor position in the window. This scrolls the
+    /// window even if it is not the current one.
+    ///
+    /// See [`NvimApi::win_set_cursor`](super::NvimApi::win_set_cursor).
+    pub async fn set_cursor(&self, c: &Client, pos: (i64, i64)) -> Result<()> {
+        c.nvim.win_set_cursor(self, pos).await
+    }
+    /// Gets the window height
+    ///
+    /// See [`NvimApi::win_get_height`](super::NvimApi::win_get_height).
+    pub async fn height(&self, c: &Client) -> Result<i64> {
+        c.nvim.win_get_height(self).await
+    }
+    /// Sets the window height.
+    ///
+    /// See [`NvimApi::win_set_height`](super::NvimApi::win_set_height).
+    pub async fn set_height(&self, c: &Client, height: i64) -> Result<()> {
+        c.nvim.win_set_height(self, height).await
+    }
+    /// Gets the window width
+    ///
+    /// See [`NvimApi::win_get_width`](super::NvimApi::win_get_width).
+    pub async fn width(&self, c: &Client) -> Result<i64> {
+        c.nvim.win_get_width(self).await
+    }
+    /// Sets the window width. This will only succeed if the screen is split
+    /// vertically.
+    ///
+    /// See [`NvimApi::win_set_width`](super::NvimApi::win_set_width).
+    pub async fn set_width(&self, c: &Client, width: i64) -> Result<()> {
+        c.nvim.win_set_width(self, width).await
+    }
+    /// Gets a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_get_var`](super::NvimApi::win_get_var).
+    pub async fn var<T>(&self, c: &Client, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        c.nvim.win_get_var(self, name).await
+    }
+    /// Sets a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_set_var`](super::NvimApi::win_set_var).
+    pub async fn set_var<T>(&self, c: &Client, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        c.nvim.win_set_var(self, name, value).await
+    }
+    /// Removes a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_del_var`](super::NvimApi::win_del_var).
+    pub async fn del_var(&self, c: &Client, name: &str) -> Result<()> {
+        c.nvim.win_del_var(self, name).await
+    }
+    /// Gets the window position in display cells. First position is zero.
+    ///
+    /// See [`NvimApi::win_get_position`](super::NvimApi::win_get_position).
+    pub async fn position(&self, c: &Client) -> Result<(i64, i64)> {
+        c.nvim.win_get_position(self).await
+    }
+    /// Gets the window tabpage
+    ///
+    /// See [`NvimApi::win_get_tabpage`](super::NvimApi::win_get_tabpage).
+    pub async fn tabpage(&self, c: &Client) -> Result<TabPage> {
+        c.nvim.win_get_tabpage(self).await
+    }
+    /// Gets the window number
+    ///
+    /// See [`NvimApi::win_get_number`](super::NvimApi::win_get_number).
+    pub async fn number(&self, c: &Client) -> Result<i64> {
+        c.nvim.win_get_number(self).await
+    }
+    /// Checks if a window is valid
+    ///
+    /// See [`NvimApi::win_is_valid`](super::NvimApi::win_is_valid).
+    pub async fn is_valid(&self, c: &Client) -> Result<bool> {
+        c.nvim.win_is_valid(self).await
+    }
+    /// Closes the window and hide the buffer it contains (like |:hide| with a
+    /// |window-ID|).
+    ///
+    /// See [`NvimApi::win_hide`](super::NvimApi::win_hide).
+    pub async fn hide(&self, c: &Client) -> Result<()> {
+        c.nvim.win_hide(self).await
+    }
+    /// Closes the window (like |:close| with a |window-ID|).
+    ///
+    /// See [`NvimApi::win_close`](super::NvimApi::win_close).
+    pub async fn close(&self, c: &Client, force: bool) -> Result<()> {
+        c.nvim.win_close(self, force).await
+    }
+    /// Set highlight namespace for a window. This will use highlights defined
+    /// with |nvim_set_hl()| for this namespace, but fall back to global
+    /// highlights (ns=0) when missing.
+    ///
+    /// See [`NvimApi::win_set_hl_ns`](super::NvimApi::win_set_hl_ns).
+    pub async fn set_hl_ns(&self, c: &Client, ns_id: i64) -> Result<()> {
+        c.nvim.win_set_hl_ns(self, ns_id).await
+    }
+    /// Computes the number of screen lines occupied by a range of text in a given
+    /// window. Works for off-screen text and takes folds into account.
+    ///
+    /// See [`NvimApi::win_text_height`](super::NvimApi::win_text_height).
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn text_height(
+        &self,
+        c: &Client,
+        opts: opts::WinTextHeight,
+    ) -> Result<HashMap<String, Value>> {
+        c.nvim.win_text_height(self, opts).await
+    }
+}
+/// A window bound to a client, so that methods don't need the client passed in.
+#[derive(Debug, Clone)]
+pub struct WindowRef<'c> {
+    handle: Window,
+    client: &'c Client,
+}
+impl<'c> WindowRef<'c> {
+    /// Bind a handle to a client.
+    pub fn new(handle: Window, client: &'c Client) -> Self {
+        Self { handle, client }
+    }
+    /// The underlying window handle.
+    pub fn handle(&self) -> &Window {
+        &self.handle
+    }
+    /// The client this handle is bound to.
+    pub fn client(&self) -> &'c Client {
+        self.client
+    }
+    /// Configures window layout. Cannot be used to move the last window in a
+    /// tabpage to a different one.
+    ///
+    /// See [`NvimApi::win_set_config`](super::NvimApi::win_set_config).
+    pub async fn set_config(&self, config: WindowConf) -> Result<()> {
+        self.handle.set_config(self.client, config).await
+    }
+    /// Gets window configuration.
+    ///
+    /// See [`NvimApi::win_get_config`](super::NvimApi::win_get_config).
+    pub async fn config(&self) -> Result<WindowConf> {
+        self.handle.config(self.client).await
+    }
+    /// Gets the current buffer in a window
+    ///
+    /// See [`NvimApi::win_get_buf`](super::NvimApi::win_get_buf).
+    pub async fn buf(&self) -> Result<Buffer> {
+        self.handle.buf(self.client).await
+    }
+    /// Sets the current buffer in a window, without side effects
+    ///
+    /// See [`NvimApi::win_set_buf`](super::NvimApi::win_set_buf).
+    pub async fn set_buf(&self, buf: &Buffer) -> Result<()> {
+        self.handle.set_buf(self.client, buf).await
+    }
+    /// Gets the (1,0)-indexed, buffer-relative cursor position for a given window
+    /// (different windows showing the same buffer have independent cursor
+    /// positions).
+    ///
+    /// See [`NvimApi::win_get_cursor`](super::NvimApi::win_get_cursor).
+    pub async fn cursor(&self) -> Result<(i64, i64)> {
+        self.handle.cursor(self.client).await
+    }
+    /// Sets the (1,0)-indexed cursor position in the window. This scrolls the
+    /// window even if it is not the current one.
+    ///
+    /// See [`NvimApi::win_set_cursor`](super::NvimApi::win_set_cursor).
+    pub async fn set_cursor(&self, pos: (i64, i64)) -> Result<()> {
+        self.handle.set_cursor(self.client, pos).await
+    }
+    /// Gets the window height
+    ///
+    /// See [`NvimApi::win_get_height`](super::NvimApi::win_get_height).
+    pub async fn height(&self) -> Result<i64> {
+        self.handle.height(self.client).await
+    }
+    /// Sets the window height.
+    ///
+    /// See [`NvimApi::win_set_height`](super::NvimApi::win_set_height).
+    pub async fn set_height(&self, height: i64) -> Result<()> {
+        self.handle.set_height(self.client, height).await
+    }
+    /// Gets the window width
+    ///
+    /// See [`NvimApi::win_get_width`](super::NvimApi::win_get_width).
+    pub async fn width(&self) -> Result<i64> {
+        self.handle.width(self.client).await
+    }
+    /// Sets the window width. This will only succeed if the screen is split
+    /// vertically.
+    ///
+    /// See [`NvimApi::win_set_width`](super::NvimApi::win_set_width).
+    pub async fn set_width(&self, width: i64) -> Result<()> {
+        self.handle.set_width(self.client, width).await
+    }
+    /// Gets a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_get_var`](super::NvimApi::win_get_var).
+    pub async fn var<T>(&self, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.handle.var(self.client, name).await
+    }
+    /// Sets a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_set_var`](super::NvimApi::win_set_var).
+    pub async fn set_var<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.handle.set_var(self.client, name, value).await
+    }
+    /// Removes a window-scoped (w:) variable
+    ///
+    /// See [`NvimApi::win_del_var`](super::NvimApi::win_del_var).
+    pub async fn del_var(&self, name: &str) -> Result<()> {
+        self.handle.del_var(self.client, name).await
+    }
+    /// Gets the window position in display cells. First position is zero.
+    ///
+    /// See [`NvimApi::win_get_position`](super::NvimApi::win_get_position).
+    pub async fn position(&self) -> Result<(i64, i64)> {
+        self.handle.position(self.client).await
+    }
+    /// Gets the window tabpage
+    ///
+    /// See [`NvimApi::win_get_tabpage`](super::NvimApi::win_get_tabpage).
+    pub async fn tabpage(&self) -> Result<TabPage> {
+        self.handle.tabpage(self.client).await
+    }
+    /// Gets the window number
+    ///
+    /// See [`NvimApi::win_get_number`](super::NvimApi::win_get_number).
+    pub async fn number(&self) -> Result<i64> {
+        self.handle.number(self.client).await
+    }
+    /// Checks if a window is valid
+    ///
+    /// See [`NvimApi::win_is_valid`](super::NvimApi::win_is_valid).
+    pub async fn is_valid(&self) -> Result<bool> {
+        self.handle.is_valid(self.client).await
+    }
+    /// Closes the window and hide the buffer it contains (like |:hide| with a
+    /// |window-ID|).
+    ///
+    /// See [`NvimApi::win_hide`](super::NvimApi::win_hide).
+    pub async fn hide(&self) -> Result<()> {
+        self.handle.hide(self.client).await
+    }
+    /// Closes the window (like |:close| with a |window-ID|).
+    ///
+    /// See [`NvimApi::win_close`](super::NvimApi::win_close).
+    pub async fn close(&self, force: bool) -> Result<()> {
+        self.handle.close(self.client, force).await
+    }
+    /// Set highlight namespace for a window. This will use highlights defined
+    /// with |nvim_set_hl()| for this namespace, but fall back to global
+    /// highlights (ns=0) when missing.
+    ///
+    /// See [`NvimApi::win_set_hl_ns`](super::NvimApi::win_set_hl_ns).
+    pub async fn set_hl_ns(&self, ns_id: i64) -> Result<()> {
+        self.handle.set_hl_ns(self.client, ns_id).await
+    }
+    /// Computes the number of screen lines occupied by a range of text in a given
+    /// window. Works for off-screen text and takes folds into account.
+    ///
+    /// See [`NvimApi::win_text_height`](super::NvimApi::win_text_height).
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn text_height(&self, opts: opts::WinTextHeight) -> Result<HashMap<String, Value>> {
+        self.handle.text_height(self.client, opts).await
+    }
+}
+impl TabPage {
+    /// Bind this tabpage to a client.
+    pub fn bind<'c>(&self, c: &'c Client) -> TabPageRef<'c> {
+        TabPageRef::new(self.clone(), c)
+    }
+    /// Gets the windows in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_list_wins`](super::NvimApi::tabpage_list_wins).
+    pub async fn windows(&self, c: &Client) -> Result<Vec<Window>> {
+        c.nvim.tabpage_list_wins(self).await
+    }
+    /// Gets a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_get_var`](super::NvimApi::tabpage_get_var).
+    pub async fn var<T>(&self, c: &Client, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        c.nvim.tabpage_get_var(self, name).await
+    }
+    /// Sets a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_set_var`](super::NvimApi::tabpage_set_var).
+    pub async fn set_var<T>(&self, c: &Client, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        c.nvim.tabpage_set_var(self, name, value).await
+    }
+    /// Removes a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_del_var`](super::NvimApi::tabpage_del_var).
+    pub async fn del_var(&self, c: &Client, name: &str) -> Result<()> {
+        c.nvim.tabpage_del_var(self, name).await
+    }
+    /// Gets the current window in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_get_win`](super::NvimApi::tabpage_get_win).
+    pub async fn win(&self, c: &Client) -> Result<Window> {
+        c.nvim.tabpage_get_win(self).await
+    }
+    /// Sets the current window in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_set_win`](super::NvimApi::tabpage_set_win).
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn set_win(&self, c: &Client, win: &Window) -> Result<()> {
+        c.nvim.tabpage_set_win(self, win).await
+    }
+    /// Gets the tabpage number
+    ///
+    /// See [`NvimApi::tabpage_get_number`](super::NvimApi::tabpage_get_number).
+    pub async fn number(&self, c: &Client) -> Result<i64> {
+        c.nvim.tabpage_get_number(self).await
+    }
+    /// Checks if a tabpage is valid
+    ///
+    /// See [`NvimApi::tabpage_is_valid`](super::NvimApi::tabpage_is_valid).
+    pub async fn is_valid(&self, c: &Client) -> Result<bool> {
+        c.nvim.tabpage_is_valid(self).await
+    }
+}
+/// A tabpage bound to a client, so that methods don't need the client passed in.
+#[derive(Debug, Clone)]
+pub struct TabPageRef<'c> {
+    handle: TabPage,
+    client: &'c Client,
+}
+impl<'c> TabPageRef<'c> {
+    /// Bind a handle to a client.
+    pub fn new(handle: TabPage, client: &'c Client) -> Self {
+        Self { handle, client }
+    }
+    /// The underlying tabpage handle.
+    pub fn handle(&self) -> &TabPage {
+        &self.handle
+    }
+    /// The client this handle is bound to.
+    pub fn client(&self) -> &'c Client {
+        self.client
+    }
+    /// Gets the windows in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_list_wins`](super::NvimApi::tabpage_list_wins).
+    pub async fn windows(&self) -> Result<Vec<Window>> {
+        self.handle.windows(self.client).await
+    }
+    /// Gets a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_get_var`](super::NvimApi::tabpage_get_var).
+    pub async fn var<T>(&self, name: &str) -> Result<T>
+    where
+        T: serde::de::DeserializeOwned,
+    {
+        self.handle.var(self.client, name).await
+    }
+    /// Sets a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_set_var`](super::NvimApi::tabpage_set_var).
+    pub async fn set_var<T>(&self, name: &str, value: T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        self.handle.set_var(self.client, name, value).await
+    }
+    /// Removes a tab-scoped (t:) variable
+    ///
+    /// See [`NvimApi::tabpage_del_var`](super::NvimApi::tabpage_del_var).
+    pub async fn del_var(&self, name: &str) -> Result<()> {
+        self.handle.del_var(self.client, name).await
+    }
+    /// Gets the current window in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_get_win`](super::NvimApi::tabpage_get_win).
+    pub async fn win(&self) -> Result<Window> {
+        self.handle.win(self.client).await
+    }
+    /// Sets the current window in a tabpage
+    ///
+    /// See [`NvimApi::tabpage_set_win`](super::NvimApi::tabpage_set_win).
+    #[cfg(feature = "nvim-0-10")]
+    pub async fn set_win(&self, win: &Window) -> Result<()> {
+        self.handle.set_win(self.client, win).await
+    }
+    /// Gets the tabpage number
+    ///
+    /// See [`NvimApi::tabpage_get_number`](super::NvimApi::tabpage_get_number).
+    pub async fn number(&self) -> Result<i64> {
+        self.handle.number(self.client).await
+    }
+    /// Checks if a tabpage is valid
+    ///
+    /// See [`NvimApi::tabpage_is_valid`](super::NvimApi::tabpage_is_valid).
+    pub async fn is_valid(&self) -> Result<bool> {
+        self.handle.is_valid(self.client).await
+    }
+}
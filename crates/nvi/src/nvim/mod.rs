@@ -9,7 +9,11 @@
 
 mod api;
 pub mod diagnostics;
+pub mod handles;
+pub mod options;
 pub mod opts;
+pub mod redraw;
 pub mod types;
+pub mod vimfn;
 
 pub use api::NvimApi;
@@ -0,0 +1,913 @@
+//! Typed Neovim options. Each option is a constant on `Opt`, typed by its value and scope,
+//! so that reading or writing an option with the wrong type or through the wrong kind of
+//! handle fails at compile time. Global-local options have their own types, which can be
+//! used both globally and through a window or buffer. Generated from
+//! `nvim_get_all_options_info` by `protoc options`, for the options listed in protoc's
+//! `overrides::OPTIONS`.
+use std::marker::PhantomData;
+
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::{NvimApi, opts};
+use crate::error::Result;
+/// The type of an option value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptType {
+    /// A boolean option, e.g. `'modifiable'`
+    Boolean,
+    /// A number option, e.g. `'columns'`
+    Number,
+    /// A string option, e.g. `'filetype'`
+    String,
+}
+/// The scope of an option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptScope {
+    /// A global option
+    Global,
+    /// A window-local option
+    Win,
+    /// A buffer-local option
+    Buf,
+}
+/// An option value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OptValue {
+    /// A boolean value
+    Boolean(bool),
+    /// A number value
+    Number(i64),
+    /// A string value
+    String(&'static str),
+}
+/// Metadata for an option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OptInfo {
+    /// The full option name
+    pub name: &'static str,
+    /// The abbreviated name, or an empty string
+    pub shortname: &'static str,
+    /// The value type
+    pub typ: OptType,
+    /// The scope
+    pub scope: OptScope,
+    /// Whether a window or buffer can override this global option
+    pub global_local: bool,
+    /// The default value
+    pub default: OptValue,
+}
+/// Metadata for every option in this module, sorted by name.
+pub const OPTIONS: &[OptInfo] = &[
+    OptInfo {
+        name: "autoindent",
+        shortname: "ai",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "autowrite",
+        shortname: "aw",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "background",
+        shortname: "bg",
+        typ: OptType::String,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::String("dark"),
+    },
+    OptInfo {
+        name: "belloff",
+        shortname: "bo",
+        typ: OptType::String,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::String("all"),
+    },
+    OptInfo {
+        name: "bufhidden",
+        shortname: "bh",
+        typ: OptType::String,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "buflisted",
+        shortname: "bl",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "buftype",
+        shortname: "bt",
+        typ: OptType::String,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "cmdheight",
+        shortname: "ch",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(1i64),
+    },
+    OptInfo {
+        name: "colorcolumn",
+        shortname: "cc",
+        typ: OptType::String,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "columns",
+        shortname: "co",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(80i64),
+    },
+    OptInfo {
+        name: "conceallevel",
+        shortname: "cole",
+        typ: OptType::Number,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "confirm",
+        shortname: "cf",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "cursorline",
+        shortname: "cul",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "expandtab",
+        shortname: "et",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "fileencoding",
+        shortname: "fenc",
+        typ: OptType::String,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "fileformat",
+        shortname: "ff",
+        typ: OptType::String,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::String("unix"),
+    },
+    OptInfo {
+        name: "filetype",
+        shortname: "ft",
+        typ: OptType::String,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "foldenable",
+        shortname: "fen",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "foldmethod",
+        shortname: "fdm",
+        typ: OptType::String,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::String("manual"),
+    },
+    OptInfo {
+        name: "hidden",
+        shortname: "hid",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "hlsearch",
+        shortname: "hls",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "ignorecase",
+        shortname: "ic",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "incsearch",
+        shortname: "is",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "laststatus",
+        shortname: "ls",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(2i64),
+    },
+    OptInfo {
+        name: "linebreak",
+        shortname: "lbr",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "lines",
+        shortname: "",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(24i64),
+    },
+    OptInfo {
+        name: "list",
+        shortname: "",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "modeline",
+        shortname: "ml",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "modifiable",
+        shortname: "ma",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "modified",
+        shortname: "mod",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "mouse",
+        shortname: "",
+        typ: OptType::String,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::String("nvi"),
+    },
+    OptInfo {
+        name: "number",
+        shortname: "nu",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "pumheight",
+        shortname: "ph",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "readonly",
+        shortname: "ro",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "relativenumber",
+        shortname: "rnu",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "report",
+        shortname: "",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(2i64),
+    },
+    OptInfo {
+        name: "ruler",
+        shortname: "ru",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "scrolloff",
+        shortname: "so",
+        typ: OptType::Number,
+        scope: OptScope::Win,
+        global_local: true,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "shiftwidth",
+        shortname: "sw",
+        typ: OptType::Number,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Number(8i64),
+    },
+    OptInfo {
+        name: "showmode",
+        shortname: "smd",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "showtabline",
+        shortname: "stal",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(1i64),
+    },
+    OptInfo {
+        name: "sidescrolloff",
+        shortname: "siso",
+        typ: OptType::Number,
+        scope: OptScope::Win,
+        global_local: true,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "signcolumn",
+        shortname: "scl",
+        typ: OptType::String,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::String("auto"),
+    },
+    OptInfo {
+        name: "smartcase",
+        shortname: "scs",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "softtabstop",
+        shortname: "sts",
+        typ: OptType::Number,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "spell",
+        shortname: "",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "splitbelow",
+        shortname: "sb",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "splitright",
+        shortname: "spr",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "swapfile",
+        shortname: "swf",
+        typ: OptType::Boolean,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "tabstop",
+        shortname: "ts",
+        typ: OptType::Number,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Number(8i64),
+    },
+    OptInfo {
+        name: "termguicolors",
+        shortname: "tgc",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "textwidth",
+        shortname: "tw",
+        typ: OptType::Number,
+        scope: OptScope::Buf,
+        global_local: false,
+        default: OptValue::Number(0i64),
+    },
+    OptInfo {
+        name: "timeoutlen",
+        shortname: "tm",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(1000i64),
+    },
+    OptInfo {
+        name: "updatetime",
+        shortname: "ut",
+        typ: OptType::Number,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Number(4000i64),
+    },
+    OptInfo {
+        name: "winfixheight",
+        shortname: "wfh",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "winfixwidth",
+        shortname: "wfw",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(false),
+    },
+    OptInfo {
+        name: "winhighlight",
+        shortname: "winhl",
+        typ: OptType::String,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::String(""),
+    },
+    OptInfo {
+        name: "wrap",
+        shortname: "",
+        typ: OptType::Boolean,
+        scope: OptScope::Win,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+    OptInfo {
+        name: "wrapscan",
+        shortname: "ws",
+        typ: OptType::Boolean,
+        scope: OptScope::Global,
+        global_local: false,
+        default: OptValue::Boolean(true),
+    },
+];
+/// A global option with a value of type `T`.
+#[derive(Debug)]
+pub struct GlobalOpt<T> {
+    name: &'static str,
+    _type: PhantomData<T>,
+}
+/// A window-local option with a value of type `T`.
+#[derive(Debug)]
+pub struct WinOpt<T> {
+    name: &'static str,
+    _type: PhantomData<T>,
+}
+/// A buffer-local option with a value of type `T`.
+#[derive(Debug)]
+pub struct BufOpt<T> {
+    name: &'static str,
+    _type: PhantomData<T>,
+}
+/// A global-local window option with a value of type `T`: a global value that each window
+/// can override. Converts into both a `GlobalOpt` and a `WinOpt`.
+#[derive(Debug)]
+pub struct GlobalLocalWinOpt<T> {
+    name: &'static str,
+    _type: PhantomData<T>,
+}
+/// A global-local buffer option with a value of type `T`: a global value that each buffer
+/// can override. Converts into both a `GlobalOpt` and a `BufOpt`.
+#[derive(Debug)]
+pub struct GlobalLocalBufOpt<T> {
+    name: &'static str,
+    _type: PhantomData<T>,
+}
+impl<T> GlobalOpt<T> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+    /// The option name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl<T> WinOpt<T> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+    /// The option name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl<T> BufOpt<T> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+    /// The option name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl<T> GlobalLocalWinOpt<T> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+    /// The option name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl<T> GlobalLocalBufOpt<T> {
+    const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            _type: PhantomData,
+        }
+    }
+    /// The option name.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+}
+impl<T> From<GlobalLocalWinOpt<T>> for GlobalOpt<T> {
+    fn from(o: GlobalLocalWinOpt<T>) -> Self {
+        Self::new(o.name)
+    }
+}
+impl<T> From<GlobalLocalWinOpt<T>> for WinOpt<T> {
+    fn from(o: GlobalLocalWinOpt<T>) -> Self {
+        Self::new(o.name)
+    }
+}
+impl<T> From<GlobalLocalBufOpt<T>> for GlobalOpt<T> {
+    fn from(o: GlobalLocalBufOpt<T>) -> Self {
+        Self::new(o.name)
+    }
+}
+impl<T> From<GlobalLocalBufOpt<T>> for BufOpt<T> {
+    fn from(o: GlobalLocalBufOpt<T>) -> Self {
+        Self::new(o.name)
+    }
+}
+/// All Neovim options, e.g. `Opt::Modifiable`.
+#[derive(Debug)]
+pub struct Opt;
+#[allow(non_upper_case_globals)]
+impl Opt {
+    /// `'autoindent'` (`'ai'`): a buf boolean option. Default: `true`.
+    pub const Autoindent: BufOpt<bool> = BufOpt::new("autoindent");
+    /// `'autowrite'` (`'aw'`): a global boolean option. Default: `false`.
+    pub const Autowrite: GlobalOpt<bool> = GlobalOpt::new("autowrite");
+    /// `'background'` (`'bg'`): a global string option. Default: `"dark"`.
+    pub const Background: GlobalOpt<String> = GlobalOpt::new("background");
+    /// `'belloff'` (`'bo'`): a global string option. Default: `"all"`.
+    pub const Belloff: GlobalOpt<String> = GlobalOpt::new("belloff");
+    /// `'bufhidden'` (`'bh'`): a buf string option. Default: `""`.
+    pub const Bufhidden: BufOpt<String> = BufOpt::new("bufhidden");
+    /// `'buflisted'` (`'bl'`): a buf boolean option. Default: `true`.
+    pub const Buflisted: BufOpt<bool> = BufOpt::new("buflisted");
+    /// `'buftype'` (`'bt'`): a buf string option. Default: `""`.
+    pub const Buftype: BufOpt<String> = BufOpt::new("buftype");
+    /// `'cmdheight'` (`'ch'`): a global number option. Default: `1`.
+    pub const Cmdheight: GlobalOpt<i64> = GlobalOpt::new("cmdheight");
+    /// `'colorcolumn'` (`'cc'`): a win string option. Default: `""`.
+    pub const Colorcolumn: WinOpt<String> = WinOpt::new("colorcolumn");
+    /// `'columns'` (`'co'`): a global number option. Default: `80`.
+    pub const Columns: GlobalOpt<i64> = GlobalOpt::new("columns");
+    /// `'conceallevel'` (`'cole'`): a win number option. Default: `0`.
+    pub const Conceallevel: WinOpt<i64> = WinOpt::new("conceallevel");
+    /// `'confirm'` (`'cf'`): a global boolean option. Default: `false`.
+    pub const Confirm: GlobalOpt<bool> = GlobalOpt::new("confirm");
+    /// `'cursorline'` (`'cul'`): a win boolean option. Default: `false`.
+    pub const Cursorline: WinOpt<bool> = WinOpt::new("cursorline");
+    /// `'expandtab'` (`'et'`): a buf boolean option. Default: `false`.
+    pub const Expandtab: BufOpt<bool> = BufOpt::new("expandtab");
+    /// `'fileencoding'` (`'fenc'`): a buf string option. Default: `""`.
+    pub const Fileencoding: BufOpt<String> = BufOpt::new("fileencoding");
+    /// `'fileformat'` (`'ff'`): a buf string option. Default: `"unix"`.
+    pub const Fileformat: BufOpt<String> = BufOpt::new("fileformat");
+    /// `'filetype'` (`'ft'`): a buf string option. Default: `""`.
+    pub const Filetype: BufOpt<String> = BufOpt::new("filetype");
+    /// `'foldenable'` (`'fen'`): a win boolean option. Default: `true`.
+    pub const Foldenable: WinOpt<bool> = WinOpt::new("foldenable");
+    /// `'foldmethod'` (`'fdm'`): a win string option. Default: `"manual"`.
+    pub const Foldmethod: WinOpt<String> = WinOpt::new("foldmethod");
+    /// `'hidden'` (`'hid'`): a global boolean option. Default: `true`.
+    pub const Hidden: GlobalOpt<bool> = GlobalOpt::new("hidden");
+    /// `'hlsearch'` (`'hls'`): a global boolean option. Default: `true`.
+    pub const Hlsearch: GlobalOpt<bool> = GlobalOpt::new("hlsearch");
+    /// `'ignorecase'` (`'ic'`): a global boolean option. Default: `false`.
+    pub const Ignorecase: GlobalOpt<bool> = GlobalOpt::new("ignorecase");
+    /// `'incsearch'` (`'is'`): a global boolean option. Default: `true`.
+    pub const Incsearch: GlobalOpt<bool> = GlobalOpt::new("incsearch");
+    /// `'laststatus'` (`'ls'`): a global number option. Default: `2`.
+    pub const Laststatus: GlobalOpt<i64> = GlobalOpt::new("laststatus");
+    /// `'linebreak'` (`'lbr'`): a win boolean option. Default: `false`.
+    pub const Linebreak: WinOpt<bool> = WinOpt::new("linebreak");
+    /// `'lines'`: a global number option. Default: `24`.
+    pub const Lines: GlobalOpt<i64> = GlobalOpt::new("lines");
+    /// `'list'`: a win boolean option. Default: `false`.
+    pub const List: WinOpt<bool> = WinOpt::new("list");
+    /// `'modeline'` (`'ml'`): a buf boolean option. Default: `true`.
+    pub const Modeline: BufOpt<bool> = BufOpt::new("modeline");
+    /// `'modifiable'` (`'ma'`): a buf boolean option. Default: `true`.
+    pub const Modifiable: BufOpt<bool> = BufOpt::new("modifiable");
+    /// `'modified'` (`'mod'`): a buf boolean option. Default: `false`.
+    pub const Modified: BufOpt<bool> = BufOpt::new("modified");
+    /// `'mouse'`: a global string option. Default: `"nvi"`.
+    pub const Mouse: GlobalOpt<String> = GlobalOpt::new("mouse");
+    /// `'number'` (`'nu'`): a win boolean option. Default: `false`.
+    pub const Number: WinOpt<bool> = WinOpt::new("number");
+    /// `'pumheight'` (`'ph'`): a global number option. Default: `0`.
+    pub const Pumheight: GlobalOpt<i64> = GlobalOpt::new("pumheight");
+    /// `'readonly'` (`'ro'`): a buf boolean option. Default: `false`.
+    pub const Readonly: BufOpt<bool> = BufOpt::new("readonly");
+    /// `'relativenumber'` (`'rnu'`): a win boolean option. Default: `false`.
+    pub const Relativenumber: WinOpt<bool> = WinOpt::new("relativenumber");
+    /// `'report'`: a global number option. Default: `2`.
+    pub const Report: GlobalOpt<i64> = GlobalOpt::new("report");
+    /// `'ruler'` (`'ru'`): a global boolean option. Default: `true`.
+    pub const Ruler: GlobalOpt<bool> = GlobalOpt::new("ruler");
+    /// `'scrolloff'` (`'so'`): a global-local win number option. Default: `0`.
+    pub const Scrolloff: GlobalLocalWinOpt<i64> = GlobalLocalWinOpt::new("scrolloff");
+    /// `'shiftwidth'` (`'sw'`): a buf number option. Default: `8`.
+    pub const Shiftwidth: BufOpt<i64> = BufOpt::new("shiftwidth");
+    /// `'showmode'` (`'smd'`): a global boolean option. Default: `true`.
+    pub const Showmode: GlobalOpt<bool> = GlobalOpt::new("showmode");
+    /// `'showtabline'` (`'stal'`): a global number option. Default: `1`.
+    pub const Showtabline: GlobalOpt<i64> = GlobalOpt::new("showtabline");
+    /// `'sidescrolloff'` (`'siso'`): a global-local win number option. Default: `0`.
+    pub const Sidescrolloff: GlobalLocalWinOpt<i64> = GlobalLocalWinOpt::new("sidescrolloff");
+    /// `'signcolumn'` (`'scl'`): a win string option. Default: `"auto"`.
+    pub const Signcolumn: WinOpt<String> = WinOpt::new("signcolumn");
+    /// `'smartcase'` (`'scs'`): a global boolean option. Default: `false`.
+    pub const Smartcase: GlobalOpt<bool> = GlobalOpt::new("smartcase");
+    /// `'softtabstop'` (`'sts'`): a buf number option. Default: `0`.
+    pub const Softtabstop: BufOpt<i64> = BufOpt::new("softtabstop");
+    /// `'spell'`: a win boolean option. Default: `false`.
+    pub const Spell: WinOpt<bool> = WinOpt::new("spell");
+    /// `'splitbelow'` (`'sb'`): a global boolean option. Default: `false`.
+    pub const Splitbelow: GlobalOpt<bool> = GlobalOpt::new("splitbelow");
+    /// `'splitright'` (`'spr'`): a global boolean option. Default: `false`.
+    pub const Splitright: GlobalOpt<bool> = GlobalOpt::new("splitright");
+    /// `'swapfile'` (`'swf'`): a buf boolean option. Default: `true`.
+    pub const Swapfile: BufOpt<bool> = BufOpt::new("swapfile");
+    /// `'tabstop'` (`'ts'`): a buf number option. Default: `8`.
+    pub const Tabstop: BufOpt<i64> = BufOpt::new("tabstop");
+    /// `'termguicolors'` (`'tgc'`): a global boolean option. Default: `false`.
+    pub const Termguicolors: GlobalOpt<bool> = GlobalOpt::new("termguicolors");
+    /// `'textwidth'` (`'tw'`): a buf number option. Default: `0`.
+    pub const Textwidth: BufOpt<i64> = BufOpt::new("textwidth");
+    /// `'timeoutlen'` (`'tm'`): a global number option. Default: `1000`.
+    pub const Timeoutlen: GlobalOpt<i64> = GlobalOpt::new("timeoutlen");
+    /// `'updatetime'` (`'ut'`): a global number option. Default: `4000`.
+    pub const Updatetime: GlobalOpt<i64> = GlobalOpt::new("updatetime");
+    /// `'winfixheight'` (`'wfh'`): a win boolean option. Default: `false`.
+    pub const Winfixheight: WinOpt<bool> = WinOpt::new("winfixheight");
+    /// `'winfixwidth'` (`'wfw'`): a win boolean option. Default: `false`.
+    pub const Winfixwidth: WinOpt<bool> = WinOpt::new("winfixwidth");
+    /// `'winhighlight'` (`'winhl'`): a win string option. Default: `""`.
+    pub const Winhighlight: WinOpt<String> = WinOpt::new("winhighlight");
+    /// `'wrap'`: a win boolean option. Default: `true`.
+    pub const Wrap: WinOpt<bool> = WinOpt::new("wrap");
+    /// `'wrapscan'` (`'ws'`): a global boolean option. Default: `true`.
+    pub const Wrapscan: GlobalOpt<bool> = GlobalOpt::new("wrapscan");
+}
+/// Typed access to global options, and the global values of global-local options.
+#[derive(Debug, Clone)]
+pub struct Options<'a> {
+    nvim: &'a NvimApi,
+}
+impl<'a> Options<'a> {
+    /// Create option accessors that call through an API handle.
+    pub fn new(nvim: &'a NvimApi) -> Self {
+        Self { nvim }
+    }
+    /// Get the global value of an option, like `:setglobal`.
+    pub async fn get<T: DeserializeOwned>(&self, opt: impl Into<GlobalOpt<T>>) -> Result<T> {
+        let opt = opt.into();
+        self.nvim
+            .get_option_value(
+                opt.name,
+                opts::SetOptionValue::default().scope("global".into()),
+            )
+            .await
+    }
+    /// Set the global value of an option, like `:setglobal`.
+    pub async fn set<T: Serialize>(
+        &self,
+        opt: impl Into<GlobalOpt<T>>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        let opt = opt.into();
+        self.nvim
+            .set_option_value(
+                opt.name,
+                value.into(),
+                opts::SetOptionValue::default().scope("global".into()),
+            )
+            .await
+    }
+    /// Get the global value of `'autowrite'`.
+    pub async fn autowrite(&self) -> Result<bool> {
+        self.get(Opt::Autowrite).await
+    }
+    /// Get the global value of `'background'`.
+    pub async fn background(&self) -> Result<String> {
+        self.get(Opt::Background).await
+    }
+    /// Get the global value of `'belloff'`.
+    pub async fn belloff(&self) -> Result<String> {
+        self.get(Opt::Belloff).await
+    }
+    /// Get the global value of `'cmdheight'`.
+    pub async fn cmdheight(&self) -> Result<i64> {
+        self.get(Opt::Cmdheight).await
+    }
+    /// Get the global value of `'columns'`.
+    pub async fn columns(&self) -> Result<i64> {
+        self.get(Opt::Columns).await
+    }
+    /// Get the global value of `'confirm'`.
+    pub async fn confirm(&self) -> Result<bool> {
+        self.get(Opt::Confirm).await
+    }
+    /// Get the global value of `'hidden'`.
+    pub async fn hidden(&self) -> Result<bool> {
+        self.get(Opt::Hidden).await
+    }
+    /// Get the global value of `'hlsearch'`.
+    pub async fn hlsearch(&self) -> Result<bool> {
+        self.get(Opt::Hlsearch).await
+    }
+    /// Get the global value of `'ignorecase'`.
+    pub async fn ignorecase(&self) -> Result<bool> {
+        self.get(Opt::Ignorecase).await
+    }
+    /// Get the global value of `'incsearch'`.
+    pub async fn incsearch(&self) -> Result<bool> {
+        self.get(Opt::Incsearch).await
+    }
+    /// Get the global value of `'laststatus'`.
+    pub async fn laststatus(&self) -> Result<i64> {
+        self.get(Opt::Laststatus).await
+    }
+    /// Get the global value of `'lines'`.
+    pub async fn lines(&self) -> Result<i64> {
+        self.get(Opt::Lines).await
+    }
+    /// Get the global value of `'mouse'`.
+    pub async fn mouse(&self) -> Result<String> {
+        self.get(Opt::Mouse).await
+    }
+    /// Get the global value of `'pumheight'`.
+    pub async fn pumheight(&self) -> Result<i64> {
+        self.get(Opt::Pumheight).await
+    }
+    /// Get the global value of `'report'`.
+    pub async fn report(&self) -> Result<i64> {
+        self.get(Opt::Report).await
+    }
+    /// Get the global value of `'ruler'`.
+    pub async fn ruler(&self) -> Result<bool> {
+        self.get(Opt::Ruler).await
+    }
+    /// Get the global value of `'scrolloff'`.
+    pub async fn scrolloff(&self) -> Result<i64> {
+        self.get(Opt::Scrolloff).await
+    }
+    /// Get the global value of `'showmode'`.
+    pub async fn showmode(&self) -> Result<bool> {
+        self.get(Opt::Showmode).await
+    }
+    /// Get the global value of `'showtabline'`.
+    pub async fn showtabline(&self) -> Result<i64> {
+        self.get(Opt::Showtabline).await
+    }
+    /// Get the global value of `'sidescrolloff'`.
+    pub async fn sidescrolloff(&self) -> Result<i64> {
+        self.get(Opt::Sidescrolloff).await
+    }
+    /// Get the global value of `'smartcase'`.
+    pub async fn smartcase(&self) -> Result<bool> {
+        self.get(Opt::Smartcase).await
+    }
+    /// Get the global value of `'splitbelow'`.
+    pub async fn splitbelow(&self) -> Result<bool> {
+        self.get(Opt::Splitbelow).await
+    }
+    /// Get the global value of `'splitright'`.
+    pub async fn splitright(&self) -> Result<bool> {
+        self.get(Opt::Splitright).await
+    }
+    /// Get the global value of `'termguicolors'`.
+    pub async fn termguicolors(&self) -> Result<bool> {
+        self.get(Opt::Termguicolors).await
+    }
+    /// Get the global value of `'timeoutlen'`.
+    pub async fn timeoutlen(&self) -> Result<i64> {
+        self.get(Opt::Timeoutlen).await
+    }
+    /// Get the global value of `'updatetime'`.
+    pub async fn updatetime(&self) -> Result<i64> {
+        self.get(Opt::Updatetime).await
+    }
+    /// Get the global value of `'wrapscan'`.
+    pub async fn wrapscan(&self) -> Result<bool> {
+        self.get(Opt::Wrapscan).await
+    }
+}
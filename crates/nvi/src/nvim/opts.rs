@@ -1,34 +1,306 @@
-//! This module contains the optional argument structures for methods in the generated API. These
-//! are the final arguments in API functions named `opts`. They are not included in the rendered
-//! protocol description, so we have to write them by hand.
+//! Options structs for the `Dict(...)` parameters of the generated API. These are
+//! generated from the keysets in the API definition by `protoc opts`.
+use std::collections::HashMap;
 
-use derive_setters::*;
+use derive_setters::Setters;
+use mrpc::Value;
 use serde_derive::{Deserialize, Serialize};
 
 use super::types;
-
-/// Options for setting highlights
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
+/// Options for `nvim_buf_attach`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
 #[setters(strip_option)]
-pub struct SetHl {
-    /// Color name or "#RRGGBB" hex color string for foreground
+pub struct BufAttach {
+    /// Include the UTF-32 and UTF-16 sizes of the replaced region in line events
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub fg: Option<String>,
-    /// Color name or "#RRGGBB" hex color string for background
+    pub utf_sizes: Option<bool>,
+    /// Also attach to command preview ('inccommand') events
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub bg: Option<String>,
-    /// Color name or "#RRGGBB" hex color string for special highlighting
+    pub preview: Option<bool>,
+}
+/// Options for `nvim_buf_delete`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct BufDelete {
+    /// Force deletion and ignore unsaved changes
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub sp: Option<String>,
-    /// Transparency level, integer between 0 and 100
+    pub force: Option<bool>,
+    /// Unloaded only, do not delete the buffer
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub blend: Option<i64>,
+    pub unload: Option<bool>,
+}
+/// Options for `nvim_clear_autocmds`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct ClearAutocmds {
+    /// Buffer number for buffer-local autocommands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<types::Buffer>,
+    /// Event or events to clear
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Vec<String>>,
+    /// Autocommand group name or ID to match against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<types::Group>,
+    /// Pattern or patterns to match exactly
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Vec<String>>,
+}
+/// Options for `nvim_cmd`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct Cmd {
+    /// Command name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cmd: Option<String>,
+    /// Command range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<Vec<Value>>,
+    /// Command count
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<i64>,
+    /// Command register
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reg: Option<String>,
+    /// Whether the command has a `!` modifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bang: Option<bool>,
+    /// Command arguments
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub args: Option<Vec<Value>>,
+    /// Which characters have special meaning in the command arguments, as the `file` and `bar` flags
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub magic: Option<HashMap<String, Value>>,
+    /// Command modifiers, e.g. `silent` or `vertical`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mods: Option<HashMap<String, Value>>,
+    /// Value of `-nargs` for the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nargs: Option<Value>,
+    /// Value of `-addr` for the command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr: Option<Value>,
+    /// The next command, if there are multiple commands separated by a `|`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nextcmd: Option<Value>,
+}
+/// Options for `nvim_cmd`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct CmdOpts {
+    /// Return the command output instead of showing it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<bool>,
+}
+/// Options for `nvim_get_context`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct Context {
+    /// Context types to gather, e.g. `regs`, `jumps`, `bufs`, `gvars` or `funcs`. Defaults to all
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub types: Option<Vec<Value>>,
+}
+/// Options for `nvim_create_augroup`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct CreateAugroup {
+    /// Clear existing commands if the group already exists. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clear: Option<bool>,
+}
+/// Options for `nvim_create_autocmd`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct CreateAutocmd {
+    /// Buffer number for buffer-local autocommands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<types::Buffer>,
+    /// Vimscript function name to call when the event is triggered
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callback: Option<String>,
+    /// Vim command to execute when the event is triggered. Can't be used with callback.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    /// Description for docs and troubleshooting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<String>,
+    /// Autocommand group name or ID to match against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<types::Group>,
+    /// Run nested autocommands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nested: Option<bool>,
+    /// Run the command only once
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub once: Option<bool>,
+    /// Pattern to match literally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Vec<String>>,
+}
+/// Options for `nvim_echo`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct EchoOpts {
+    /// Echo the message as a `:verbose` message, only shown if 'verbose' is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub verbose: Option<bool>,
+}
+/// Options for `nvim_buf_get_text`, `nvim_buf_set_mark`, `nvim_get_mark`, `nvim_parse_cmd`, `nvim_select_popupmenu_item`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct Empty {}
+/// Options for `nvim_eval_statusline`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct EvalStatusline {
+    /// Window to use as context for the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winid: Option<types::Window>,
+    /// Maximum width of the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub maxwidth: Option<i64>,
+    /// Character to fill blank spaces in the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fillchar: Option<String>,
+    /// Return highlight information
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlights: Option<bool>,
+    /// Evaluate the winbar instead of the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_winbar: Option<bool>,
+    /// Evaluate the tabline instead of the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_tabline: Option<bool>,
+    /// Evaluate the statuscolumn for this line number instead of the statusline
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_statuscol_lnum: Option<i64>,
+}
+/// Options for `nvim_exec_autocmds`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct ExecAutocmds {
+    /// Buffer number for buffer-local autocommands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<types::Buffer>,
+    /// Autocommand group name or ID to match against.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<types::Group>,
+    /// Process the modeline after the autocommands
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub modeline: Option<bool>,
+    /// Pattern to match literally
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Vec<String>>,
+    /// Data to send to event
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<Value>,
+}
+/// Options for `nvim_exec2`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct ExecOpts {
+    /// Capture and return all non-error output
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output: Option<bool>,
+}
+/// Options for `nvim_get_autocmds`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetAutocmds {
+    /// Event or events to match against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<Vec<String>>,
+    /// Autocommand group name or ID to match against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group: Option<types::Group>,
+    /// Pattern or patterns to match against. Can't be used with buffer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Vec<String>>,
+    /// Buffer number or list of buffer numbers for buffer-local autocommands. Can't be used with pattern
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub buffer: Option<Value>,
+    /// Autocommand ID to match against
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+}
+/// Options for `nvim_buf_get_commands`, `nvim_get_commands`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetCommands {
+    /// Include builtin commands. Not supported yet, must be false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub builtin: Option<bool>,
+}
+/// Options for `nvim_buf_get_extmark_by_id`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetExtmark {
+    /// Include the extmark's details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<bool>,
+    /// Give highlight groups by name rather than by ID in the details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hl_name: Option<bool>,
+}
+/// Options for `nvim_buf_get_extmarks`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetExtmarks {
+    /// Maximum number of extmarks to return
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<i64>,
+    /// Include the details of each extmark
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<bool>,
+    /// Give highlight groups by name rather than by ID in the details
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hl_name: Option<bool>,
+    /// Also include extmarks that start before the range and overlap it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub overlap: Option<bool>,
+    /// Only include extmarks of this type: `highlight`, `sign`, `virt_text` or `virt_lines`
+    #[serde(rename = "type")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typ: Option<String>,
+}
+/// Options for `nvim_get_hl`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetHighlight {
+    /// Get a highlight definition by ID
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<i64>,
+    /// Get a highlight definition by name
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    /// Return the name of a linked group instead of the effective definition. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub link: Option<bool>,
+    /// Create the highlight group if it doesn't exist. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub create: Option<bool>,
+}
+/// Options for `nvim_get_hl_ns`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct GetNs {
+    /// Window to get the highlight namespace of
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub winid: Option<types::Window>,
+}
+/// Options for `nvim_set_hl`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct SetHl {
     /// Bold text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub bold: Option<bool>,
     /// Standout text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub standout: Option<bool>,
+    /// Strikethrough text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strikethrough: Option<bool>,
     /// Underlined text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub underline: Option<bool>,
@@ -44,106 +316,109 @@ pub struct SetHl {
     /// Dashed underlined text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub underdashed: Option<bool>,
-    /// Strikethrough text
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub strikethrough: Option<bool>,
     /// Italic text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub italic: Option<bool>,
     /// Reverse text
     #[serde(skip_serializing_if = "Option::is_none")]
     pub reverse: Option<bool>,
+    /// Alternate font
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub altfont: Option<bool>,
     /// Don't combine with other highlights
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nocombine: Option<bool>,
-    /// Name of another highlight group to link to
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub link: Option<String>,
     /// Don't override existing definition
     #[serde(skip_serializing_if = "Option::is_none")]
     pub default: Option<bool>,
+    /// cterm attribute map
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cterm: Option<HashMap<String, Value>>,
+    /// Color name or "#RRGGBB" hex color string for foreground
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg: Option<String>,
+    /// Color name or "#RRGGBB" hex color string for background
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg: Option<String>,
+    /// Color name or "#RRGGBB" hex color string for special highlighting
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sp: Option<String>,
     /// Sets foreground of cterm color
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ctermfg: Option<String>,
     /// Sets background of cterm color
     #[serde(skip_serializing_if = "Option::is_none")]
     pub ctermbg: Option<String>,
-    /// cterm attribute map
+    /// Name of another highlight group to link to
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub cterm: Option<String>,
-    /// Force update when highlight group exists
+    pub link: Option<String>,
+    /// Name of a highlight group in the global namespace to link to
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub force: Option<bool>,
-}
-
-/// Options for `nvim_buf_delete` method
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
-#[setters(strip_option)]
-pub struct BufDelete {
-    /// Force deletion and ignore unsaved changes
+    pub global_link: Option<String>,
+    /// Fall back to the global definition for attributes this one doesn't set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fallback: Option<bool>,
+    /// Transparency level, integer between 0 and 100
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub blend: Option<i64>,
+    /// Whether the foreground is a terminal palette index, as reported by `nvim_get_hl`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fg_indexed: Option<bool>,
+    /// Whether the background is a terminal palette index, as reported by `nvim_get_hl`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bg_indexed: Option<bool>,
+    /// Force update when highlight group exists
     #[serde(skip_serializing_if = "Option::is_none")]
     pub force: Option<bool>,
-    /// Unloaded only, do not delete the buffer
+    /// URL to link text with this highlight to, in terminals that support hyperlinks
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub unload: Option<bool>,
+    pub url: Option<String>,
 }
-
-/// Options for `nvim_create_autocmd` method
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
+/// Options for `nvim_buf_set_keymap`, `nvim_set_keymap`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
 #[setters(strip_option)]
-pub struct CreateAutocmd {
-    /// Autocommand group name or ID to match against.
+pub struct Keymap {
+    /// Make the mapping non-recursive
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub group: Option<types::Group>,
-    /// Pattern to match literally
+    pub noremap: Option<bool>,
+    /// Unused; see `nowait`
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pattern: Option<Vec<String>>,
-    /// Buffer number for buffer-local autocommands
+    pub noawait: Option<bool>,
+    /// Don't wait for more characters when a longer mapping starts with this one
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub buffer: Option<u64>,
-    /// Description for docs and troubleshooting
+    pub nowait: Option<bool>,
+    /// Don't echo the mapping's command
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub desc: Option<String>,
-    /// Vimscript function name to call when the event is triggered
+    pub silent: Option<bool>,
+    /// Only remap characters that were defined local to the script
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub callback: Option<String>,
-    /// Vim command to execute when the event is triggered. Can't be used with callback.
+    pub script: Option<bool>,
+    /// The right-hand side is an expression to evaluate
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub command: Option<String>,
-    /// Run the command only once
+    pub expr: Option<bool>,
+    /// Fail if the mapping already exists
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub once: Option<bool>,
-    /// Run nested autocommands
+    pub unique: Option<bool>,
+    /// Human-readable description of the mapping
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub nested: Option<bool>,
+    pub desc: Option<String>,
+    /// When `expr` is set, replace keycodes in the resulting string
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replace_keycodes: Option<bool>,
 }
-
-/// Options for `nvim_exec_autocmds` function
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
+/// Options for `nvim_open_term`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
 #[setters(strip_option)]
-pub struct ExecAutocmds {
-    /// Autocommand group name or ID to match against.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub group: Option<types::Group>,
-    /// Pattern to match literally
+pub struct OpenTerm {
+    /// Convert newlines to CRLF in data sent to the terminal. Defaults to true
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pattern: Option<Vec<String>>,
-    /// Buffer number for buffer-local autocommands
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub buffer: Option<u64>,
-    /// Process the modeline after the autocommands
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub modeline: Option<bool>,
-    /// Data to send to event
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub data: Option<Vec<u8>>,
+    pub force_crlf: Option<bool>,
 }
-
-/// Options for `nvim_exec_autocmds` function
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
+/// Options for `nvim_get_option_info2`, `nvim_get_option_value`, `nvim_set_option_value`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
 #[setters(strip_option)]
 pub struct SetOptionValue {
-    /// "global" or "local", analogous to ":setglobal" an ":setlocal"
+    /// "global" or "local", analogous to ":setglobal" and ":setlocal"
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<String>,
     /// Window ID
@@ -152,25 +427,166 @@ pub struct SetOptionValue {
     /// Buffer ID
     #[serde(skip_serializing_if = "Option::is_none")]
     pub buf: Option<types::Buffer>,
+    /// Get the default value of the option for this filetype. Can't be used with scope, win or buf
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filetype: Option<String>,
 }
-
-/// Options for `nvim_clear_autocmds` method
-#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq, Setters, Default)]
+/// Options for `nvim_buf_set_extmark`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
 #[setters(strip_option)]
-pub struct ClearAutocmds {
-    /// Event or events to clear
+pub struct SetExtmark {
+    /// ID of the extmark to edit
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub event: Option<Vec<String>>,
-
-    /// Pattern or patterns to match exactly
+    pub id: Option<i64>,
+    /// Deprecated alias of end_row
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub pattern: Option<Vec<String>>,
-
-    /// Buffer number for buffer-local autocommands
+    pub end_line: Option<i64>,
+    /// Ending line of the mark, 0-based inclusive
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub buffer: Option<types::Buffer>,
-
-    /// Autocommand group name or ID to match against
+    pub end_row: Option<i64>,
+    /// Ending column of the mark, 0-based exclusive
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub group: Option<types::Group>,
+    pub end_col: Option<i64>,
+    /// Highlight group for the text in the mark's range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hl_group: Option<String>,
+    /// Virtual text to link to the mark, as a list of `[text, highlight]` chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_text: Option<Vec<Value>>,
+    /// Position of the virtual text: `eol`, `overlay`, `right_align` or `inline`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_text_pos: Option<String>,
+    /// Show the virtual text at this window column, starting from the first text column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_text_win_col: Option<i64>,
+    /// Hide the virtual text when the text under it is hidden, e.g. by horizontal scrolling
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_text_hide: Option<bool>,
+    /// Repeat the virtual text on wrapped lines
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_text_repeat_linebreak: Option<bool>,
+    /// Continue the highlight for the rest of the screen line when the range extends past the end of the line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hl_eol: Option<bool>,
+    /// How the virtual text highlight combines with the text under it: `replace`, `combine` or `blend`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hl_mode: Option<String>,
+    /// Hide the mark when its whole range is deleted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub invalidate: Option<bool>,
+    /// Only use the mark for the current redraw, from a decoration provider callback
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ephemeral: Option<bool>,
+    /// Priority of the mark's highlight, sign and virtual text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<i64>,
+    /// Keep the mark to the right of text inserted at its position. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub right_gravity: Option<bool>,
+    /// Keep the end of the mark to the right of text inserted at its position. Defaults to false
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_right_gravity: Option<bool>,
+    /// Virtual lines to show next to the mark, each a list of `[text, highlight]` chunks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_lines: Option<Vec<Value>>,
+    /// Show the virtual lines above the mark's line rather than below it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_lines_above: Option<bool>,
+    /// Show the virtual lines from the leftmost column of the window, over the sign and number columns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub virt_lines_leftcol: Option<bool>,
+    /// Fail if the position is outside the buffer. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub strict: Option<bool>,
+    /// Text to show in the sign column, one or two display cells wide
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_text: Option<String>,
+    /// Highlight group for the sign text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sign_hl_group: Option<String>,
+    /// Highlight group for the number column
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub number_hl_group: Option<String>,
+    /// Highlight group for the whole line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_hl_group: Option<String>,
+    /// Highlight group for the sign text when the cursor is on the line and 'cursorline' is set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cursorline_hl_group: Option<String>,
+    /// Conceal the text in the range, replacing it with this character unless it's empty
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub conceal: Option<String>,
+    /// Whether to spell check the text in the range
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spell: Option<bool>,
+    /// Send the mark to UIs that watch extmarks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ui_watched: Option<bool>,
+    /// Restore the mark's position when a deletion is undone. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub undo_restore: Option<bool>,
+    /// URL to link the text in the range to, in terminals that support hyperlinks
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Only show the mark in windows whose scope includes the mark's namespace
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scoped: Option<bool>,
+}
+/// Options for `nvim_buf_create_user_command`, `nvim_create_user_command`
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct UserCommand {
+    /// The kind of range the command takes, as for `-addr`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub addr: Option<Value>,
+    /// The command can take a `!` modifier
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bang: Option<bool>,
+    /// The command can be followed by a `|` and another command
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bar: Option<bool>,
+    /// Completion for the command arguments, as for `-complete`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub complete: Option<Value>,
+    /// The command takes a count, as for `-count`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub count: Option<Value>,
+    /// Description of the command, shown in listings
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub desc: Option<Value>,
+    /// Replace an existing command with the same name. Defaults to true
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<bool>,
+    /// Report where the command was invoked, rather than defined, in verbose messages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keepscript: Option<bool>,
+    /// The number of arguments the command takes, as for `-nargs`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nargs: Option<Value>,
+    /// Preview callback for 'inccommand'
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub preview: Option<Value>,
+    /// The command takes a range, as for `-range`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<Value>,
+    /// The first argument can be a register name, as for `-register`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub register: Option<bool>,
+}
+/// Options for `nvim_win_text_height`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize, Setters)]
+#[setters(strip_option)]
+pub struct WinTextHeight {
+    /// Starting line index, 0-based inclusive. Defaults to 0
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_row: Option<i64>,
+    /// Ending line index, 0-based inclusive. Defaults to the last line
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_row: Option<i64>,
+    /// Starting virtual column on start_row, 0-based inclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_vcol: Option<i64>,
+    /// Ending virtual column on end_row, 0-based exclusive
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub end_vcol: Option<i64>,
 }
@@ -0,0 +1,862 @@
+//! Typed UI events, as sent to attached UIs in `redraw` notifications. Generated from the
+//! `ui_events` in the API definition by `protoc redraw`.
+use std::collections::HashMap;
+
+use mrpc::Value;
+use serde::de::DeserializeOwned;
+
+use super::types::{Buffer, TabPage, Window};
+use crate::error::{Error, Result};
+/// Decode the arguments of a single event. Newer versions of Neovim may append arguments
+/// to an event, so anything past the arguments we know about is ignored.
+fn decode_args<T: DeserializeOwned>(args: &Value, count: usize) -> Result<T> {
+    let Value::Array(args) = args else {
+        return Err(Error::Decode {
+            msg: format!("expected event arguments to be an array, got {args}"),
+            source: None,
+        });
+    };
+    let args = Value::Array(args.iter().take(count).cloned().collect());
+    Ok(serde_rmpv::from_value(&args)?)
+}
+/// An event sent to attached UIs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RedrawEvent {
+    /// The `mode_info_set(enabled: bool, cursor_styles: array)` event.
+    ModeInfoSet {
+        /// `enabled: bool`
+        enabled: bool,
+        /// `cursor_styles: array`
+        cursor_styles: Vec<Value>,
+    },
+    /// The `update_menu()` event.
+    UpdateMenu,
+    /// The `busy_start()` event.
+    BusyStart,
+    /// The `busy_stop()` event.
+    BusyStop,
+    /// The `mouse_on()` event.
+    MouseOn,
+    /// The `mouse_off()` event.
+    MouseOff,
+    /// The `mode_change(mode: string, mode_idx: int)` event.
+    ModeChange {
+        /// `mode: string`
+        mode: String,
+        /// `mode_idx: int`
+        mode_idx: i64,
+    },
+    /// The `bell()` event.
+    Bell,
+    /// The `visual_bell()` event.
+    VisualBell,
+    /// The `flush()` event.
+    Flush,
+    /// The `suspend()` event.
+    Suspend,
+    /// The `set_title(title: string)` event.
+    SetTitle {
+        /// `title: string`
+        title: String,
+    },
+    /// The `set_icon(icon: string)` event.
+    SetIcon {
+        /// `icon: string`
+        icon: String,
+    },
+    /// The `screenshot(path: string)` event.
+    Screenshot {
+        /// `path: string`
+        path: String,
+    },
+    /// The `option_set(name: string, value: object)` event.
+    OptionSet {
+        /// `name: string`
+        name: String,
+        /// `value: object`
+        value: Value,
+    },
+    /// The `chdir(path: string)` event.
+    Chdir {
+        /// `path: string`
+        path: String,
+    },
+    /// The `update_fg(fg: int)` event.
+    UpdateFg {
+        /// `fg: int`
+        fg: i64,
+    },
+    /// The `update_bg(bg: int)` event.
+    UpdateBg {
+        /// `bg: int`
+        bg: i64,
+    },
+    /// The `update_sp(sp: int)` event.
+    UpdateSp {
+        /// `sp: int`
+        sp: i64,
+    },
+    /// The `resize(width: int, height: int)` event.
+    Resize {
+        /// `width: int`
+        width: i64,
+        /// `height: int`
+        height: i64,
+    },
+    /// The `clear()` event.
+    Clear,
+    /// The `eol_clear()` event.
+    EolClear,
+    /// The `cursor_goto(row: int, col: int)` event.
+    CursorGoto {
+        /// `row: int`
+        row: i64,
+        /// `col: int`
+        col: i64,
+    },
+    /// The `highlight_set(attrs: dict)` event.
+    HighlightSet {
+        /// `attrs: dict`
+        attrs: HashMap<String, Value>,
+    },
+    /// The `put(str: string)` event.
+    Put {
+        /// `str: string`
+        str: String,
+    },
+    /// The `set_scroll_region(top: int, bot: int, left: int, right: int)` event.
+    SetScrollRegion {
+        /// `top: int`
+        top: i64,
+        /// `bot: int`
+        bot: i64,
+        /// `left: int`
+        left: i64,
+        /// `right: int`
+        right: i64,
+    },
+    /// The `scroll(count: int)` event.
+    Scroll {
+        /// `count: int`
+        count: i64,
+    },
+    /// The `default_colors_set(rgb_fg: int, rgb_bg: int, rgb_sp: int, cterm_fg: int, cterm_bg: int)` event.
+    DefaultColorsSet {
+        /// `rgb_fg: int`
+        rgb_fg: i64,
+        /// `rgb_bg: int`
+        rgb_bg: i64,
+        /// `rgb_sp: int`
+        rgb_sp: i64,
+        /// `cterm_fg: int`
+        cterm_fg: i64,
+        /// `cterm_bg: int`
+        cterm_bg: i64,
+    },
+    /// The `hl_attr_define(id: int, rgb_attrs: dict, cterm_attrs: dict, info: array)` event.
+    HlAttrDefine {
+        /// `id: int`
+        id: i64,
+        /// `rgb_attrs: dict`
+        rgb_attrs: HashMap<String, Value>,
+        /// `cterm_attrs: dict`
+        cterm_attrs: HashMap<String, Value>,
+        /// `info: array`
+        info: Vec<Value>,
+    },
+    /// The `hl_group_set(name: string, id: int)` event.
+    HlGroupSet {
+        /// `name: string`
+        name: String,
+        /// `id: int`
+        id: i64,
+    },
+    /// The `grid_resize(grid: int, width: int, height: int)` event.
+    GridResize {
+        /// `grid: int`
+        grid: i64,
+        /// `width: int`
+        width: i64,
+        /// `height: int`
+        height: i64,
+    },
+    /// The `grid_clear(grid: int)` event.
+    GridClear {
+        /// `grid: int`
+        grid: i64,
+    },
+    /// The `grid_cursor_goto(grid: int, row: int, col: int)` event.
+    GridCursorGoto {
+        /// `grid: int`
+        grid: i64,
+        /// `row: int`
+        row: i64,
+        /// `col: int`
+        col: i64,
+    },
+    /// The `grid_line(grid: int, row: int, col_start: int, data: array, wrap: bool)` event.
+    GridLine {
+        /// `grid: int`
+        grid: i64,
+        /// `row: int`
+        row: i64,
+        /// `col_start: int`
+        col_start: i64,
+        /// `data: array`
+        data: Vec<Value>,
+        /// `wrap: bool`
+        wrap: bool,
+    },
+    /// The `grid_scroll(grid: int, top: int, bot: int, left: int, right: int, rows: int, cols: int)` event.
+    GridScroll {
+        /// `grid: int`
+        grid: i64,
+        /// `top: int`
+        top: i64,
+        /// `bot: int`
+        bot: i64,
+        /// `left: int`
+        left: i64,
+        /// `right: int`
+        right: i64,
+        /// `rows: int`
+        rows: i64,
+        /// `cols: int`
+        cols: i64,
+    },
+    /// The `grid_destroy(grid: int)` event.
+    GridDestroy {
+        /// `grid: int`
+        grid: i64,
+    },
+    /// The `win_pos(grid: int, win: window, startrow: int, startcol: int, width: int, height: int)` event.
+    WinPos {
+        /// `grid: int`
+        grid: i64,
+        /// `win: window`
+        win: Window,
+        /// `startrow: int`
+        startrow: i64,
+        /// `startcol: int`
+        startcol: i64,
+        /// `width: int`
+        width: i64,
+        /// `height: int`
+        height: i64,
+    },
+    /// The `win_float_pos(grid: int, win: window, anchor: string, anchor_grid: int, anchor_row: float, anchor_col: float, focusable: bool, zindex: int)` event.
+    WinFloatPos {
+        /// `grid: int`
+        grid: i64,
+        /// `win: window`
+        win: Window,
+        /// `anchor: string`
+        anchor: String,
+        /// `anchor_grid: int`
+        anchor_grid: i64,
+        /// `anchor_row: float`
+        anchor_row: f64,
+        /// `anchor_col: float`
+        anchor_col: f64,
+        /// `focusable: bool`
+        focusable: bool,
+        /// `zindex: int`
+        zindex: i64,
+    },
+    /// The `win_external_pos(grid: int, win: window)` event.
+    WinExternalPos {
+        /// `grid: int`
+        grid: i64,
+        /// `win: window`
+        win: Window,
+    },
+    /// The `win_hide(grid: int)` event.
+    WinHide {
+        /// `grid: int`
+        grid: i64,
+    },
+    /// The `win_close(grid: int)` event.
+    WinClose {
+        /// `grid: int`
+        grid: i64,
+    },
+    /// The `msg_set_pos(grid: int, row: int, scrolled: bool, sep_char: string)` event.
+    MsgSetPos {
+        /// `grid: int`
+        grid: i64,
+        /// `row: int`
+        row: i64,
+        /// `scrolled: bool`
+        scrolled: bool,
+        /// `sep_char: string`
+        sep_char: String,
+    },
+    /// The `win_viewport(grid: int, win: window, topline: int, botline: int, curline: int, curcol: int, line_count: int, scroll_delta: int)` event.
+    WinViewport {
+        /// `grid: int`
+        grid: i64,
+        /// `win: window`
+        win: Window,
+        /// `topline: int`
+        topline: i64,
+        /// `botline: int`
+        botline: i64,
+        /// `curline: int`
+        curline: i64,
+        /// `curcol: int`
+        curcol: i64,
+        /// `line_count: int`
+        line_count: i64,
+        /// `scroll_delta: int`
+        scroll_delta: i64,
+    },
+    /// The `win_extmark(grid: int, win: window, ns_id: int, mark_id: int, row: int, col: int)` event.
+    WinExtmark {
+        /// `grid: int`
+        grid: i64,
+        /// `win: window`
+        win: Window,
+        /// `ns_id: int`
+        ns_id: i64,
+        /// `mark_id: int`
+        mark_id: i64,
+        /// `row: int`
+        row: i64,
+        /// `col: int`
+        col: i64,
+    },
+    /// The `popupmenu_show(items: array, selected: int, row: int, col: int, grid: int)` event.
+    PopupmenuShow {
+        /// `items: array`
+        items: Vec<Value>,
+        /// `selected: int`
+        selected: i64,
+        /// `row: int`
+        row: i64,
+        /// `col: int`
+        col: i64,
+        /// `grid: int`
+        grid: i64,
+    },
+    /// The `popupmenu_hide()` event.
+    PopupmenuHide,
+    /// The `popupmenu_select(selected: int)` event.
+    PopupmenuSelect {
+        /// `selected: int`
+        selected: i64,
+    },
+    /// The `tabline_update(current: tabpage, tabs: array, current_buffer: buffer, buffers: array)` event.
+    TablineUpdate {
+        /// `current: tabpage`
+        current: TabPage,
+        /// `tabs: array`
+        tabs: Vec<Value>,
+        /// `current_buffer: buffer`
+        current_buffer: Buffer,
+        /// `buffers: array`
+        buffers: Vec<Value>,
+    },
+    /// The `cmdline_show(content: array, pos: int, firstc: string, prompt: string, indent: int, level: int)` event.
+    CmdlineShow {
+        /// `content: array`
+        content: Vec<Value>,
+        /// `pos: int`
+        pos: i64,
+        /// `firstc: string`
+        firstc: String,
+        /// `prompt: string`
+        prompt: String,
+        /// `indent: int`
+        indent: i64,
+        /// `level: int`
+        level: i64,
+    },
+    /// The `cmdline_pos(pos: int, level: int)` event.
+    CmdlinePos {
+        /// `pos: int`
+        pos: i64,
+        /// `level: int`
+        level: i64,
+    },
+    /// The `cmdline_special_char(c: string, shift: bool, level: int)` event.
+    CmdlineSpecialChar {
+        /// `c: string`
+        c: String,
+        /// `shift: bool`
+        shift: bool,
+        /// `level: int`
+        level: i64,
+    },
+    /// The `cmdline_hide(level: int)` event.
+    CmdlineHide {
+        /// `level: int`
+        level: i64,
+    },
+    /// The `cmdline_block_show(lines: array)` event.
+    CmdlineBlockShow {
+        /// `lines: array`
+        lines: Vec<Value>,
+    },
+    /// The `cmdline_block_append(lines: array)` event.
+    CmdlineBlockAppend {
+        /// `lines: array`
+        lines: Vec<Value>,
+    },
+    /// The `cmdline_block_hide()` event.
+    CmdlineBlockHide,
+    /// The `wildmenu_show(items: array)` event.
+    WildmenuShow {
+        /// `items: array`
+        items: Vec<Value>,
+    },
+    /// The `wildmenu_select(selected: int)` event.
+    WildmenuSelect {
+        /// `selected: int`
+        selected: i64,
+    },
+    /// The `wildmenu_hide()` event.
+    WildmenuHide,
+    /// The `msg_show(kind: string, content: array, replace_last: bool)` event.
+    MsgShow {
+        /// `kind: string`
+        kind: String,
+        /// `content: array`
+        content: Vec<Value>,
+        /// `replace_last: bool`
+        replace_last: bool,
+    },
+    /// The `msg_clear()` event.
+    MsgClear,
+    /// The `msg_showcmd(content: array)` event.
+    MsgShowcmd {
+        /// `content: array`
+        content: Vec<Value>,
+    },
+    /// The `msg_showmode(content: array)` event.
+    MsgShowmode {
+        /// `content: array`
+        content: Vec<Value>,
+    },
+    /// The `msg_ruler(content: array)` event.
+    MsgRuler {
+        /// `content: array`
+        content: Vec<Value>,
+    },
+    /// The `msg_history_show(entries: array)` event.
+    MsgHistoryShow {
+        /// `entries: array`
+        entries: Vec<Value>,
+    },
+    /// The `msg_history_clear()` event.
+    MsgHistoryClear,
+    /// The `error_exit(status: int)` event.
+    ErrorExit {
+        /// `status: int`
+        status: i64,
+    },
+    /// An event this version of nvi doesn't know about.
+    Unknown {
+        /// The event name
+        name: String,
+        /// The raw event arguments
+        args: Value,
+    },
+}
+impl RedrawEvent {
+    /// Decode a single event from its name and arguments.
+    pub fn decode(name: &str, args: &Value) -> Result<Self> {
+        Ok(match name {
+            "mode_info_set" => {
+                let (enabled, cursor_styles): (bool, Vec<Value>) = decode_args(args, 2)?;
+                Self::ModeInfoSet {
+                    enabled,
+                    cursor_styles,
+                }
+            }
+            "update_menu" => Self::UpdateMenu,
+            "busy_start" => Self::BusyStart,
+            "busy_stop" => Self::BusyStop,
+            "mouse_on" => Self::MouseOn,
+            "mouse_off" => Self::MouseOff,
+            "mode_change" => {
+                let (mode, mode_idx): (String, i64) = decode_args(args, 2)?;
+                Self::ModeChange { mode, mode_idx }
+            }
+            "bell" => Self::Bell,
+            "visual_bell" => Self::VisualBell,
+            "flush" => Self::Flush,
+            "suspend" => Self::Suspend,
+            "set_title" => {
+                let (title,): (String,) = decode_args(args, 1)?;
+                Self::SetTitle { title }
+            }
+            "set_icon" => {
+                let (icon,): (String,) = decode_args(args, 1)?;
+                Self::SetIcon { icon }
+            }
+            "screenshot" => {
+                let (path,): (String,) = decode_args(args, 1)?;
+                Self::Screenshot { path }
+            }
+            "option_set" => {
+                let (name, value): (String, Value) = decode_args(args, 2)?;
+                Self::OptionSet { name, value }
+            }
+            "chdir" => {
+                let (path,): (String,) = decode_args(args, 1)?;
+                Self::Chdir { path }
+            }
+            "update_fg" => {
+                let (fg,): (i64,) = decode_args(args, 1)?;
+                Self::UpdateFg { fg }
+            }
+            "update_bg" => {
+                let (bg,): (i64,) = decode_args(args, 1)?;
+                Self::UpdateBg { bg }
+            }
+            "update_sp" => {
+                let (sp,): (i64,) = decode_args(args, 1)?;
+                Self::UpdateSp { sp }
+            }
+            "resize" => {
+                let (width, height): (i64, i64) = decode_args(args, 2)?;
+                Self::Resize { width, height }
+            }
+            "clear" => Self::Clear,
+            "eol_clear" => Self::EolClear,
+            "cursor_goto" => {
+                let (row, col): (i64, i64) = decode_args(args, 2)?;
+                Self::CursorGoto { row, col }
+            }
+            "highlight_set" => {
+                let (attrs,): (HashMap<String, Value>,) = decode_args(args, 1)?;
+                Self::HighlightSet { attrs }
+            }
+            "put" => {
+                let (str,): (String,) = decode_args(args, 1)?;
+                Self::Put { str }
+            }
+            "set_scroll_region" => {
+                let (top, bot, left, right): (i64, i64, i64, i64) = decode_args(args, 4)?;
+                Self::SetScrollRegion {
+                    top,
+                    bot,
+                    left,
+                    right,
+                }
+            }
+            "scroll" => {
+                let (count,): (i64,) = decode_args(args, 1)?;
+                Self::Scroll { count }
+            }
+            "default_colors_set" => {
+                let (rgb_fg, rgb_bg, rgb_sp, cterm_fg, cterm_bg): (i64, i64, i64, i64, i64) =
+                    decode_args(args, 5)?;
+                Self::DefaultColorsSet {
+                    rgb_fg,
+                    rgb_bg,
+                    rgb_sp,
+                    cterm_fg,
+                    cterm_bg,
+                }
+            }
+            "hl_attr_define" => {
+                let (id, rgb_attrs, cterm_attrs, info): (
+                    i64,
+                    HashMap<String, Value>,
+                    HashMap<String, Value>,
+                    Vec<Value>,
+                ) = decode_args(args, 4)?;
+                Self::HlAttrDefine {
+                    id,
+                    rgb_attrs,
+                    cterm_attrs,
+                    info,
+                }
+            }
+            "hl_group_set" => {
+                let (name, id): (String, i64) = decode_args(args, 2)?;
+                Self::HlGroupSet { name, id }
+            }
+            "grid_resize" => {
+                let (grid, width, height): (i64, i64, i64) = decode_args(args, 3)?;
+                Self::GridResize {
+                    grid,
+                    width,
+                    height,
+                }
+            }
+            "grid_clear" => {
+                let (grid,): (i64,) = decode_args(args, 1)?;
+                Self::GridClear { grid }
+            }
+            "grid_cursor_goto" => {
+                let (grid, row, col): (i64, i64, i64) = decode_args(args, 3)?;
+                Self::GridCursorGoto { grid, row, col }
+            }
+            "grid_line" => {
+                let (grid, row, col_start, data, wrap): (i64, i64, i64, Vec<Value>, bool) =
+                    decode_args(args, 5)?;
+                Self::GridLine {
+                    grid,
+                    row,
+                    col_start,
+                    data,
+                    wrap,
+                }
+            }
+            "grid_scroll" => {
+                let (grid, top, bot, left, right, rows, cols): (i64, i64, i64, i64, i64, i64, i64) =
+                    decode_args(args, 7)?;
+                Self::GridScroll {
+                    grid,
+                    top,
+                    bot,
+                    left,
+                    right,
+                    rows,
+                    cols,
+                }
+            }
+            "grid_destroy" => {
+                let (grid,): (i64,) = decode_args(args, 1)?;
+                Self::GridDestroy { grid }
+            }
+            "win_pos" => {
+                let (grid, win, startrow, startcol, width, height): (
+                    i64,
+                    Window,
+                    i64,
+                    i64,
+                    i64,
+                    i64,
+                ) = decode_args(args, 6)?;
+                Self::WinPos {
+                    grid,
+                    win,
+                    startrow,
+                    startcol,
+                    width,
+                    height,
+                }
+            }
+            "win_float_pos" => {
+                let (grid, win, anchor, anchor_grid, anchor_row, anchor_col, focusable, zindex): (
+                    i64,
+                    Window,
+                    String,
+                    i64,
+                    f64,
+                    f64,
+                    bool,
+                    i64,
+                ) = decode_args(args, 8)?;
+                Self::WinFloatPos {
+                    grid,
+                    win,
+                    anchor,
+                    anchor_grid,
+                    anchor_row,
+                    anchor_col,
+                    focusable,
+                    zindex,
+                }
+            }
+            "win_external_pos" => {
+                let (grid, win): (i64, Window) = decode_args(args, 2)?;
+                Self::WinExternalPos { grid, win }
+            }
+            "win_hide" => {
+                let (grid,): (i64,) = decode_args(args, 1)?;
+                Self::WinHide { grid }
+            }
+            "win_close" => {
+                let (grid,): (i64,) = decode_args(args, 1)?;
+                Self::WinClose { grid }
+            }
+            "msg_set_pos" => {
+                let (grid, row, scrolled, sep_char): (i64, i64, bool, String) =
+                    decode_args(args, 4)?;
+                Self::MsgSetPos {
+                    grid,
+                    row,
+                    scrolled,
+                    sep_char,
+                }
+            }
+            "win_viewport" => {
+                let (grid, win, topline, botline, curline, curcol, line_count, scroll_delta): (
+                    i64,
+                    Window,
+                    i64,
+                    i64,
+                    i64,
+                    i64,
+                    i64,
+                    i64,
+                ) = decode_args(args, 8)?;
+                Self::WinViewport {
+                    grid,
+                    win,
+                    topline,
+                    botline,
+                    curline,
+                    curcol,
+                    line_count,
+                    scroll_delta,
+                }
+            }
+            "win_extmark" => {
+                let (grid, win, ns_id, mark_id, row, col): (i64, Window, i64, i64, i64, i64) =
+                    decode_args(args, 6)?;
+                Self::WinExtmark {
+                    grid,
+                    win,
+                    ns_id,
+                    mark_id,
+                    row,
+                    col,
+                }
+            }
+            "popupmenu_show" => {
+                let (items, selected, row, col, grid): (Vec<Value>, i64, i64, i64, i64) =
+                    decode_args(args, 5)?;
+                Self::PopupmenuShow {
+                    items,
+                    selected,
+                    row,
+                    col,
+                    grid,
+                }
+            }
+            "popupmenu_hide" => Self::PopupmenuHide,
+            "popupmenu_select" => {
+                let (selected,): (i64,) = decode_args(args, 1)?;
+                Self::PopupmenuSelect { selected }
+            }
+            "tabline_update" => {
+                let (current, tabs, current_buffer, buffers): (
+                    TabPage,
+                    Vec<Value>,
+                    Buffer,
+                    Vec<Value>,
+                ) = decode_args(args, 4)?;
+                Self::TablineUpdate {
+                    current,
+                    tabs,
+                    current_buffer,
+                    buffers,
+                }
+            }
+            "cmdline_show" => {
+                let (content, pos, firstc, prompt, indent, level): (
+                    Vec<Value>,
+                    i64,
+                    String,
+                    String,
+                    i64,
+                    i64,
+                ) = decode_args(args, 6)?;
+                Self::CmdlineShow {
+                    content,
+                    pos,
+                    firstc,
+                    prompt,
+                    indent,
+                    level,
+                }
+            }
+            "cmdline_pos" => {
+                let (pos, level): (i64, i64) = decode_args(args, 2)?;
+                Self::CmdlinePos { pos, level }
+            }
+            "cmdline_special_char" => {
+                let (c, shift, level): (String, bool, i64) = decode_args(args, 3)?;
+                Self::CmdlineSpecialChar { c, shift, level }
+            }
+            "cmdline_hide" => {
+                let (level,): (i64,) = decode_args(args, 1)?;
+                Self::CmdlineHide { level }
+            }
+            "cmdline_block_show" => {
+                let (lines,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::CmdlineBlockShow { lines }
+            }
+            "cmdline_block_append" => {
+                let (lines,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::CmdlineBlockAppend { lines }
+            }
+            "cmdline_block_hide" => Self::CmdlineBlockHide,
+            "wildmenu_show" => {
+                let (items,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::WildmenuShow { items }
+            }
+            "wildmenu_select" => {
+                let (selected,): (i64,) = decode_args(args, 1)?;
+                Self::WildmenuSelect { selected }
+            }
+            "wildmenu_hide" => Self::WildmenuHide,
+            "msg_show" => {
+                let (kind, content, replace_last): (String, Vec<Value>, bool) =
+                    decode_args(args, 3)?;
+                Self::MsgShow {
+                    kind,
+                    content,
+                    replace_last,
+                }
+            }
+            "msg_clear" => Self::MsgClear,
+            "msg_showcmd" => {
+                let (content,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::MsgShowcmd { content }
+            }
+            "msg_showmode" => {
+                let (content,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::MsgShowmode { content }
+            }
+            "msg_ruler" => {
+                let (content,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::MsgRuler { content }
+            }
+            "msg_history_show" => {
+                let (entries,): (Vec<Value>,) = decode_args(args, 1)?;
+                Self::MsgHistoryShow { entries }
+            }
+            "msg_history_clear" => Self::MsgHistoryClear,
+            "error_exit" => {
+                let (status,): (i64,) = decode_args(args, 1)?;
+                Self::ErrorExit { status }
+            }
+            _ => Self::Unknown {
+                name: name.into(),
+                args: args.clone(),
+            },
+        })
+    }
+    /// Decode the parameters of a `redraw` notification. Each parameter is a batch of
+    /// events of the same kind: the event name, followed by the arguments of each event.
+    pub fn parse(params: &[Value]) -> Result<Vec<Self>> {
+        let mut ret = vec![];
+        for batch in params {
+            let Some(batch) = batch.as_array() else {
+                return Err(Error::Decode {
+                    msg: format!("expected a redraw batch to be an array, got {batch}"),
+                    source: None,
+                });
+            };
+            let Some((name, events)) = batch.split_first() else {
+                continue;
+            };
+            let Some(name) = name.as_str() else {
+                return Err(Error::Decode {
+                    msg: format!("expected a redraw event name, got {name}"),
+                    source: None,
+                });
+            };
+            for args in events {
+                ret.push(Self::decode(name, args)?);
+            }
+        }
+        Ok(ret)
+    }
+}
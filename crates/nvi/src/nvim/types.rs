@@ -4,23 +4,26 @@
 
 #![allow(missing_docs)]
 #![allow(clippy::absolute_paths)]
+// The generated API methods on the handle types live in `handles.rs`.
+#![allow(clippy::multiple_inherent_impl)]
 
-use std::fmt;
+use std::{borrow::Cow, fmt, str, string};
 
 use derive_setters::*;
+use serde::{
+    Deserializer, Serializer,
+    de::{self, DeserializeOwned, Visitor},
+};
 use serde_derive::{Deserialize, Serialize};
 use serde_with::{Bytes, NoneAsEmptyString, serde_as};
 
-use super::opts;
+pub use super::api::{BUFFER_EXT_TYPE, TABPAGE_EXT_TYPE, WINDOW_EXT_TYPE};
+use super::{
+    options::{BufOpt, WinOpt},
+    opts,
+};
 use crate::{client, error::Result};
 
-/// The type identifier for Buffer objects in the MessagePack protocol
-pub const BUFFER_EXT_TYPE: i8 = 0;
-/// The type identifier for Window objects in the MessagePack protocol
-pub const WINDOW_EXT_TYPE: i8 = 1;
-/// The type identifier for TabPage objects in the MessagePack protocol
-pub const TABPAGE_EXT_TYPE: i8 = 2;
-
 /// Convert a byte array to a u64
 fn u8_array_to_u64(bytes: &[u8]) -> u64 {
     bytes
@@ -29,6 +32,142 @@ fn u8_array_to_u64(bytes: &[u8]) -> u64 {
         .fold(0u64, |acc, &b| (acc << 8) | b as u64)
 }
 
+/// A string from Neovim. Neovim strings are byte strings, and buffers can hold text that isn't
+/// valid UTF-8, like a Latin-1 file or a binary. An `NvimString` keeps the raw bytes, so text
+/// round-trips losslessly through the API.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NvimString(Vec<u8>);
+
+impl NvimString {
+    /// Create a string from raw bytes.
+    pub fn new(bytes: impl Into<Vec<u8>>) -> Self {
+        Self(bytes.into())
+    }
+
+    /// The raw bytes of the string.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Consume the string, returning the raw bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    /// The string as UTF-8, or an error if it isn't valid UTF-8.
+    pub fn to_str(&self) -> Result<&str, str::Utf8Error> {
+        str::from_utf8(&self.0)
+    }
+
+    /// The string as UTF-8, with invalid sequences replaced by U+FFFD.
+    pub fn to_string_lossy(&self) -> Cow<'_, str> {
+        String::from_utf8_lossy(&self.0)
+    }
+}
+
+impl fmt::Debug for NvimString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_str() {
+            Ok(s) => write!(f, "{s:?}"),
+            Err(_) => write!(f, "NvimString({:?})", self.0),
+        }
+    }
+}
+
+impl fmt::Display for NvimString {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_string_lossy())
+    }
+}
+
+impl From<&str> for NvimString {
+    fn from(s: &str) -> Self {
+        Self(s.into())
+    }
+}
+
+impl From<String> for NvimString {
+    fn from(s: String) -> Self {
+        Self(s.into_bytes())
+    }
+}
+
+impl From<&[u8]> for NvimString {
+    fn from(b: &[u8]) -> Self {
+        Self(b.into())
+    }
+}
+
+impl From<Vec<u8>> for NvimString {
+    fn from(b: Vec<u8>) -> Self {
+        Self(b)
+    }
+}
+
+impl TryFrom<NvimString> for String {
+    type Error = string::FromUtf8Error;
+
+    fn try_from(s: NvimString) -> Result<Self, Self::Error> {
+        Self::from_utf8(s.0)
+    }
+}
+
+impl PartialEq<str> for NvimString {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other.as_bytes()
+    }
+}
+
+impl PartialEq<&str> for NvimString {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == other.as_bytes()
+    }
+}
+
+impl serde::Serialize for NvimString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        // Neovim reads both MessagePack strings and binaries as strings, so we only fall back to
+        // a binary when the bytes aren't valid UTF-8.
+        match self.to_str() {
+            Ok(s) => serializer.serialize_str(s),
+            Err(_) => serializer.serialize_bytes(&self.0),
+        }
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for NvimString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        /// Accepts strings and, for strings that aren't valid UTF-8, bytes.
+        struct NvimStringVisitor;
+
+        impl Visitor<'_> for NvimStringVisitor {
+            type Value = NvimString;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "a string or bytes")
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_string<E: de::Error>(self, v: String) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+
+            fn visit_byte_buf<E: de::Error>(self, v: Vec<u8>) -> Result<Self::Value, E> {
+                Ok(v.into())
+            }
+        }
+
+        deserializer.deserialize_any(NvimStringVisitor)
+    }
+}
+
 #[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
 #[serde(rename = "_ExtStruct")]
@@ -56,6 +195,38 @@ impl Buffer {
             )
             .await
     }
+
+    /// Get the value of a buffer-local option, e.g. `buf.opt(c, Opt::Modifiable)`.
+    pub async fn opt<T: DeserializeOwned>(
+        &self,
+        c: &client::Client,
+        opt: impl Into<BufOpt<T>>,
+    ) -> Result<T> {
+        let opt = opt.into();
+        c.nvim
+            .get_option_value(
+                opt.name(),
+                opts::SetOptionValue::default().buf(self.clone()),
+            )
+            .await
+    }
+
+    /// Set the value of a buffer-local option.
+    pub async fn set_opt<T: serde::Serialize>(
+        &self,
+        c: &client::Client,
+        opt: impl Into<BufOpt<T>>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        let opt = opt.into();
+        c.nvim
+            .set_option_value(
+                opt.name(),
+                value.into(),
+                opts::SetOptionValue::default().buf(self.clone()),
+            )
+            .await
+    }
 }
 
 impl From<Buffer> for u64 {
@@ -101,9 +272,9 @@ impl Window {
     /// Returns (x, y, width, height).
     pub async fn geom(&self, c: &client::Client) -> Result<(i64, i64, i64, i64)> {
         // Invert the weird row/col order that Neovim uses
-        let (y, x) = c.nvim.win_get_position(self).await?;
-        let width = c.nvim.win_get_width(self).await?;
-        let height = c.nvim.win_get_height(self).await?;
+        let (y, x) = self.position(c).await?;
+        let width = self.width(c).await?;
+        let height = self.height(c).await?;
         Ok((x, y, width, height))
     }
 
@@ -132,6 +303,38 @@ impl Window {
             )
             .await
     }
+
+    /// Get the value of a window-local option, e.g. `win.opt(c, Opt::Wrap)`.
+    pub async fn opt<T: DeserializeOwned>(
+        &self,
+        c: &client::Client,
+        opt: impl Into<WinOpt<T>>,
+    ) -> Result<T> {
+        let opt = opt.into();
+        c.nvim
+            .get_option_value(
+                opt.name(),
+                opts::SetOptionValue::default().win(self.clone()),
+            )
+            .await
+    }
+
+    /// Set the value of a window-local option.
+    pub async fn set_opt<T: serde::Serialize>(
+        &self,
+        c: &client::Client,
+        opt: impl Into<WinOpt<T>>,
+        value: impl Into<T>,
+    ) -> Result<()> {
+        let opt = opt.into();
+        c.nvim
+            .set_option_value(
+                opt.name(),
+                value.into(),
+                opts::SetOptionValue::default().win(self.clone()),
+            )
+            .await
+    }
 }
 
 impl From<Window> for u64 {
@@ -522,6 +725,17 @@ impl Group {
     }
 }
 
+/// An error Neovim reports asynchronously through `nvim_error_event`, usually because a
+/// notification we sent failed.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ErrorEvent {
+    /// The error type, from the `error_types` of the API definition: 0 for an exception, 1 for a
+    /// validation error.
+    pub typ: i64,
+    /// The error message
+    pub message: String,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 /// Log levels for Neovim notifications.
 pub enum LogLevel {
@@ -681,11 +895,36 @@ pub struct WindowConf {
 #[cfg(test)]
 mod tests {
     use pretty_assertions::assert_eq;
-    use rmpv::Value;
+    use rmpv::{Value, decode::read_value};
     use serde_rmpv::from_value;
 
     use super::*;
-    use crate::test::NviTest;
+    use crate::{
+        nvim::{options::Opt, redraw::RedrawEvent},
+        test::NviTest,
+    };
+
+    #[tokio::test]
+    async fn test_options() {
+        let test = NviTest::builder().run().await.unwrap();
+        let c = &test.client;
+        assert!(c.options().columns().await.unwrap() > 0);
+        c.options().set(Opt::Ignorecase, true).await.unwrap();
+        assert!(c.options().ignorecase().await.unwrap());
+
+        let buf = c.nvim.get_current_buf().await.unwrap();
+        buf.set_opt(c, Opt::Filetype, "rust").await.unwrap();
+        assert_eq!(buf.opt(c, Opt::Filetype).await.unwrap(), "rust");
+
+        let win = c.nvim.get_current_win().await.unwrap();
+        win.set_opt(c, Opt::Wrap, false).await.unwrap();
+        assert!(!win.opt(c, Opt::Wrap).await.unwrap());
+
+        c.options().set(Opt::Scrolloff, 3).await.unwrap();
+        win.set_opt(c, Opt::Scrolloff, 5).await.unwrap();
+        assert_eq!(c.options().scrolloff().await.unwrap(), 3);
+        assert_eq!(win.opt(c, Opt::Scrolloff).await.unwrap(), 5);
+    }
 
     #[tokio::test]
     async fn test_window_geom() {
@@ -695,6 +934,31 @@ mod tests {
         assert!(g.3 > 0);
     }
 
+    #[tokio::test]
+    async fn test_handle_methods() {
+        let test = NviTest::builder().run().await.unwrap();
+        let c = &test.client;
+
+        let buf = c.nvim.get_current_buf().await.unwrap();
+        buf.set_lines(c, 0, -1, false, vec!["one".into(), "two".into()])
+            .await
+            .unwrap();
+        let lines = buf.lines(c, 0, -1, false).await.unwrap();
+        assert_eq!(lines, vec!["one", "two"]);
+
+        let b = buf.bind(c);
+        assert_eq!(b.line_count().await.unwrap(), 2);
+        assert!(b.is_valid().await.unwrap());
+
+        let win = c.nvim.get_current_win().await.unwrap().bind(c);
+        win.set_cursor((2, 1)).await.unwrap();
+        assert_eq!(win.cursor().await.unwrap(), (2, 1));
+        assert_eq!(&win.buf().await.unwrap(), b.handle());
+
+        let tab = win.tabpage().await.unwrap();
+        assert_eq!(tab.windows(c).await.unwrap(), vec![win.handle().clone()]);
+    }
+
     #[test]
     fn test_deser_windowconf() {
         // Verify that we can deserialize with all missing fields
@@ -730,6 +994,103 @@ mod tests {
         assert_eq!(ret, v2);
     }
 
+    #[test]
+    fn test_parse_redraw() {
+        let params = vec![
+            Value::Array(vec![
+                Value::from("grid_cursor_goto"),
+                Value::Array(vec![Value::from(1), Value::from(2), Value::from(3)]),
+            ]),
+            Value::Array(vec![
+                Value::from("win_pos"),
+                // A trailing argument from a newer Neovim should be ignored
+                Value::Array(vec![
+                    Value::from(2),
+                    Value::Ext(WINDOW_EXT_TYPE, vec![1]),
+                    Value::from(0),
+                    Value::from(0),
+                    Value::from(80),
+                    Value::from(24),
+                    Value::from("extra"),
+                ]),
+            ]),
+            Value::Array(vec![
+                Value::from("flush"),
+                Value::Array(vec![]),
+                Value::Array(vec![]),
+            ]),
+            Value::Array(vec![Value::from("no_such_event"), Value::from(1)]),
+        ];
+        assert_eq!(
+            RedrawEvent::parse(&params).unwrap(),
+            vec![
+                RedrawEvent::GridCursorGoto {
+                    grid: 1,
+                    row: 2,
+                    col: 3
+                },
+                RedrawEvent::WinPos {
+                    grid: 2,
+                    win: Window((WINDOW_EXT_TYPE, vec![1])),
+                    startrow: 0,
+                    startcol: 0,
+                    width: 80,
+                    height: 24
+                },
+                RedrawEvent::Flush,
+                RedrawEvent::Flush,
+                RedrawEvent::Unknown {
+                    name: "no_such_event".into(),
+                    args: Value::from(1)
+                },
+            ]
+        );
+
+        let bad = vec![Value::Array(vec![
+            Value::from("grid_clear"),
+            Value::Array(vec![Value::from("one")]),
+        ])];
+        assert!(RedrawEvent::parse(&bad).is_err());
+    }
+
+    #[test]
+    fn test_deser_error_event() {
+        let v = Value::Array(vec![
+            Value::from(0),
+            Value::from("E492: Not an editor command"),
+        ]);
+        let e: ErrorEvent = from_value(&v).unwrap();
+        assert_eq!(
+            e,
+            ErrorEvent {
+                typ: 0,
+                message: "E492: Not an editor command".into()
+            }
+        );
+    }
+
+    #[test]
+    fn test_nvim_string() {
+        // Valid UTF-8 goes over the wire as a string, anything else as a binary
+        let s = NvimString::from("café");
+        assert_eq!(serde_rmpv::to_value(&s).unwrap(), Value::from("café"));
+        assert_eq!(s.to_str().unwrap(), "café");
+
+        let latin1 = NvimString::new(b"caf\xe9");
+        let v = serde_rmpv::to_value(&latin1).unwrap();
+        assert_eq!(v, Value::Binary(b"caf\xe9".to_vec()));
+        assert_eq!(from_value::<NvimString>(&v).unwrap(), latin1);
+        assert!(latin1.to_str().is_err());
+        assert_eq!(latin1.to_string_lossy(), "caf\u{fffd}");
+        assert_eq!(format!("{latin1:?}"), "NvimString([99, 97, 102, 233])");
+
+        // Neovim may also send invalid UTF-8 as a MessagePack string
+        let v = read_value(&mut &b"\xa4caf\xe9"[..]).unwrap();
+        assert!(matches!(v, Value::String(_)));
+        assert_eq!(from_value::<NvimString>(&v).unwrap(), latin1);
+        assert!(String::try_from(latin1).is_err());
+    }
+
     #[test]
     fn test_deser_event() {
         let v: Event = from_value(&Value::from("User")).unwrap();
@@ -0,0 +1,243 @@
+//! Typed bindings for Vimscript builtin functions, the functions available as `vim.fn` in
+//! Lua. Each call goes through `nvim_call_function`. Generated from the LuaCATS
+//! annotations in `$VIMRUNTIME/lua/vim/_meta/vimfn.lua` by `protoc vimfn`, for the
+//! functions listed in protoc's `overrides::VIMFN_FUNCTIONS`.
+#![allow(clippy::doc_lazy_continuation)]
+#![allow(deprecated)]
+use mrpc::Value;
+use serde::{Serialize, de::DeserializeOwned};
+
+use super::NvimApi;
+use crate::error::Result;
+/// Convert an argument to a MessagePack value.
+fn to_value(v: impl Serialize) -> Result<Value> {
+    Ok(serde_rmpv::to_value(&v)?)
+}
+/// Build a positional argument list. Trailing unset optional arguments are dropped, so
+/// that Vim applies its defaults. Unset arguments followed by set ones are passed as nil.
+fn positional(required: Vec<Value>, optional: Vec<Option<Value>>) -> Vec<Value> {
+    let set = optional
+        .iter()
+        .rposition(Option::is_some)
+        .map_or(0, |i| i + 1);
+    required
+        .into_iter()
+        .chain(
+            optional
+                .into_iter()
+                .take(set)
+                .map(|v| v.unwrap_or(Value::Nil)),
+        )
+        .collect()
+}
+/// Typed bindings for Vimscript builtin functions.
+#[derive(Clone, Debug)]
+pub struct VimFn<'a> {
+    nvim: &'a NvimApi,
+}
+impl<'a> VimFn<'a> {
+    /// Create bindings that call through an API handle.
+    pub fn new(nvim: &'a NvimApi) -> Self {
+        Self { nvim }
+    }
+    /// The result is the name of a buffer. Mostly as it is displayed by the `:ls` command, but not using special names such as "[No Name]". If `buf` is omitted the current buffer is used.
+    pub async fn bufname(&self, buf: Option<Value>) -> Result<String> {
+        let args = positional(vec![], vec![buf.map(to_value).transpose()?]);
+        self.nvim.call_function("bufname", args).await
+    }
+    /// The result is a Number, which is the number of a buffer associated with the `buf` argument. See `bufname()` for how `buf` is interpreted. If the buffer doesn't exist, -1 is returned.
+    ///
+    /// If the `create` argument is present and TRUE, a new, unlisted, buffer is created and its number is returned.
+    pub async fn bufnr(&self, buf: Option<Value>, create: Option<Value>) -> Result<i64> {
+        let args = positional(
+            vec![],
+            vec![
+                buf.map(to_value).transpose()?,
+                create.map(to_value).transpose()?,
+            ],
+        );
+        self.nvim.call_function("bufnr", args).await
+    }
+    /// This function checks if an executable with the name `expr` exists. `expr` must be the name of the program without any arguments.
+    ///
+    /// The result is a Number: 1 exists 0 does not exist `exepath()` can be used to get the full path of an executable.
+    pub async fn executable(&self, expr: &str) -> Result<i64> {
+        let args = positional(vec![to_value(expr)?], vec![]);
+        self.nvim.call_function("executable", args).await
+    }
+    /// The result is a Number, which is `TRUE` if `expr` is defined, zero otherwise.
+    ///
+    /// For checking for a supported feature use `has()`. For checking if a file exists use `filereadable()`.
+    pub async fn exists(&self, expr: &str) -> Result<i64> {
+        let args = positional(vec![to_value(expr)?], vec![]);
+        self.nvim.call_function("exists", args).await
+    }
+    /// Expand wildcards and the following special keywords in `string`. 'wildignorecase' applies.
+    ///
+    /// If `list` is given and it is `TRUE`, a List will be returned. Otherwise the result is a String and when there are several matches, they are separated by <NL> characters.
+    ///
+    /// If the expansion fails, the result is an empty string. A name for a non-existing file is not included, unless `string` does not start with '%', '#' or '<', see below.
+    ///
+    /// When `string` starts with '%', '#' or '<', the expansion is done like for the `cmdline-special` variables with their associated modifiers.
+    ///
+    /// Unless the optional `nosuf` argument is given and it is `TRUE`, the 'suffixes' and 'wildignore' options are used.
+    pub async fn expand(&self, string: &str, nosuf: Option<bool>) -> Result<String> {
+        let args = positional(
+            vec![to_value(string)?],
+            vec![nosuf.map(to_value).transpose()?],
+        );
+        self.nvim.call_function("expand", args).await
+    }
+    /// Expand wildcards and the following special keywords in `string`. 'wildignorecase' applies.
+    ///
+    /// If `list` is given and it is `TRUE`, a List will be returned. Otherwise the result is a String and when there are several matches, they are separated by <NL> characters.
+    ///
+    /// If the expansion fails, the result is an empty string. A name for a non-existing file is not included, unless `string` does not start with '%', '#' or '<', see below.
+    ///
+    /// When `string` starts with '%', '#' or '<', the expansion is done like for the `cmdline-special` variables with their associated modifiers.
+    ///
+    /// Unless the optional `nosuf` argument is given and it is `TRUE`, the 'suffixes' and 'wildignore' options are used.
+    pub async fn expand_list(
+        &self,
+        string: &str,
+        nosuf: bool,
+        list: impl Serialize,
+    ) -> Result<Vec<String>> {
+        let args = positional(
+            vec![to_value(string)?, to_value(nosuf)?, to_value(list)?],
+            vec![],
+        );
+        self.nvim.call_function("expand", args).await
+    }
+    /// Modify file name `fname` according to `mods`. `mods` is a string of characters like it is used for file names on the command line. See `filename-modifiers`.
+    ///
+    /// When `fname` is empty then with `mods` ":h" returns ".", so that `:cd` can be used with it. This is different from expand('%:h') without a buffer name, which returns an empty string.
+    pub async fn fnamemodify(&self, fname: &str, mods: &str) -> Result<String> {
+        let args = positional(vec![to_value(fname)?, to_value(mods)?], vec![]);
+        self.nvim.call_function("fnamemodify", args).await
+    }
+    /// The result is a Number which is the state of the modifiers for the last obtained character with getchar() or in another way. These values are added together: 2 shift 4 control 8 alt (meta) 16 meta (when it's different from ALT) 32 mouse double click 64 mouse triple click 96 mouse quadruple click (== 32 + 64) 128 command (Mac) or super Only the modifiers that have not been included in the character itself are obtained. Thus Shift-a results in "A" without a modifier. Returns 0 if no modifiers are used.
+    pub async fn getcharmod(&self) -> Result<i64> {
+        let args = positional(vec![], vec![]);
+        self.nvim.call_function("getcharmod", args).await
+    }
+    /// Get a single character from the user or input stream as a string. If `expr` is omitted, wait until a character is available. If `expr` is 0 or false, only get a character when one is available. Return an empty string otherwise. If `expr` is 1 or true, only check if a character is available, it is not consumed. Return an empty string if no character is available. Otherwise this works like `getchar()`, except that a number result is converted to a string.
+    pub async fn getcharstr(&self, expr: Option<i64>) -> Result<String> {
+        let args = positional(vec![], vec![expr.map(to_value).transpose()?]);
+        self.nvim.call_function("getcharstr", args).await
+    }
+    /// The result is a String, which is the name of the current working directory. 'autochdir' is ignored.
+    ///
+    /// With `winnr` return the local current directory of this window in the current tab page. `winnr` can be the window number or the `window-ID`. If `winnr` is -1 return the name of the global working directory. See also `haslocaldir()`.
+    ///
+    /// With `winnr` and `tabnr` return the local current directory of the window in the specified tab page. If `winnr` is -1 return the working directory of the tabpage. If `winnr` is zero use the current window, if `tabnr` is zero use the current tabpage. Without any arguments, return the actual working directory of the current window. Return an empty string if the arguments are invalid.
+    pub async fn getcwd(&self, winnr: Option<i64>, tabnr: Option<i64>) -> Result<String> {
+        let args = positional(
+            vec![],
+            vec![
+                winnr.map(to_value).transpose()?,
+                tabnr.map(to_value).transpose()?,
+            ],
+        );
+        self.nvim.call_function("getcwd", args).await
+    }
+    /// Without `end` the result is a String, which is line `lnum` from the current buffer.
+    ///
+    /// `lnum` can be a String, then `line()` is used to get the line number.
+    ///
+    /// When `lnum` is a number smaller than 1 or bigger than the number of lines in the buffer, an empty string is returned.
+    ///
+    /// When `end` is given the result is a `List` where each item is a line from the current buffer in the range `lnum` to `end`, including line `end`.
+    pub async fn getline(&self, lnum: impl Serialize) -> Result<String> {
+        let args = positional(vec![to_value(lnum)?], vec![]);
+        self.nvim.call_function("getline", args).await
+    }
+    /// Without `end` the result is a String, which is line `lnum` from the current buffer.
+    ///
+    /// `lnum` can be a String, then `line()` is used to get the line number.
+    ///
+    /// When `lnum` is a number smaller than 1 or bigger than the number of lines in the buffer, an empty string is returned.
+    ///
+    /// When `end` is given the result is a `List` where each item is a line from the current buffer in the range `lnum` to `end`, including line `end`.
+    pub async fn getline_list(
+        &self,
+        lnum: impl Serialize,
+        end_: impl Serialize,
+    ) -> Result<Vec<String>> {
+        let args = positional(vec![to_value(lnum)?, to_value(end_)?], vec![]);
+        self.nvim.call_function("getline", args).await
+    }
+    /// The result is a String, which is the contents of register `regname`.
+    pub async fn getreg(&self, regname: Option<&str>) -> Result<String> {
+        let args = positional(vec![], vec![regname.map(to_value).transpose()?]);
+        self.nvim.call_function("getreg", args).await
+    }
+    /// Returns 1 if `feature` is supported, 0 otherwise. The `feature` argument is a feature name like "nvim-0.2.1" or "win32", see below. See also `exists()`.
+    pub async fn has(&self, feature: &str) -> Result<i64> {
+        let args = positional(vec![to_value(feature)?], vec![]);
+        self.nvim.call_function("has", args).await
+    }
+    /// Turn the internal byte representation of keys into a form that can be used for `:map`.
+    pub async fn keytrans(&self, string: &str) -> Result<String> {
+        let args = positional(vec![to_value(string)?], vec![]);
+        self.nvim.call_function("keytrans", args).await
+    }
+    /// Return a string that indicates the current mode. If `expr` is supplied and it evaluates to a non-zero Number or a non-empty String (`non-zero-arg`), then the full mode is returned, otherwise only the first letter is returned.
+    pub async fn mode(&self, expr: Option<Value>) -> Result<String> {
+        let args = positional(vec![], vec![expr.map(to_value).transpose()?]);
+        self.nvim.call_function("mode", args).await
+    }
+    /// Return a String with `fmt`, where "%" items are replaced by the formatted form of their respective arguments.
+    pub async fn printf(&self, fmt: &str, rest: Vec<Value>) -> Result<String> {
+        let mut args = positional(vec![to_value(fmt)?], vec![]);
+        args.extend(rest);
+        self.nvim.call_function("printf", args).await
+    }
+    /// Set the register `regname` to `value`. If `regname` is "" or "@", the unnamed register '"' is used.
+    pub async fn setreg<T: DeserializeOwned>(
+        &self,
+        regname: &str,
+        value: impl Serialize,
+        options: Option<&str>,
+    ) -> Result<T> {
+        let args = positional(
+            vec![to_value(regname)?, to_value(value)?],
+            vec![options.map(to_value).transpose()?],
+        );
+        self.nvim.call_function("setreg", args).await
+    }
+    /// Returns `standard-path` locations of various default files and directories.
+    pub async fn stdpath<T: DeserializeOwned>(&self, what: &str) -> Result<T> {
+        let args = positional(vec![to_value(what)?], vec![]);
+        self.nvim.call_function("stdpath", args).await
+    }
+    /// The result is a String, which is a formatted date and time, as specified by the `format` string. The given `time` is used, or the current time if no time is given.
+    pub async fn strftime(&self, format: &str, time: Option<f64>) -> Result<String> {
+        let args = positional(
+            vec![to_value(format)?],
+            vec![time.map(to_value).transpose()?],
+        );
+        self.nvim.call_function("strftime", args).await
+    }
+    /// The result is a String, which is the name of a file that doesn't exist. It can be used for a temporary file.
+    pub async fn tempname(&self) -> Result<String> {
+        let args = positional(vec![], vec![]);
+        self.nvim.call_function("tempname", args).await
+    }
+    /// The result is a Number representing the type of `expr`. Instead of using the number directly, it is better to use the v:t_ variable that has the value: Number: 0 `v:t_number` String: 1 `v:t_string` Funcref: 2 `v:t_func` List: 3 `v:t_list` Dictionary: 4 `v:t_dict` Float: 5 `v:t_float` Boolean: 6 `v:t_bool` (`v:false` and `v:true`) Null: 7 (`v:null`) Blob: 10 `v:t_blob`
+    pub async fn r#type(&self, expr: impl Serialize) -> Result<i64> {
+        let args = positional(vec![to_value(expr)?], vec![]);
+        self.nvim.call_function("type", args).await
+    }
+    /// Get the `window-ID` for the specified window. When `win` is missing use the current window. With `win` this is the window number. The top window has number 1. Without `tab` use the current tab, otherwise the tab with number `tab`. The first tab has number one. Return zero if the window cannot be found.
+    pub async fn win_getid(&self, win: Option<i64>, tab: Option<i64>) -> Result<i64> {
+        let args = positional(
+            vec![],
+            vec![
+                win.map(to_value).transpose()?,
+                tab.map(to_value).transpose()?,
+            ],
+        );
+        self.nvim.call_function("win_getid", args).await
+    }
+}
@@ -0,0 +1,113 @@
+//! Panic isolation for plugin handlers.
+//!
+//! A panic in a request or notification handler would otherwise unwind through the connection
+//! task and take the whole plugin down, leaving Lua callers hanging in `rpcrequest`. Handlers are
+//! run inside [`catch`], which turns a panic into a [`Panic`] value that can be logged and
+//! returned to the caller, so the plugin keeps serving.
+
+use std::{
+    backtrace::Backtrace,
+    cell::RefCell,
+    fmt,
+    future::Future,
+    panic::{self, AssertUnwindSafe, PanicHookInfo},
+    sync::Once,
+};
+
+use futures::FutureExt;
+
+tokio::task_local! {
+    /// Set while a handler is being polled inside `catch`.
+    static CATCHING: bool;
+}
+
+thread_local! {
+    /// The location and backtrace of the last panic caught on this thread.
+    static LAST_PANIC: RefCell<Option<(String, String)>> = const { RefCell::new(None) };
+}
+
+/// A panic caught in a handler.
+#[derive(Debug, Clone)]
+pub struct Panic {
+    /// The panic message
+    pub message: String,
+    /// Where the panic occurred, e.g. `src/main.rs:10:5`
+    pub location: String,
+    /// The backtrace captured when the panic occurred
+    pub backtrace: String,
+}
+
+impl fmt::Display for Panic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "panicked at {}: {}", self.location, self.message)
+    }
+}
+
+/// Install a panic hook that records the location and backtrace of panics inside [`catch`],
+/// rather than printing them. Panics elsewhere go to the previous hook. Installed once.
+fn install_hook() {
+    static HOOK: Once = Once::new();
+    HOOK.call_once(|| {
+        let prev = panic::take_hook();
+        panic::set_hook(Box::new(move |info: &PanicHookInfo<'_>| {
+            if CATCHING.try_with(|c| *c).unwrap_or(false) {
+                let location = info
+                    .location()
+                    .map(|l| l.to_string())
+                    .unwrap_or_else(|| "<unknown>".into());
+                let backtrace = Backtrace::force_capture().to_string();
+                LAST_PANIC.with(|p| p.replace(Some((location, backtrace))));
+            } else {
+                prev(info);
+            }
+        }));
+    });
+}
+
+/// Run a handler future, catching any panic while it is polled.
+pub async fn catch<F: Future>(inner: F) -> Result<F::Output, Panic> {
+    install_hook();
+    CATCHING
+        .scope(true, AssertUnwindSafe(inner).catch_unwind())
+        .await
+        .map_err(|payload| {
+            let message = if let Some(s) = payload.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = payload.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "<non-string panic payload>".into()
+            };
+            let (location, backtrace) = LAST_PANIC
+                .with(|p| p.take())
+                .unwrap_or_else(|| ("<unknown>".into(), String::new()));
+            Panic {
+                message,
+                location,
+                backtrace,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_catch() {
+        let ret = catch(Box::pin(async { 42 })).await;
+        assert_eq!(ret.unwrap(), 42);
+
+        let ret = catch(Box::pin(async {
+            tokio::task::yield_now().await;
+            if true {
+                panic!("handler exploded");
+            }
+        }))
+        .await;
+        let p: Panic = ret.unwrap_err();
+        assert_eq!(p.message, "handler exploded");
+        assert!(p.location.contains("panics.rs"), "{}", p.location);
+        assert!(p.to_string().starts_with("panicked at "));
+    }
+}
@@ -1,28 +1,56 @@
 //! Service implementation for Nvi plugins.
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     str::FromStr,
-    sync::{Arc, Mutex},
+    sync::{
+        Arc, Mutex, OnceLock,
+        atomic::{AtomicU64, Ordering},
+    },
+    time::Duration,
 };
 
 use async_trait::async_trait;
-use tokio::sync::{RwLock, broadcast};
-use tracing::{debug, trace, warn};
+use serde_derive::{Deserialize, Serialize};
+use tokio::{
+    sync::{RwLock, broadcast, watch},
+    time,
+};
+use tracing::{debug, error, trace, warn};
 
 use crate::{
-    Value,
-    client::Client,
-    error::{Error, Result},
-    highlights, macro_types, nvim,
+    Value, auth,
+    client::{Client, ConnectionId, Peers},
+    error::{Error, PluginError, Result},
+    events::Subscriptions,
+    highlights,
+    host::HostStatus,
+    logging, macro_types, nvim,
     nvim::types,
+    panics::{self, Panic},
 };
 
 /// The message used to query the status of the plugin
 pub const STATUS_MESSAGE: &str = "__nvi_status";
 
+/// The request that changes the plugin's log filter at runtime, registered for every plugin, e.g.
+/// `my_plugin.set_log_level('debug')`. A plugin method with the same name replaces it. The filter
+/// is process-wide, so in a plugin host this changes the level for every hosted plugin. See
+/// [`logging::set_level`].
+pub const SET_LOG_LEVEL: &str = "set_log_level";
+
+/// The notification Neovim sends to report an error asynchronously, e.g. for a failed notification
+pub const ERROR_EVENT: &str = "nvim_error_event";
+
+/// The version of the protocol nvi uses to talk to plugins, exposed in plugin manifests. This is
+/// bumped whenever the method naming or argument conventions change incompatibly.
+///
+/// Version 2 replies to the status request with a [`StatusReport`] rather than a string.
+pub const PROTOCOL_VERSION: u64 = 2;
+
 /// The status of the plugin
-#[derive(Debug, Clone, Copy, strum::Display)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::Display, Serialize, Deserialize)]
 #[strum(serialize_all = "lowercase")]
+#[serde(rename_all = "lowercase")]
 pub enum Status {
     /// The plugin is stopped
     Stopped,
@@ -32,6 +60,19 @@ pub enum Status {
     Running,
 }
 
+/// The reply to the status request: the plugin's status, and the number of panics caught in its
+/// handlers since it started.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusReport {
+    /// The status of the plugin
+    pub status: Status,
+    /// The number of panics caught in request and notification handlers
+    pub panics: u64,
+    /// The status of each plugin in a [`PluginHost`](crate::host::PluginHost), by name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub plugins: BTreeMap<String, Self>,
+}
+
 /// The `NviPlugin` trait is the way Nvi plugins are defined. Usually this is done with the
 /// `nvi_plugin` attribute macro, which generates the required methods for the trait.
 #[allow(unused_variables)]
@@ -39,6 +80,12 @@ pub enum Status {
 pub trait NviPlugin: Sync + Send + 'static {
     fn name(&self) -> String;
 
+    /// The plugin version. The `nvi_plugin` macro sets this to the version of the crate the plugin
+    /// is defined in.
+    fn version(&self) -> String {
+        "".into()
+    }
+
     /// Return the highlight groups for this service. Highlight group names have the plugin name
     /// prepended (as in `Client::hl_name`) before creation.
     fn highlights(&self) -> Result<highlights::Highlights> {
@@ -50,7 +97,8 @@ pub trait NviPlugin: Sync + Send + 'static {
         vec![]
     }
 
-    /// Return the plugin doc string. The string is empty if there are no docs.
+    /// Return the plugin doc string. The string is empty if there are no docs. The first line of
+    /// the doc string is the short description used in vimdoc headers and plugin listings.
     fn docs(&self) -> Result<String> {
         Ok("".into())
     }
@@ -60,6 +108,7 @@ pub trait NviPlugin: Sync + Send + 'static {
     /// derived with the `nvim_service` annotation, and should not be over-ridden by the user.
     async fn bootstrap(&self, client: &mut Client) -> Result<()> {
         let methods = self.inspect();
+        let own_log_level = methods.iter().any(|m| m.name == SET_LOG_LEVEL);
         for method in methods {
             let name = method.name.clone();
 
@@ -112,6 +161,11 @@ pub trait NviPlugin: Sync + Send + 'static {
         client
             .register_rpcrequest::<String>(&self.name(), STATUS_MESSAGE, &[])
             .await?;
+        if !own_log_level {
+            client
+                .register_rpcrequest(&self.name(), SET_LOG_LEVEL, &["level"])
+                .await?;
+        }
         let highlights = self.highlights()?;
         highlights.create(client).await?;
         Ok(())
@@ -165,6 +219,109 @@ pub trait NviPlugin: Sync + Send + 'static {
     async fn connected(&mut self, client: &mut Client) -> Result<()> {
         Ok(())
     }
+
+    /// Handle an error that Neovim reports asynchronously. Neovim can't reply to a notification,
+    /// so if one fails - for instance, a call to one of the `*_notify` API functions - the error
+    /// arrives here. By default, the error is logged.
+    async fn error_event(&self, client: &mut Client, error: types::ErrorEvent) -> Result<()> {
+        warn!("error event from Neovim: {:?}", error);
+        Ok(())
+    }
+
+    /// Decide whether an error from one of this plugin's methods is also shown to the user with
+    /// `vim.notify`. Request errors are already raised in the Lua caller, so by default only
+    /// errors from notifications, which have no caller to return to, are shown.
+    fn notify_error(&self, error: &PluginError, request: bool) -> bool {
+        !request
+    }
+
+    /// A handle on the status of the plugins hosted by this one, reported in reply to the status
+    /// request. It's taken once, when the plugin is first connected, and read without locking the
+    /// plugin. Only [`PluginHost`](crate::host::PluginHost) hosts other plugins.
+    fn hosted_status(&self) -> HostStatus {
+        HostStatus::default()
+    }
+}
+
+/// Map a plugin's method names to whether they take a mutable receiver.
+pub fn method_mutability<T: NviPlugin>(plugin: &T) -> HashMap<String, bool> {
+    plugin
+        .inspect()
+        .into_iter()
+        .map(|m| (m.name, m.is_mut))
+        .collect()
+}
+
+/// A plugin instance that outlives its connections, along with the state kept across them. Used
+/// when reconnecting, and by the shared-state listeners, to make a connection for each editor.
+pub struct SharedPlugin<T>
+where
+    T: NviPlugin,
+{
+    /// The plugin instance
+    plugin: Arc<RwLock<T>>,
+    /// A map of method names to their mutability
+    methods: HashMap<String, bool>,
+    /// The number of panics caught in handlers, over all connections
+    panics: Arc<AtomicU64>,
+    /// The clients for every open connection to the plugin
+    peers: Peers,
+    /// The status of hosted plugins, readable without the plugin lock
+    hosted: HostStatus,
+}
+
+impl<T> SharedPlugin<T>
+where
+    T: NviPlugin,
+{
+    /// Share a plugin instance between connections.
+    pub fn new(plugin: T) -> Self {
+        Self {
+            methods: method_mutability(&plugin),
+            hosted: plugin.hosted_status(),
+            plugin: Arc::new(RwLock::new(plugin)),
+            panics: Arc::new(AtomicU64::new(0)),
+            peers: Peers::default(),
+        }
+    }
+
+    /// Create a connection to the plugin. The connection registers its client in the peers once
+    /// the plugin is bootstrapped, and removes it when the connection closes.
+    pub fn connection(&self, shutdown_tx: broadcast::Sender<()>) -> RpcConnection<T> {
+        RpcConnection {
+            plugin: self.plugin.clone(),
+            shutdown_tx,
+            channel_id: Arc::new(Mutex::new(None)),
+            methods: self.methods.clone(),
+            status: Arc::new(Mutex::new(Status::Stopped)),
+            api_level: Arc::new(OnceLock::new()),
+            panics: self.panics.clone(),
+            id: ConnectionId::next(),
+            peers: self.peers.clone(),
+            auth: None,
+            auth_timeout: auth::DEFAULT_TIMEOUT,
+            authenticated: watch::Sender::new(None),
+            subscriptions: Subscriptions::default(),
+            closed: watch::Sender::new(()),
+            hosted: self.hosted.clone(),
+        }
+    }
+}
+
+// The plugin itself isn't cloned, so `T` needn't be `Clone`.
+impl<T> Clone for SharedPlugin<T>
+where
+    T: NviPlugin,
+{
+    fn clone(&self) -> Self {
+        Self {
+            plugin: self.plugin.clone(),
+            methods: self.methods.clone(),
+            panics: self.panics.clone(),
+            peers: self.peers.clone(),
+            hosted: self.hosted.clone(),
+        }
+    }
 }
 
 /// RpcConnection handles a single RPC connection
@@ -172,8 +329,8 @@ pub struct RpcConnection<T>
 where
     T: NviPlugin,
 {
-    /// The plugin instance
-    plugin: RwLock<T>,
+    /// The plugin instance, which may outlive this connection
+    plugin: Arc<RwLock<T>>,
     /// The channel used to signal shutdown
     shutdown_tx: broadcast::Sender<()>,
     /// The channel ID for this connection
@@ -182,6 +339,27 @@ where
     methods: HashMap<String, bool>,
     /// The status of the connection
     status: Arc<Mutex<Status>>,
+    /// The API level of the connected Neovim, shared with every client we hand out
+    api_level: Arc<OnceLock<u64>>,
+    /// The number of panics caught in handlers, shared with the other connections to the plugin
+    panics: Arc<AtomicU64>,
+    /// The ID of this connection
+    id: ConnectionId,
+    /// The clients for every connection that shares this plugin instance
+    peers: Peers,
+    /// The token the peer must present before the plugin is bootstrapped, if any
+    auth: Option<auth::Token>,
+    /// How long the peer has to authenticate
+    auth_timeout: Duration,
+    /// Whether the peer has authenticated: `None` until it tries, then the outcome
+    authenticated: watch::Sender<Option<bool>>,
+    /// The subscribers to events sent by Lua
+    subscriptions: Subscriptions,
+    /// Closed when mrpc drops the connection after the editor disconnects, which starts the
+    /// cleanup in [`Self::watch_close`]
+    closed: watch::Sender<()>,
+    /// The status of hosted plugins, readable without the plugin lock
+    hosted: HostStatus,
 }
 
 impl<T> RpcConnection<T>
@@ -190,29 +368,100 @@ where
 {
     /// Create a new RpcConnection
     pub fn new(shutdown_tx: broadcast::Sender<()>, plugin: T) -> Self {
-        let method_mutability = plugin
-            .inspect()
-            .into_iter()
-            .map(|m| (m.name, m.is_mut))
-            .collect();
+        SharedPlugin::new(plugin).connection(shutdown_tx)
+    }
 
-        Self {
-            plugin: RwLock::new(plugin),
-            shutdown_tx,
-            channel_id: Arc::new(Mutex::new(None)),
-            methods: method_mutability,
-            status: Arc::new(Mutex::new(Status::Stopped)),
+    /// Require the peer to present `token` with an [`auth::AUTH_MESSAGE`] request before
+    /// anything else. Connections that don't, or that take longer than `timeout`, are closed.
+    pub fn with_auth(mut self, token: auth::Token, timeout: Duration) -> Self {
+        self.auth = Some(token);
+        self.auth_timeout = timeout;
+        self
+    }
+
+    /// Check a message that arrived before the peer authenticated. Only a matching
+    /// [`auth::AUTH_MESSAGE`] request is accepted; anything else rejects the connection.
+    fn authenticate(&self, method: &str, params: &[Value]) -> mrpc::Result<Value> {
+        let token = params.first().and_then(Value::as_str);
+        let ok = method == auth::AUTH_MESSAGE
+            && self
+                .auth
+                .as_ref()
+                .zip(token)
+                .is_some_and(|(auth, token)| auth.verify(token));
+        self.authenticated.send_replace(Some(ok));
+        if ok {
+            debug!("connection {} authenticated", self.id);
+            Ok(Value::Boolean(true))
+        } else {
+            if method == auth::AUTH_MESSAGE {
+                warn!("connection {} presented an invalid token", self.id);
+            } else {
+                warn!(
+                    "connection {} called {method} before authenticating",
+                    self.id
+                );
+            }
+            Err(auth_error())
+        }
+    }
+
+    /// Whether the peer must still authenticate before its messages are handled.
+    fn needs_auth(&self) -> bool {
+        self.auth.is_some() && *self.authenticated.borrow() != Some(true)
+    }
+
+    /// Wait for the peer to authenticate, returning false if it fails to or times out.
+    async fn wait_authenticated(&self) -> bool {
+        let mut rx = self.authenticated.subscribe();
+        match time::timeout(self.auth_timeout, rx.wait_for(Option::is_some)).await {
+            Ok(Ok(v)) => *v == Some(true),
+            Ok(Err(_)) => false,
+            Err(_) => {
+                warn!("connection {} did not authenticate in time", self.id);
+                false
+            }
+        }
+    }
+
+    /// Record a panic caught in a handler, and convert it to an error for the caller.
+    fn handle_panic(&self, method: &str, p: &Panic) -> Error {
+        self.panics.fetch_add(1, Ordering::Relaxed);
+        error!("handler {method} {p}\n{}", p.backtrace);
+        Error::Plugin {
+            kind: "panic".into(),
+            message: p.to_string(),
+            data: None,
         }
     }
 
     /// Create a new Client for the given plugin
     fn make_client(&self, plugin_name: &str, sender: mrpc::RpcSender) -> Client {
-        Client::new(
+        let mut client = Client::new(
             sender,
             plugin_name,
             self.channel_id.lock().unwrap().expect("channel id not set"),
             self.shutdown_tx.clone(),
-        )
+        );
+        client.nvim.api_level = self.api_level.clone();
+        client.connection_id = self.id;
+        client.peers = self.peers.downgrade();
+        client.subscriptions = self.subscriptions.clone();
+        client
+    }
+
+    /// Clean up after the connection once it closes: remove it from the peers, so that it no
+    /// longer receives broadcasts, and end its event subscriptions.
+    fn watch_close(&self) {
+        let mut closed = self.closed.subscribe();
+        let (id, peers, subscriptions) = (self.id, self.peers.clone(), self.subscriptions.clone());
+        tokio::spawn(async move {
+            // Only ever errors, once the sender is dropped with the connection.
+            let _ = closed.changed().await;
+            trace!("connection {id} closed");
+            peers.remove(id);
+            subscriptions.clear();
+        });
     }
 
     /// Handle an error that occurred during a notification
@@ -222,48 +471,72 @@ where
         e: Error,
         sender: mrpc::RpcSender,
     ) -> mrpc::Result<()> {
-        warn!("error handling notification: {:?}", e);
-        let client = self.make_client(&self.plugin.read().await.name(), sender);
-        if let Err(notify_err) = client
-            .notify(
-                types::LogLevel::Warn,
-                &format!("nvi notify error: {method} - {e:?}"),
-            )
-            .await
-        {
-            warn!("error sending notify error notification: {:?}", notify_err);
-        }
+        let name = self.plugin.read().await.name();
+        let error = PluginError::new(&name, method, e);
+        warn!("error handling notification: {error}");
+        self.surface_error(&name, &error, false, sender).await;
         Err(mrpc::RpcError::Service(mrpc::ServiceError {
             name: "NviNotifyError".to_string(),
-            value: Value::String(format!("{e:?}").into()),
+            value: error.to_value(),
         }))
     }
 
-    /// Handle an error that occurred during a request
+    /// Handle an error that occurred during a request. The error is returned to the caller as a
+    /// structured [`PluginError`], which the Lua wrappers raise with `error()`.
     async fn handle_request_error(
         &self,
         method: &str,
         e: Value,
         sender: mrpc::RpcSender,
     ) -> mrpc::Result<Value> {
-        warn!("error handling request: {:?}", e);
-        let client = self.make_client(&self.plugin.read().await.name(), sender);
+        let name = self.plugin.read().await.name();
+        let error = PluginError::from_value(&e).unwrap_or_else(|| {
+            let msg = match e.as_str() {
+                Some(s) => s.to_string(),
+                None => e.to_string(),
+            };
+            PluginError::new(&name, method, Error::Internal { msg })
+        });
+        warn!("error handling request: {error}");
+        self.surface_error(&name, &error, true, sender).await;
+        Err(mrpc::RpcError::Service(mrpc::ServiceError {
+            name: "NviRequestError".to_string(),
+            value: error.to_value(),
+        }))
+    }
+
+    /// Show an error to the user with `vim.notify`, if the plugin asks for it. The plugin lock is
+    /// released before the notification is sent, so that the round trip to the editor can't hold
+    /// up other handlers.
+    async fn surface_error(
+        &self,
+        name: &str,
+        error: &PluginError,
+        request: bool,
+        sender: mrpc::RpcSender,
+    ) {
+        let notify = self.plugin.read().await.notify_error(error, request);
+        if !notify {
+            return;
+        }
+        let client = self.make_client(name, sender);
         if let Err(notify_err) = client
-            .notify(
-                types::LogLevel::Warn,
-                &format!("nvi request error: {method} - {e:?}"),
-            )
+            .notify(types::LogLevel::Warn, &error.to_string())
             .await
         {
-            warn!("error sending request error notification: {:?}", notify_err);
+            warn!("error sending error notification: {:?}", notify_err);
         }
-        Err(mrpc::RpcError::Service(mrpc::ServiceError {
-            name: "NviRequestError".to_string(),
-            value: Value::String(format!("{e:?}").into()),
-        }))
     }
 }
 
+/// The error returned to peers that fail to authenticate.
+fn auth_error() -> mrpc::RpcError {
+    mrpc::RpcError::Service(mrpc::ServiceError {
+        name: "NviAuthError".to_string(),
+        value: Value::from("authentication failed"),
+    })
+}
+
 /// A wrapper service that translates from mrpc to NviPlugin.
 #[async_trait::async_trait]
 impl<T> mrpc::Connection for RpcConnection<T>
@@ -271,10 +544,18 @@ where
     T: NviPlugin,
 {
     async fn connected(&self, sender: mrpc::RpcSender) -> mrpc::Result<()> {
+        // Returning an error closes the connection before the plugin sees it.
+        if self.auth.is_some() && !self.wait_authenticated().await {
+            warn!("closing unauthenticated connection {}", self.id);
+            return Err(auth_error());
+        }
         *self.status.lock().unwrap() = Status::Connected;
-        let nv = nvim::NvimApi {
-            rpc_sender: sender.clone(),
-        };
+        let mut nv = nvim::NvimApi::new(sender.clone());
+        nv.api_level = self.api_level.clone();
+        match nv.detect_api_level().await {
+            Ok(level) => trace!("connected to Neovim API level {level}"),
+            Err(e) => warn!("error detecting API level: {:?}", e),
+        }
         let mut plugin = self.plugin.write().await;
         let ci = nv.get_chan_info(0).await.map_err(|e| {
             warn!("error getting channel info: {:?}", e);
@@ -284,6 +565,7 @@ where
             })
         })?;
         self.channel_id.lock().unwrap().replace(ci.id);
+        self.watch_close();
 
         let mut client = self.make_client(&plugin.name(), sender);
         match plugin.bootstrap(&mut client).await {
@@ -294,6 +576,10 @@ where
                 return Ok(());
             }
         }
+        self.peers.insert(client.clone());
+        if let Err(e) = logging::attach(&client, self.closed.subscribe()).await {
+            warn!("error attaching log forwarder: {:?}", e);
+        }
         let ret = plugin.connected(&mut client).await;
         match ret {
             Ok(_) => trace!("connected() completed"),
@@ -312,8 +598,37 @@ where
         method: &str,
         params: Vec<Value>,
     ) -> mrpc::Result<Value> {
+        if self.needs_auth() {
+            return self.authenticate(method, &params);
+        }
+        if method == auth::AUTH_MESSAGE {
+            // Already authenticated, or no token required.
+            return Ok(Value::Boolean(true));
+        }
         if method == STATUS_MESSAGE {
-            return Ok(self.status.lock().unwrap().to_string().into());
+            let report = StatusReport {
+                status: *self.status.lock().unwrap(),
+                panics: self.panics.load(Ordering::Relaxed),
+                plugins: self.hosted.report(),
+            };
+            return serde_rmpv::to_value(&report).map_err(|e| {
+                mrpc::RpcError::Service(mrpc::ServiceError {
+                    name: "NviServiceError".to_string(),
+                    value: Value::String(format!("{e:?}").into()),
+                })
+            });
+        }
+        // A plugin's own method of the same name takes precedence over the built-in.
+        if method == SET_LOG_LEVEL && !self.methods.contains_key(method) {
+            let level = params.first().and_then(Value::as_str).unwrap_or_default();
+            return match logging::set_level(level) {
+                Ok(()) => Ok(Value::Nil),
+                Err(e) => {
+                    let name = self.plugin.read().await.name();
+                    let e = PluginError::value(&name, method, e);
+                    self.handle_request_error(method, e, sender).await
+                }
+            };
         }
 
         debug!("recv request: {:?}", method);
@@ -323,16 +638,22 @@ where
         let result = if is_mut {
             let mut plugin = self.plugin.write().await;
             let mut client = self.make_client(&plugin.name(), sender.clone());
-            plugin.request_mut(&mut client, method, &params).await
+            panics::catch(plugin.request_mut(&mut client, method, &params)).await
         } else {
             let plugin = self.plugin.read().await;
             let mut client = self.make_client(&plugin.name(), sender.clone());
-            plugin.request(&mut client, method, &params).await
+            panics::catch(plugin.request(&mut client, method, &params)).await
         };
 
         match result {
-            Ok(v) => Ok(v),
-            Err(e) => self.handle_request_error(method, e, sender).await,
+            Ok(Ok(v)) => Ok(v),
+            Ok(Err(e)) => self.handle_request_error(method, e, sender).await,
+            Err(p) => {
+                let e = self.handle_panic(method, &p);
+                let name = self.plugin.read().await.name();
+                let e = PluginError::value(&name, method, e);
+                self.handle_request_error(method, e, sender).await
+            }
         }
     }
 
@@ -342,23 +663,41 @@ where
         method: &str,
         params: Vec<Value>,
     ) -> mrpc::Result<()> {
+        if self.needs_auth() {
+            return self.authenticate(method, &params).map(|_| ());
+        }
         debug!("recv notification: {:?}", method);
         trace!("recv notification data: {:?} {:?}", method, params);
 
+        if !self.methods.contains_key(method) && self.subscriptions.publish(method, &params) {
+            return Ok(());
+        }
+
         let is_mut = self.methods.get(method).copied().unwrap_or(false);
-        let result = if is_mut {
+        let result = if method == ERROR_EVENT {
+            let plugin = self.plugin.read().await;
+            let mut client = self.make_client(&plugin.name(), sender.clone());
+            match serde_rmpv::from_value(&Value::Array(params)) {
+                Ok(error) => panics::catch(plugin.error_event(&mut client, error)).await,
+                Err(e) => Ok(Err(e.into())),
+            }
+        } else if is_mut {
             let mut plugin = self.plugin.write().await;
             let mut client = self.make_client(&plugin.name(), sender.clone());
-            plugin.notify_mut(&mut client, method, &params).await
+            panics::catch(plugin.notify_mut(&mut client, method, &params)).await
         } else {
             let plugin = self.plugin.read().await;
             let mut client = self.make_client(&plugin.name(), sender.clone());
-            plugin.notify(&mut client, method, &params).await
+            panics::catch(plugin.notify(&mut client, method, &params)).await
         };
 
         match result {
-            Ok(()) => Ok(()),
-            Err(e) => self.handle_notification_error(method, e, sender).await,
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => self.handle_notification_error(method, e, sender).await,
+            Err(p) => {
+                let e = self.handle_panic(method, &p);
+                self.handle_notification_error(method, e, sender).await
+            }
         }
     }
 }
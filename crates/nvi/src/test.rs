@@ -15,6 +15,7 @@ use tracing_subscriber::util::SubscriberInitExt;
 use crate::{
     Client, NviPlugin,
     connect::connect_unix,
+    demo::Demos,
     error::{Error, Result},
     process::start_nvim_headless,
 };
@@ -333,6 +334,7 @@ pub async fn wait_for_path(path: &Path) -> Result<()> {
     }
     Err(Error::IO {
         msg: "socket never appeared".to_string(),
+        source: None,
     })
 }
 
@@ -363,3 +365,26 @@ where
     nv.await.unwrap()?;
     Ok(())
 }
+
+/// Run every registered demo against a headless Neovim instance, using a fresh plugin from
+/// `make_plugin` for each. All demos are run, and an error naming each failing demo is returned if
+/// any of them fail. This lets a plugin's demos double as regression tests.
+pub async fn run_demos<T, F>(demos: &Demos, make_plugin: F) -> Result<()>
+where
+    T: NviPlugin + Unpin + Sync + 'static,
+    F: Fn() -> T,
+{
+    let mut failures = vec![];
+    for name in demos.list() {
+        if let Err(e) = demos.run_headless(&name, make_plugin()).await {
+            failures.push(format!("{name}: {e}"));
+        }
+    }
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::Internal {
+            msg: format!("demos failed: {}", failures.join("; ")),
+        })
+    }
+}
@@ -247,7 +247,13 @@ impl PaneBuilder {
         // Set the buffer content
         client
             .nvim
-            .buf_set_lines(&buffer, 0, -1, true, content.lines.clone())
+            .buf_set_lines(
+                &buffer,
+                0,
+                -1,
+                true,
+                content.lines.iter().map(|l| l.as_str().into()).collect(),
+            )
             .await?;
 
         let mut conf = self.window_conf.unwrap_or_default();
@@ -281,14 +287,8 @@ impl PaneBuilder {
             conf = conf.relative(types::Relative::Editor);
 
             // Get the editor dimensions using &o_columns and &o_lines
-            let editor_width: u64 = client
-                .nvim
-                .get_option_value("columns", Default::default())
-                .await?;
-            let editor_height: u64 = client
-                .nvim
-                .get_option_value("lines", Default::default())
-                .await?;
+            let editor_width = client.options().columns().await? as u64;
+            let editor_height = client.options().lines().await? as u64;
 
             let (row, col) = pos.win_pos((editor_width, editor_height), (width, height), padding);
 
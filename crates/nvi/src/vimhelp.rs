@@ -0,0 +1,651 @@
+//! A parser for the Vim help file format.
+//!
+//! Help files are loosely structured plain text, with a handful of conventions that carry meaning:
+//!
+//! - The first line names the file with a tag, e.g. `*api.txt*  Nvim`.
+//! - Lines of `=` characters separate sections. The line after a separator is the section title,
+//!   usually followed by tags.
+//! - Lines ending with one or more `*tags*` introduce tagged entries, like function definitions.
+//! - Lines ending with ` ~` are headings.
+//! - A line ending with `>` (optionally followed by a language, e.g. `>lua`) starts a code block,
+//!   which runs until a line starting with `<`, or a line that is not indented.
+//! - Lines starting with `•`, `-`, `*` or a number followed by `.` are list items.
+//! - Inline text contains `*tags*`, `|references|`, `` `code` `` and `{arguments}`.
+//!
+//! This module parses help text into a `Document`, which can be queried for tagged entries and
+//! rendered to markdown.
+
+use std::mem;
+
+/// An inline span of text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline {
+    /// Plain text
+    Text(String),
+    /// A tag definition, written `*tag*`
+    Tag(String),
+    /// A reference to a tag, written `|tag|`
+    Link(String),
+    /// Inline code, written `` `code` ``
+    Code(String),
+    /// An argument name, written `{arg}`
+    Arg(String),
+}
+
+/// A block-level element of a help file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Block {
+    /// A heading, marked with a trailing `~`
+    Heading(String),
+    /// A line introducing a tagged entry. The text is the part of the line to the left of the
+    /// tags, e.g. a function signature. It may be empty.
+    Tagged {
+        /// The text to the left of the tags
+        text: String,
+        /// The tags on the line, without the surrounding `*`
+        tags: Vec<String>,
+    },
+    /// A paragraph of text
+    Paragraph(Vec<Inline>),
+    /// A list, with one entry per item
+    List(Vec<Vec<Inline>>),
+    /// A code block, with its common indentation removed
+    Code {
+        /// The language of the block, if specified
+        lang: Option<String>,
+        /// The code
+        code: String,
+    },
+}
+
+/// A section of a help file, delimited by a line of `=` characters.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Section {
+    /// The section title. This is empty for the text before the first separator.
+    pub title: String,
+    /// The tags on the title line
+    pub tags: Vec<String>,
+    /// The contents of the section
+    pub blocks: Vec<Block>,
+}
+
+/// A tagged entry in a help file - the tagged line, and the blocks up to the next tagged line or
+/// the end of the section.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// The text to the left of the tags, e.g. a function signature
+    pub text: &'a str,
+    /// The tags on the entry line
+    pub tags: &'a [String],
+    /// The body of the entry
+    pub blocks: &'a [Block],
+}
+
+/// A parsed help file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Document {
+    /// The tag naming the file, e.g. `api.txt`, if the file has a header line
+    pub name: Option<String>,
+    /// The text following the name on the header line
+    pub description: String,
+    /// The sections of the file
+    pub sections: Vec<Section>,
+}
+
+impl Document {
+    /// Find the entry for a tag, e.g. `nvim_buf_attach()`.
+    pub fn entry(&self, tag: &str) -> Option<Entry<'_>> {
+        for s in &self.sections {
+            for (i, b) in s.blocks.iter().enumerate() {
+                if let Block::Tagged { text, tags } = b
+                    && tags.iter().any(|t| t == tag)
+                {
+                    let rest = &s.blocks[i + 1..];
+                    let end = rest
+                        .iter()
+                        .position(|b| matches!(b, Block::Tagged { .. }))
+                        .unwrap_or(rest.len());
+                    return Some(Entry {
+                        text,
+                        tags,
+                        blocks: &rest[..end],
+                    });
+                }
+            }
+        }
+        None
+    }
+
+    /// Render the document as markdown.
+    pub fn to_markdown(&self) -> String {
+        let mut parts = vec![];
+        if let Some(name) = &self.name {
+            parts.push(format!("# {name}"));
+        }
+        for s in &self.sections {
+            if !s.title.is_empty() {
+                parts.push(format!("## {}", s.title));
+            }
+            let body = render_markdown(&s.blocks);
+            if !body.is_empty() {
+                parts.push(body);
+            }
+        }
+        parts.join("\n\n")
+    }
+}
+
+/// Render a sequence of blocks as markdown.
+pub fn render_markdown(blocks: &[Block]) -> String {
+    blocks
+        .iter()
+        .map(|b| match b {
+            Block::Heading(text) => format!("**{text}**"),
+            Block::Tagged { text, tags } => {
+                if text.is_empty() {
+                    format!("### {}", tags.join(", "))
+                } else {
+                    format!("### `{text}`")
+                }
+            }
+            Block::Paragraph(inlines) => inlines_to_markdown(inlines),
+            Block::List(items) => items
+                .iter()
+                .map(|i| format!("* {}", inlines_to_markdown(i)))
+                .collect::<Vec<_>>()
+                .join("\n"),
+            Block::Code { lang, code } => {
+                format!("```{}\n{code}\n```", lang.as_deref().unwrap_or("text"))
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n\n")
+}
+
+/// Render inline spans as markdown. Tag definitions are dropped, since they have no markdown
+/// equivalent.
+fn inlines_to_markdown(inlines: &[Inline]) -> String {
+    let mut ret = String::new();
+    for i in inlines {
+        match i {
+            Inline::Text(t) => ret.push_str(t),
+            Inline::Tag(_) => {}
+            Inline::Link(t) | Inline::Code(t) | Inline::Arg(t) => {
+                ret.push('`');
+                ret.push_str(t);
+                ret.push('`');
+            }
+        }
+    }
+    ret.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Parse inline markup in a line of text.
+pub fn parse_inline(text: &str) -> Vec<Inline> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut ret = vec![];
+    let mut buf = String::new();
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some((span, len)) = inline_span(&chars, i) {
+            if !buf.is_empty() {
+                ret.push(Inline::Text(mem::take(&mut buf)));
+            }
+            ret.push(span);
+            i += len;
+        } else {
+            buf.push(chars[i]);
+            i += 1;
+        }
+    }
+    if !buf.is_empty() {
+        ret.push(Inline::Text(buf));
+    }
+    ret
+}
+
+/// Try to parse an inline span starting at `start`, returning the span and the number of chars it
+/// covers.
+fn inline_span(chars: &[char], start: usize) -> Option<(Inline, usize)> {
+    let open = chars[start];
+    let close = match open {
+        '*' | '|' | '`' => open,
+        '{' => '}',
+        _ => return None,
+    };
+    let len = chars[start + 1..].iter().position(|c| *c == close)?;
+    if len == 0 {
+        return None;
+    }
+    let content: String = chars[start + 1..start + 1 + len].iter().collect();
+    let end = start + len + 2;
+    let bounded = |i: Option<usize>| {
+        i.and_then(|i| chars.get(i))
+            .is_none_or(|c| c.is_whitespace())
+    };
+    let span = match open {
+        // Tags and references can't contain whitespace, and tags must stand alone, so that
+        // multiplication and globs aren't mistaken for tags.
+        '*' if !content.contains(char::is_whitespace)
+            && bounded(start.checked_sub(1))
+            && bounded(Some(end)) =>
+        {
+            Inline::Tag(content)
+        }
+        '|' if !content.contains(char::is_whitespace) => Inline::Link(content),
+        '`' => Inline::Code(content),
+        '{' if content
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') =>
+        {
+            Inline::Arg(content)
+        }
+        _ => return None,
+    };
+    Some((span, len + 2))
+}
+
+/// Split trailing tags from a line, returning the text to the left and the tags. Returns `None` if
+/// the line doesn't end with a tag.
+fn trailing_tags(line: &str) -> Option<(String, Vec<String>)> {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let mut tags = vec![];
+    while let Some(w) = words.last() {
+        match w.strip_prefix('*').and_then(|w| w.strip_suffix('*')) {
+            Some(t) if !t.is_empty() && !t.contains('*') => {
+                tags.push(t.to_string());
+                words.pop();
+            }
+            _ => break,
+        }
+    }
+    if tags.is_empty() {
+        return None;
+    }
+    tags.reverse();
+    // Keep the original spacing of the text to the left of the tags.
+    let text = if words.is_empty() {
+        String::new()
+    } else {
+        let first_tag = line.find(&format!("*{}*", tags[0]))?;
+        line[..first_tag].trim().to_string()
+    };
+    Some((text, tags))
+}
+
+/// If the line starts a code block, return the text before the marker and the block language.
+fn code_start(line: &str) -> Option<(&str, Option<String>)> {
+    let trimmed = line.trim_end();
+    let marker = trimmed.rfind('>')?;
+    let (before, lang) = (&trimmed[..marker], &trimmed[marker + 1..]);
+    if !before.is_empty() && !before.ends_with(char::is_whitespace) {
+        return None;
+    }
+    if !lang.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    let lang = (!lang.is_empty()).then(|| lang.to_string());
+    Some((before.trim(), lang))
+}
+
+/// If the line is a list item, return the item text.
+fn list_item(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    for marker in ["• ", "- ", "* "] {
+        if let Some(rest) = trimmed.strip_prefix(marker) {
+            return Some(rest);
+        }
+    }
+    let digits = trimmed.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        return trimmed[digits..].strip_prefix(". ");
+    }
+    None
+}
+
+/// Is this line a section separator?
+fn is_separator(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.len() >= 10 && trimmed.chars().all(|c| c == '=')
+}
+
+/// Remove the common leading whitespace from a set of lines.
+fn dedent(lines: &[String]) -> String {
+    let indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+    let lines: Vec<&str> = lines
+        .iter()
+        .map(|l| l.get(indent..).unwrap_or_default().trim_end())
+        .collect();
+    lines.join("\n").trim_matches('\n').to_string()
+}
+
+/// Incremental parser state.
+#[derive(Default)]
+struct Parser {
+    /// The document being built
+    doc: Document,
+    /// The section being built
+    section: Section,
+    /// Lines of the current paragraph
+    para: Vec<String>,
+    /// Items of the current list
+    list: Vec<String>,
+    /// The current code block, if we're in one
+    code: Option<(Option<String>, Vec<String>)>,
+    /// Is the next non-blank line a section title?
+    expect_title: bool,
+}
+
+impl Parser {
+    /// Finish the current paragraph or list.
+    fn flush(&mut self) {
+        if !self.para.is_empty() {
+            let text = self.para.join(" ");
+            self.section
+                .blocks
+                .push(Block::Paragraph(parse_inline(text.trim())));
+            self.para.clear();
+        }
+        if !self.list.is_empty() {
+            let items = self.list.iter().map(|i| parse_inline(i.trim())).collect();
+            self.section.blocks.push(Block::List(items));
+            self.list.clear();
+        }
+    }
+
+    /// Finish the current code block.
+    fn flush_code(&mut self) {
+        if let Some((lang, lines)) = self.code.take() {
+            self.section.blocks.push(Block::Code {
+                lang,
+                code: dedent(&lines),
+            });
+        }
+    }
+
+    /// Process a line, returning false if it was consumed as part of a code block.
+    fn code_line(&mut self, line: &str) -> bool {
+        let Some((_, lines)) = &mut self.code else {
+            return false;
+        };
+        if line.starts_with('<') {
+            self.flush_code();
+            return true;
+        }
+        if !line.is_empty() && !line.starts_with(char::is_whitespace) {
+            self.flush_code();
+            return false;
+        }
+        lines.push(line.to_string());
+        true
+    }
+
+    /// Process a line of the file.
+    fn line(&mut self, line: &str) {
+        if self.code_line(line) {
+            return;
+        }
+        let trimmed = line.trim();
+
+        if trimmed.is_empty() {
+            self.flush();
+            return;
+        }
+        if is_separator(line) {
+            self.flush();
+            self.doc.sections.push(mem::take(&mut self.section));
+            self.expect_title = true;
+            return;
+        }
+        if self.expect_title {
+            self.expect_title = false;
+            let (title, tags) = trailing_tags(line).unwrap_or_else(|| (trimmed.into(), vec![]));
+            self.section.title = title;
+            self.section.tags = tags;
+            return;
+        }
+        if let Some(heading) = trimmed.strip_suffix('~')
+            && (heading.is_empty() || heading.ends_with(' '))
+        {
+            self.flush();
+            self.section
+                .blocks
+                .push(Block::Heading(heading.trim().to_string()));
+            return;
+        }
+        if let Some((text, tags)) = trailing_tags(line) {
+            self.flush();
+            self.section.blocks.push(Block::Tagged { text, tags });
+            return;
+        }
+        // Long signatures are placed on the line after their tags.
+        if !line.starts_with(char::is_whitespace)
+            && self.para.is_empty()
+            && self.list.is_empty()
+            && let Some(Block::Tagged { text, .. }) = self.section.blocks.last_mut()
+            && text.is_empty()
+        {
+            *text = trimmed.to_string();
+            return;
+        }
+        if let Some((before, lang)) = code_start(line) {
+            if !before.is_empty() {
+                self.para.push(before.to_string());
+            }
+            self.flush();
+            self.code = Some((lang, vec![]));
+            return;
+        }
+        if let Some(item) = list_item(line) {
+            if !self.para.is_empty() {
+                self.flush();
+            }
+            self.list.push(item.to_string());
+            return;
+        }
+        if let Some(last) = self.list.last_mut() {
+            last.push(' ');
+            last.push_str(trimmed);
+        } else {
+            self.para.push(trimmed.to_string());
+        }
+    }
+
+    /// Finish parsing and return the document.
+    fn finish(mut self) -> Document {
+        self.flush_code();
+        self.flush();
+        self.doc.sections.push(self.section);
+        self.doc
+            .sections
+            .retain(|s| !s.title.is_empty() || !s.blocks.is_empty());
+        self.doc
+    }
+}
+
+/// Parse the text of a help file.
+pub fn parse(text: &str) -> Document {
+    let mut p = Parser::default();
+    let mut lines = text.lines().peekable();
+
+    // The header line names the file, e.g. "*api.txt*  Nvim".
+    if let Some(first) = lines.peek()
+        && let Some(rest) = first.strip_prefix('*')
+        && let Some((name, description)) = rest.split_once('*')
+    {
+        p.doc.name = Some(name.to_string());
+        p.doc.description = description.trim().to_string();
+        lines.next();
+    }
+
+    for line in lines {
+        // Skip the modeline
+        if line.trim_start().starts_with("vim:") {
+            continue;
+        }
+        p.line(line);
+    }
+    p.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    const API: &str = "\
+*api.txt*               Nvim
+
+
+                 NVIM REFERENCE MANUAL    by Thiago de Arruda
+
+
+Nvim API                                                           *API* *api*
+
+==============================================================================
+Buffer Functions                                                  *api-buffer*
+
+nvim_buf_attach({buffer}, {send_buffer}, {opts})           *nvim_buf_attach()*
+    Activates buffer-update events on a channel, or as Lua callbacks.
+
+    Example (Lua): capture buffer updates in a global `events` variable (use
+    \"vim.print(events)\" to see its contents): >lua
+        events = {}
+        vim.api.nvim_buf_attach(0, false, {
+          on_lines = function(...)
+            table.insert(events, {...})
+          end,
+        })
+<
+
+    See also: ~
+      • |nvim_buf_detach()|
+      • |api-buffer-updates-lua|
+
+    Parameters: ~
+      • {buffer}       Buffer id, or 0 for current buffer
+      • {send_buffer}  True if the initial notification should contain the
+                       whole buffer.
+
+                                              *nvim_buf_get_extmarks_long()*
+nvim_buf_get_extmarks_long({buffer}, {ns_id}, {start}, {end}, {opts})
+    Gets extmarks. See |api-indexing|.
+
+ vim:tw=78:ts=8:sw=4:sts=4:et:ft=help:norl:
+";
+
+    #[test]
+    fn test_parse_inline() {
+        assert_eq!(
+            parse_inline("Use |foo()| with {arg} and `x * y`, see *tag* a*b*c"),
+            vec![
+                Inline::Text("Use ".into()),
+                Inline::Link("foo()".into()),
+                Inline::Text(" with ".into()),
+                Inline::Arg("arg".into()),
+                Inline::Text(" and ".into()),
+                Inline::Code("x * y".into()),
+                Inline::Text(", see ".into()),
+                Inline::Tag("tag".into()),
+                Inline::Text(" a*b*c".into()),
+            ]
+        );
+        assert_eq!(
+            parse_inline("{ not an arg }"),
+            vec![Inline::Text("{ not an arg }".into())]
+        );
+    }
+
+    #[test]
+    fn test_parse_document() {
+        let doc = parse(API);
+        assert_eq!(doc.name.as_deref(), Some("api.txt"));
+        assert_eq!(doc.description, "Nvim");
+        assert_eq!(doc.sections.len(), 2);
+        assert_eq!(
+            doc.sections[0].blocks[1],
+            Block::Tagged {
+                text: "Nvim API".into(),
+                tags: vec!["API".into(), "api".into()],
+            }
+        );
+        assert_eq!(doc.sections[1].title, "Buffer Functions");
+        assert_eq!(doc.sections[1].tags, vec!["api-buffer".to_string()]);
+    }
+
+    #[test]
+    fn test_entry() {
+        let doc = parse(API);
+        let e = doc.entry("nvim_buf_attach()").unwrap();
+        assert_eq!(e.text, "nvim_buf_attach({buffer}, {send_buffer}, {opts})");
+        assert_eq!(
+            e.blocks[0],
+            Block::Paragraph(vec![Inline::Text(
+                "Activates buffer-update events on a channel, or as Lua callbacks.".into()
+            )])
+        );
+        assert_eq!(
+            e.blocks[2],
+            Block::Code {
+                lang: Some("lua".into()),
+                code: "events = {}\nvim.api.nvim_buf_attach(0, false, {\n  on_lines = \
+                       function(...)\n    table.insert(events, {...})\n  end,\n})"
+                    .into(),
+            }
+        );
+        assert_eq!(e.blocks[3], Block::Heading("See also:".into()));
+        assert_eq!(
+            e.blocks[4],
+            Block::List(vec![
+                vec![Inline::Link("nvim_buf_detach()".into())],
+                vec![Inline::Link("api-buffer-updates-lua".into())],
+            ])
+        );
+        let Block::List(params) = &e.blocks[6] else {
+            panic!("expected a list: {:?}", e.blocks[6]);
+        };
+        assert_eq!(params.len(), 2);
+        assert_eq!(
+            params[1].last(),
+            Some(&Inline::Text(
+                "  True if the initial notification should contain the whole buffer.".into()
+            ))
+        );
+        assert_eq!(e.blocks.len(), 7);
+
+        let e = doc.entry("nvim_buf_get_extmarks_long()").unwrap();
+        assert_eq!(
+            e.text,
+            "nvim_buf_get_extmarks_long({buffer}, {ns_id}, {start}, {end}, {opts})"
+        );
+        assert_eq!(e.blocks.len(), 1);
+
+        assert!(doc.entry("nvim_nonexistent()").is_none());
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let doc = parse(API);
+        let e = doc.entry("nvim_buf_attach()").unwrap();
+        let md = render_markdown(&e.blocks[..3]);
+        assert_eq!(
+            md,
+            "Activates buffer-update events on a channel, or as Lua callbacks.\n\n\
+             Example (Lua): capture buffer updates in a global `events` variable (use \
+             \"vim.print(events)\" to see its contents):\n\n\
+             ```lua\nevents = {}\nvim.api.nvim_buf_attach(0, false, {\n  on_lines = \
+             function(...)\n    table.insert(events, {...})\n  end,\n})\n```"
+        );
+        let full = doc.to_markdown();
+        assert!(full.starts_with("# api.txt\n\n"));
+        assert!(full.contains("## Buffer Functions"));
+        assert!(full.contains("* `nvim_buf_detach()`"));
+        assert!(full.contains("**Parameters:**"));
+    }
+}
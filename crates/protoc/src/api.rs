@@ -3,12 +3,17 @@
 /// ```sh
 // nvim --api-info | msgpack2json | jq
 /// ```
-use std::{collections::HashMap, fmt, process::Command};
+use std::{
+    collections::HashMap,
+    fmt, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
-use anyhow::Result;
+use anyhow::{Result, bail};
 use regex::Regex;
 use rmp_serde as rmps;
-use serde::de::IntoDeserializer;
+use serde::de::{IntoDeserializer, value};
 use serde_derive::Deserialize;
 
 /// The type of a Neovim API parameter or return value
@@ -40,6 +45,9 @@ pub enum Type {
     LuaRef,
     /// A dictionary (alias)
     Dict,
+    /// A dictionary with the keys of a keyset, e.g. `Dict(create_autocmd)`
+    #[serde(skip)]
+    KeyDict(String),
     /// An object
     Object,
     /// A string
@@ -72,6 +80,7 @@ impl fmt::Display for Type {
             Self::Integer => write!(f, "int"),
             Self::LuaRef => write!(f, "luaRef"),
             Self::Dict => write!(f, "dict"),
+            Self::KeyDict(keyset) => write!(f, "dict({keyset})"),
             Self::Object => write!(f, "object"),
             Self::String => write!(f, "string"),
             Self::Tabpage => write!(f, "tabpage"),
@@ -87,8 +96,11 @@ impl<'de> serde::Deserialize<'de> for Type {
         D: serde::Deserializer<'de>,
     {
         let re = Regex::new(r"^ArrayOf\((\w+)(?:, (\d+))?\)$").unwrap();
+        let keyset = Regex::new(r"^Dict\((\w+)\)$").unwrap();
         let s = String::deserialize(deserializer)?;
-        if let Some(captures) = re.captures(&s) {
+        if let Some(captures) = keyset.captures(&s) {
+            Ok(Self::KeyDict(captures[1].to_string()))
+        } else if let Some(captures) = re.captures(&s) {
             Ok(Self::ArrayOf {
                 typ: Box::new(Self::deserialize(
                     captures.get(1).unwrap().as_str().into_deserializer(),
@@ -101,6 +113,16 @@ impl<'de> serde::Deserialize<'de> for Type {
     }
 }
 
+impl Type {
+    /// Parse a type name as it appears in the API definition, e.g. `ArrayOf(String)` or
+    /// `Dict(create_autocmd)`. Returns `None` for types we don't model, like
+    /// `Union(Integer, String)`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let de: value::StrDeserializer<value::Error> = s.into_deserializer();
+        <Self as serde::Deserialize>::deserialize(de).ok()
+    }
+}
+
 /// An extension type
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct ExtType {
@@ -131,6 +153,32 @@ pub struct Function {
     pub deprecated_since: Option<u32>,
 }
 
+impl Function {
+    /// A human-readable signature for the function, e.g. `nvim_buf_get_name(buffer: buffer) ->
+    /// string`.
+    pub fn signature(&self) -> String {
+        let mut ret = format!(
+            "{}({}) -> {}",
+            self.name,
+            params(&self.parameters),
+            self.return_type
+        );
+        if let Some(d) = self.deprecated_since {
+            ret.push_str(&format!(" [deprecated since {d}]"));
+        }
+        ret
+    }
+}
+
+/// Format a parameter list, e.g. `buffer: buffer, start: int`.
+fn params(parameters: &[Parameter]) -> String {
+    parameters
+        .iter()
+        .map(|p| format!("{}: {}", p.1, p.0))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
 /// A UI event definition
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct UIEvent {
@@ -142,6 +190,34 @@ pub struct UIEvent {
     pub parameters: Vec<Parameter>,
 }
 
+impl UIEvent {
+    /// A human-readable signature for the event, e.g. `mode_change(mode: string, mode_idx: int)`.
+    pub fn signature(&self) -> String {
+        format!("{}({})", self.name, params(&self.parameters))
+    }
+}
+
+impl fmt::Display for ExtType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "id {}", self.id)?;
+        if let Some(prefix) = &self.prefix {
+            write!(f, ", prefix {prefix}")?;
+        }
+        Ok(())
+    }
+}
+
+/// A keyset: the set of keys accepted by a `Dict(...)` options parameter.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct Keyset {
+    /// The name of the keyset, e.g. `create_autocmd`
+    pub name: String,
+    /// The keys, in declaration order
+    pub keys: Vec<String>,
+    /// The type of each key, e.g. `Boolean` or `Union(Integer, String)`
+    pub types: HashMap<String, String>,
+}
+
 /// A version definition
 #[derive(Debug, PartialEq, Deserialize)]
 pub struct Version {
@@ -179,10 +255,96 @@ pub struct Api {
     pub error_types: HashMap<String, ExtType>,
     /// The types in the API
     pub types: HashMap<String, ExtType>,
+    /// The keysets for options parameters. Older Neovim releases don't export these.
+    #[serde(default)]
+    pub keysets: Vec<Keyset>,
 }
 
-/// Get the Neovim API definition
-pub fn get_api() -> Result<Api> {
+/// Get the raw MessagePack API definition from the `nvim` binary on the path.
+fn get_api_info() -> Result<Vec<u8>> {
     let output = Command::new("nvim").arg("--api-info").output()?;
-    Ok(rmps::from_slice(&output.stdout)?)
+    if !output.status.success() {
+        bail!("nvim --api-info failed: {}", output.status);
+    }
+    Ok(output.stdout)
+}
+
+/// Get the Neovim API definition. If a snapshot path is given, the definition is read from the
+/// snapshot, otherwise it's read from the `nvim` binary on the path.
+pub fn get_api(snapshot: Option<&Path>) -> Result<Api> {
+    let data = match snapshot {
+        Some(path) => fs::read(path)?,
+        None => get_api_info()?,
+    };
+    Ok(rmps::from_slice(&data)?)
+}
+
+/// Save a snapshot of the API definition from the `nvim` binary on the path to a directory. The
+/// snapshot is named for the Neovim version, e.g. `nvim-0.10.4.msgpack`. Returns the path of the
+/// snapshot.
+pub fn snapshot(dir: &Path) -> Result<PathBuf> {
+    let data = get_api_info()?;
+    let api: Api = rmps::from_slice(&data)?;
+    let v = &api.version;
+    let mut name = format!("nvim-{}.{}.{}", v.major, v.minor, v.patch);
+    if v.prerelease {
+        name.push_str("-dev");
+    }
+    let path = dir.join(format!("{name}.msgpack"));
+    fs::create_dir_all(dir)?;
+    fs::write(&path, data)?;
+    Ok(path)
+}
+
+/// The directory holding the checked-in API snapshots.
+pub const SNAPSHOT_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/api");
+
+/// Find an API snapshot. An existing path is used as is, otherwise the name is taken to be a
+/// release, e.g. `0.11`, and resolved to the latest checked-in snapshot for it.
+pub fn find_snapshot(name: &Path) -> Result<PathBuf> {
+    if name.exists() {
+        return Ok(name.to_path_buf());
+    }
+    let release = name.to_string_lossy();
+    let prefix = format!("nvim-{release}.");
+    let mut found: Vec<(u64, PathBuf)> = vec![];
+    for entry in fs::read_dir(SNAPSHOT_DIR)? {
+        let path = entry?.path();
+        let Some(file) = path.file_name().and_then(|f| f.to_str()) else {
+            continue;
+        };
+        let patch = file
+            .strip_prefix(&prefix)
+            .and_then(|f| f.strip_suffix(".msgpack"))
+            .map(|f| f.strip_suffix("-dev").unwrap_or(f))
+            .and_then(|f| f.parse().ok());
+        if let Some(patch) = patch {
+            found.push((patch, path));
+        }
+    }
+    match found.into_iter().max_by_key(|(patch, _)| *patch) {
+        Some((_, path)) => Ok(path),
+        None => {
+            bail!("no snapshot for {release} in {SNAPSHOT_DIR} - save one with `protoc snapshot`")
+        }
+    }
+}
+
+/// Get the Neovim runtime directory, which contains the help files.
+pub fn get_runtime() -> Result<PathBuf> {
+    let output = Command::new("nvim")
+        .args([
+            "--clean",
+            "--headless",
+            "-c",
+            "lua io.stdout:write(vim.env.VIMRUNTIME)",
+            "-c",
+            "qa!",
+        ])
+        .output()?;
+    let path = String::from_utf8(output.stdout)?;
+    if path.trim().is_empty() {
+        bail!("could not determine the Neovim runtime directory");
+    }
+    Ok(PathBuf::from(path.trim()))
 }
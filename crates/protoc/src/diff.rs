@@ -0,0 +1,78 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    path::Path,
+};
+
+use anyhow::Result;
+
+use crate::api;
+
+/// Compare two sets of named items, printing additions, removals and changes under a heading.
+/// Each item is described by a string, and an item is considered changed if its description
+/// differs.
+fn diff_items(heading: &str, old: &BTreeMap<String, String>, new: &BTreeMap<String, String>) {
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    let mut lines = vec![];
+    for name in names {
+        match (old.get(name), new.get(name)) {
+            (None, Some(n)) => lines.push(format!("\t+ {n}")),
+            (Some(o), None) => lines.push(format!("\t- {o}")),
+            (Some(o), Some(n)) if o != n => {
+                lines.push(format!("\t~ {n}"));
+                lines.push(format!("\t    was: {o}"));
+            }
+            _ => {}
+        }
+    }
+    if !lines.is_empty() {
+        println!("{heading}:");
+        for l in lines {
+            println!("{l}");
+        }
+    }
+}
+
+/// Describe the functions in an API, keyed by name.
+fn functions(api: &api::Api) -> BTreeMap<String, String> {
+    api.functions
+        .iter()
+        .map(|f| (f.name.clone(), f.signature()))
+        .collect()
+}
+
+/// Describe the UI events in an API, keyed by name.
+fn ui_events(api: &api::Api) -> BTreeMap<String, String> {
+    api.ui_events
+        .iter()
+        .map(|e| (e.name.clone(), e.signature()))
+        .collect()
+}
+
+/// Describe the extension types in an API, keyed by name.
+fn types(types: &HashMap<String, api::ExtType>) -> BTreeMap<String, String> {
+    types
+        .iter()
+        .map(|(name, t)| (name.clone(), format!("{name} ({t})")))
+        .collect()
+}
+
+/// Print the differences between two API snapshots: added (+), removed (-) and changed (~)
+/// functions, UI events and types.
+pub fn diff(old: &Path, new: &Path) -> Result<()> {
+    let old = api::get_api(Some(old))?;
+    let new = api::get_api(Some(new))?;
+    let (ov, nv) = (&old.version, &new.version);
+    println!(
+        "API v{}.{}.{} (level {}) -> v{}.{}.{} (level {})",
+        ov.major, ov.minor, ov.patch, ov.api_level, nv.major, nv.minor, nv.patch, nv.api_level
+    );
+    diff_items("Functions", &functions(&old), &functions(&new));
+    diff_items("UI Events", &ui_events(&old), &ui_events(&new));
+    diff_items("Types", &types(&old.types), &types(&new.types));
+    diff_items(
+        "Error Types",
+        &types(&old.error_types),
+        &types(&new.error_types),
+    );
+    Ok(())
+}
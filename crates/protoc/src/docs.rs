@@ -1094,39 +1094,6 @@ pub const DOCS: &[(&str, &str)] = &[
             Sets the current window.
         ",
     ),
-    (
-        "nvim_set_decoration_provider",
-        "
-            Set or change decoration provider for a |namespace|
-
-            This is a very general purpose interface for having Lua callbacks being
-            triggered during the redraw code.
-
-            The expected usage is to set |extmarks| for the currently redrawn buffer.
-            |nvim_buf_set_extmark()| can be called to add marks on a per-window or
-            per-lines basis. Use the ephemeral key to only use the mark for the
-            current screen redraw (the callback will be called again for the next
-            redraw).
-
-            Note: this function should not be called often. Rather, the callbacks
-            themselves can be used to throttle unneeded callbacks. the on_start
-            callback can return false to disable the provider until the next redraw.
-            Similarly, return false in on_win will skip the on_line calls for
-            that window (but any extmarks set in on_win will still be used). A
-            plugin managing multiple sources of decoration should ideally only set one
-            provider, and merge the sources internally. You can use multiple ns_id
-            for the extmarks set/modified inside the callback anyway.
-
-            Note: doing anything other than setting extmarks is considered
-            experimental. Doing things like changing options are not explicitly
-            forbidden, but is likely to have unexpected consequences (such as 100% CPU
-            consumption). Doing vim.rpcnotify should be OK, but vim.rpcrequest is
-            quite dubious for the moment.
-
-            Note: It is not allowed to remove or update extmarks in on_line
-            callbacks.
-        ",
-    ),
     (
         "nvim_set_hl",
         "
@@ -1,10 +1,12 @@
+use std::path::Path;
+
 use anyhow::Result;
 
 use crate::api;
 
 /// Dumps the Neovim API definition
-pub fn dump(raw: bool) -> Result<()> {
-    let v = api::get_api()?;
+pub fn dump(raw: bool, snapshot: Option<&Path>) -> Result<()> {
+    let v = api::get_api(snapshot)?;
     if raw {
         println!("{v:#?}");
     } else {
@@ -14,26 +16,11 @@ pub fn dump(raw: bool) -> Result<()> {
             if f.deprecated_since.is_some() {
                 continue;
             }
-            print!("\t{}(", f.name);
-            let args = f
-                .parameters
-                .iter()
-                .map(|p| format!("{}: {}", p.1, p.0))
-                .collect::<Vec<String>>()
-                .join(", ");
-            print!("{args})");
-            print!(") -> {}", f.return_type);
-            println!();
+            println!("\t{}", f.signature());
         }
         println!("UI Events:");
         for e in v.ui_events {
-            let params = e
-                .parameters
-                .iter()
-                .map(|p| format!("{}: {}", p.1, p.0))
-                .collect::<Vec<String>>()
-                .join(", ");
-            println!("\t{}({})", e.name, params);
+            println!("\t{}", e.signature());
         }
     };
     Ok(())
@@ -0,0 +1,181 @@
+use std::path::Path;
+
+use anyhow::Result;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::{api, overrides, protoc};
+
+/// The handle types, as (API type name, Rust type, bound type, description).
+const HANDLES: &[(&str, &str, &str, &str)] = &[
+    ("Buffer", "Buffer", "BufferRef", "buffer"),
+    ("Window", "Window", "WindowRef", "window"),
+    ("Tabpage", "TabPage", "TabPageRef", "tabpage"),
+];
+
+/// The method name for a handle function: the function name with the type prefix and any `get_`
+/// stripped, e.g. `nvim_buf_get_lines` becomes `lines`.
+fn method_name(func: &str, prefix: &str) -> String {
+    if let Some((_, m)) = overrides::HANDLE_METHOD_NAMES
+        .iter()
+        .find(|(f, _)| *f == func)
+    {
+        return m.to_string();
+    }
+    let name = &func[prefix.len()..];
+    name.strip_prefix("get_").unwrap_or(name).to_string()
+}
+
+/// Generate a method on the handle type, and the matching method on the bound type.
+fn generate_method(f: &api::Function, prefix: &str) -> Result<(TokenStream, TokenStream)> {
+    let mid = Ident::new(&method_name(&f.name, prefix), Span::call_site());
+    // All functions have the nvim_ prefix, so we strip it.
+    let api_id = Ident::new(&f.name[5..], Span::call_site());
+    let protoc::Signature {
+        generics,
+        where_clause,
+        args,
+        arg_names,
+        ret_type,
+        ..
+    } = protoc::signature(f);
+    // The first argument is the handle itself.
+    let args = &args[1..];
+    let arg_names = &arg_names[1..];
+
+    // Only the summary paragraph - the full docs live on the API function.
+    let mut docs: Vec<String> = protoc::doc_lines(&f.name)
+        .into_iter()
+        .take_while(|l| !l.is_empty())
+        .map(|l| format!(" {l}"))
+        .collect();
+    if !docs.is_empty() {
+        docs.push(String::new());
+    }
+    docs.push(format!(
+        " See [`NvimApi::{api_id}`](super::NvimApi::{api_id})."
+    ));
+    let gates = protoc::generate_gates(f)?;
+
+    Ok((
+        quote! {
+            #(#[doc = #docs])*
+            #gates
+            pub async fn #mid #generics(&self, c: &Client, #(#args),*) -> Result<#ret_type>
+                #where_clause
+            {
+                c.nvim.#api_id(self, #(#arg_names),*).await
+            }
+        },
+        quote! {
+            #(#[doc = #docs])*
+            #gates
+            pub async fn #mid #generics(&self, #(#args),*) -> Result<#ret_type>
+                #where_clause
+            {
+                self.handle.#mid(self.client, #(#arg_names),*).await
+            }
+        },
+    ))
+}
+
+/// Generate the impl block for a handle type, and its bound type.
+fn generate_handle(
+    a: &api::Api,
+    (api_name, rust_name, ref_name, desc): (&str, &str, &str, &str),
+) -> Result<TokenStream> {
+    let Some(prefix) = a.types.get(api_name).and_then(|t| t.prefix.as_deref()) else {
+        return Ok(quote! {});
+    };
+    let typ = api::Type::parse(api_name);
+    let (methods, bound): (Vec<TokenStream>, Vec<TokenStream>) = a
+        .functions
+        .iter()
+        .filter(|f| !overrides::SKIP_FUNCTIONS.contains(&f.name.as_str()))
+        .filter(|f| {
+            f.deprecated_since
+                .is_none_or(|d| d > protoc::BASE_API_LEVEL)
+        })
+        .filter(|f| f.name.starts_with(prefix))
+        .filter(|f| {
+            f.parameters
+                .first()
+                .is_some_and(|p| Some(&p.0) == typ.as_ref())
+        })
+        .map(|f| generate_method(f, prefix))
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .unzip();
+
+    let id = Ident::new(rust_name, Span::call_site());
+    let ref_id = Ident::new(ref_name, Span::call_site());
+    let bind_doc = format!(" Bind this {desc} to a client.");
+    let ref_doc =
+        format!(" A {desc} bound to a client, so that methods don't need the client passed in.");
+    let handle_doc = format!(" The underlying {desc} handle.");
+    Ok(quote! {
+        impl #id {
+            #[doc = #bind_doc]
+            pub fn bind<'c>(&self, c: &'c Client) -> #ref_id<'c> {
+                #ref_id::new(self.clone(), c)
+            }
+
+            #(#methods)*
+        }
+
+        #[doc = #ref_doc]
+        #[derive(Debug, Clone)]
+        pub struct #ref_id<'c> {
+            handle: #id,
+            client: &'c Client,
+        }
+
+        impl<'c> #ref_id<'c> {
+            /// Bind a handle to a client.
+            pub fn new(handle: #id, client: &'c Client) -> Self {
+                Self { handle, client }
+            }
+
+            #[doc = #handle_doc]
+            pub fn handle(&self) -> &#id {
+                &self.handle
+            }
+
+            /// The client this handle is bound to.
+            pub fn client(&self) -> &'c Client {
+                self.client
+            }
+
+            #(#bound)*
+        }
+    })
+}
+
+/// Write the generated handle methods to stdout.
+pub fn handles(snapshot: Option<&Path>) -> Result<()> {
+    let a = api::get_api(snapshot)?;
+    let handles = HANDLES
+        .iter()
+        .map(|h| generate_handle(&a, *h))
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    let toks = quote! {
+        //! Methods on the `Buffer`, `Window` and `TabPage` handles, and bound handles that carry
+        //! their client. Generated from the `nvim_buf_`, `nvim_win_` and `nvim_tabpage_` functions
+        //! in the API definition by `protoc handles`.
+        #![allow(clippy::doc_lazy_continuation)]
+        #![allow(deprecated)]
+        #![allow(clippy::multiple_inherent_impl)]
+        use std::collections::HashMap;
+
+        use mrpc::Value;
+        use serde::Serialize;
+
+        use super::{opts, types::*};
+        use crate::{client::Client, error::Result};
+
+        #(#handles)*
+    };
+    print!("{}", protoc::format_with_prettyplease(toks));
+    Ok(())
+}
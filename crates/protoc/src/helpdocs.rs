@@ -0,0 +1,102 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+use anyhow::Result;
+use nvi::vimhelp::{self, Block};
+
+use crate::{api, overrides, protoc::BASE_API_LEVEL};
+
+/// Extract the description of an API function from the parsed API help file, rendered as
+/// markdown. This is the body of the function's entry up to the first heading, which skips the
+/// "Parameters", "Return" and "Attributes" sections that we express in the Rust signature.
+pub fn function_docs(doc: &vimhelp::Document, name: &str) -> Option<String> {
+    let entry = doc.entry(&format!("{name}()"))?;
+    let end = entry
+        .blocks
+        .iter()
+        .position(|b| matches!(b, Block::Heading(_)))
+        .unwrap_or(entry.blocks.len());
+    let md = vimhelp::render_markdown(&entry.blocks[..end]);
+    (!md.is_empty()).then_some(md)
+}
+
+/// Extract the description of a keyset key from the help entry of a function that takes the
+/// keyset. Keys are documented as list items under the function's parameters, like
+/// `• fg: color name or "#RRGGBB"`, sometimes with a type before the colon, like
+/// `• group (string|integer) optional: ...`.
+pub fn key_docs(doc: &vimhelp::Document, func: &str, key: &str) -> Option<String> {
+    let entry = doc.entry(&format!("{func}()"))?;
+    entry
+        .blocks
+        .iter()
+        .filter_map(|b| match b {
+            Block::List(items) => Some(items),
+            _ => None,
+        })
+        .flatten()
+        .find_map(|item| {
+            let md = vimhelp::render_markdown(&[Block::List(vec![item.clone()])]);
+            let text = md.strip_prefix("* ")?;
+            let rest = text
+                .strip_prefix(key)
+                .or_else(|| text.strip_prefix(&format!("`{key}`")))
+                .filter(|r| r.starts_with([' ', ':', '(']))?
+                .trim_start();
+            let rest = match rest.strip_prefix('(') {
+                Some(r) => r.split_once(')')?.1,
+                None => rest,
+            };
+            let (before, desc) = rest.split_once(':')?;
+            // Allow qualifiers like "optional" between the key and the colon, but not prose.
+            (before.split_whitespace().count() <= 1 && !desc.trim().is_empty())
+                .then(|| desc.trim().to_string())
+        })
+}
+
+/// Read and parse Neovim's `api.txt` help file. If no path is given, the help file is found in
+/// the Neovim runtime directory.
+pub fn api_help(path: Option<PathBuf>) -> Result<vimhelp::Document> {
+    let path = match path {
+        Some(p) => p,
+        None => api::get_runtime()?.join("doc").join("api.txt"),
+    };
+    Ok(vimhelp::parse(&fs::read_to_string(&path)?))
+}
+
+/// Format a documentation table in the style of `docs.rs`.
+fn format_table(docs: &[(String, String)]) -> String {
+    let mut ret = String::from(
+        "/// Documentation for Neovim API functions\npub const DOCS: &[(&str, &str)] = &[\n",
+    );
+    for (name, doc) in docs {
+        let doc = doc.replace('\\', "\\\\").replace('"', "\\\"");
+        ret.push_str(&format!("    (\n        \"{name}\",\n        \"\n"));
+        for line in doc.lines() {
+            if line.is_empty() {
+                ret.push('\n');
+            } else {
+                ret.push_str(&format!("        {line}\n"));
+            }
+        }
+        ret.push_str("        \",\n    ),\n");
+    }
+    ret.push_str("];\n");
+    ret
+}
+
+/// Regenerate the API documentation table from Neovim's `api.txt` help file, and print it to
+/// stdout. If no path is given, the help file is found in the Neovim runtime directory.
+pub fn helpdocs(path: Option<PathBuf>, snapshot: Option<&Path>) -> Result<()> {
+    let doc = api_help(path)?;
+    let docs: Vec<(String, String)> = api::get_api(snapshot)?
+        .functions
+        .into_iter()
+        .filter(|f| !overrides::SKIP_FUNCTIONS.contains(&f.name.as_str()))
+        .filter(|f| f.deprecated_since.is_none_or(|d| d > BASE_API_LEVEL))
+        .filter_map(|f| function_docs(&doc, &f.name).map(|d| (f.name, d)))
+        .collect();
+    print!("{}", format_table(&docs));
+    Ok(())
+}
@@ -0,0 +1,185 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{Result, bail};
+use nvi::vimhelp;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::{
+    api, helpdocs, overrides,
+    protoc::{self, BASE_API_LEVEL},
+};
+
+/// The struct name for a keyset, e.g. `CreateAutocmd` for `create_autocmd`.
+pub fn struct_name(keyset: &str) -> String {
+    if let Some((_, n)) = overrides::KEYSET_NAMES.iter().find(|(k, _)| *k == keyset) {
+        return n.to_string();
+    }
+    keyset
+        .split('_')
+        .map(|w| {
+            let mut c = w.chars();
+            c.next()
+                .map(|f| f.to_uppercase().chain(c).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// Make the type of a keyset field. Returns `None` for types that can't be sent over RPC.
+fn field_type(t: &api::Type) -> Option<TokenStream> {
+    Some(match t {
+        api::Type::Array => quote! { Vec<Value> },
+        api::Type::ArrayOf { typ, .. } => {
+            let typ = field_type(typ)?;
+            quote! { Vec<#typ> }
+        }
+        api::Type::Boolean => quote! { bool },
+        api::Type::Buffer => quote! { types::Buffer },
+        api::Type::Dict | api::Type::Dictionary => quote! { HashMap<String, Value> },
+        api::Type::Float => quote! { f64 },
+        api::Type::Integer => quote! { i64 },
+        api::Type::Object => quote! { Value },
+        api::Type::String => quote! { String },
+        api::Type::Tabpage => quote! { types::TabPage },
+        api::Type::Window => quote! { types::Window },
+        api::Type::Function | api::Type::LuaRef | api::Type::Void => return None,
+    })
+}
+
+/// The keysets taken by the generated API functions, mapped to the functions that take them. The
+/// API definition types these parameters as `Dict(<keyset>)`.
+fn keyset_params(a: &api::Api) -> BTreeMap<&str, Vec<&str>> {
+    let mut ret: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+    let funcs = a
+        .functions
+        .iter()
+        .filter(|f| !overrides::SKIP_FUNCTIONS.contains(&f.name.as_str()))
+        .filter(|f| f.deprecated_since.is_none_or(|d| d > BASE_API_LEVEL));
+    for f in funcs {
+        for p in &f.parameters {
+            if let api::Type::KeyDict(k) = &p.0 {
+                let names = ret.entry(k.as_str()).or_default();
+                if !names.contains(&f.name.as_str()) {
+                    names.push(&f.name);
+                }
+            }
+        }
+    }
+    ret
+}
+
+/// Documentation for a keyset key, from the help for the first function taking the keyset that
+/// documents it. Falls back to naming the key if none do.
+fn key_doc(doc: &vimhelp::Document, funcs: &[&str], key: &str) -> String {
+    funcs
+        .iter()
+        .find_map(|f| helpdocs::key_docs(doc, f, key))
+        .map(|d| format!(" {d}"))
+        .unwrap_or_else(|| format!(" The `{key}` option"))
+}
+
+/// Generate the options struct for a keyset, taken by `funcs`. Keys holding Lua references are
+/// skipped, since they can't be passed over RPC, as are private keys with a leading underscore.
+fn generate_keyset(k: &api::Keyset, funcs: &[&str], doc: &vimhelp::Document) -> TokenStream {
+    let id = Ident::new(&struct_name(&k.name), Span::call_site());
+    let funcs: Vec<String> = funcs.iter().map(|f| format!("`{f}`")).collect();
+    let doc = format!(" Options for {}", funcs.join(", "));
+
+    let mut fields = vec![];
+    let mut eq = true;
+    for key in k.keys.iter().filter(|k| !k.starts_with('_')) {
+        let typ = if let Some(t) = overrides::get_keyset_type(&k.name, key) {
+            t
+        } else {
+            let raw = k.types.get(key).map(String::as_str).unwrap_or("Object");
+            match raw {
+                // Highlight groups are passed by name
+                "HLGroupID" => quote! { String },
+                "LuaRef" => continue,
+                _ => match api::Type::parse(raw) {
+                    Some(t) => match field_type(&t) {
+                        Some(t) => t,
+                        None => continue,
+                    },
+                    None => quote! { Value },
+                },
+            }
+        };
+        // Values and floats aren't `Eq`, so only derive it for structs without them.
+        let words = typ.to_string();
+        if words
+            .split(|c: char| !c.is_alphanumeric())
+            .any(|w| w == "Value" || w == "f64")
+        {
+            eq = false;
+        }
+        let name = protoc::clean_name(key);
+        let fid = Ident::new(&name, Span::call_site());
+        let fdoc = key_doc(doc, funcs, key);
+        let rename = (name != *key).then(|| quote! { #[serde(rename = #key)] });
+        fields.push(quote! {
+            #[doc = #fdoc]
+            #rename
+            #[serde(skip_serializing_if = "Option::is_none")]
+            pub #fid: Option<#typ>,
+        });
+    }
+
+    let eq = eq.then(|| quote! { Eq, });
+
+    quote! {
+        #[doc = #doc]
+        #[derive(Debug, Clone, Default, PartialEq, #eq Serialize, Deserialize, Setters)]
+        #[setters(strip_option)]
+        pub struct #id {
+            #(#fields)*
+        }
+    }
+}
+
+/// Write the generated options structs to stdout, one for each keyset used by an API function.
+/// Field docs are taken from Neovim's `api.txt` help file, found in the runtime directory if no
+/// path is given.
+pub fn opts(path: Option<PathBuf>, snapshot: Option<&Path>) -> Result<()> {
+    let a = api::get_api(snapshot)?;
+    let doc = helpdocs::api_help(path)?;
+    let mut structs = vec![];
+    for (name, funcs) in keyset_params(&a) {
+        let Some(k) = a.keysets.iter().find(|k| k.name == name) else {
+            bail!("keyset {name} is not in the API definition");
+        };
+        structs.push(generate_keyset(k, &funcs, &doc));
+    }
+    let body = quote! { #(#structs)* };
+
+    // Only import what the generated fields use, to keep the output free of warnings.
+    let used = body.to_string();
+    let mut imports = vec![];
+    if used.contains("HashMap") {
+        imports.push(quote! { use std::collections::HashMap; });
+    }
+    imports.push(quote! {
+        use derive_setters::Setters;
+    });
+    if used.contains("Value") {
+        imports.push(quote! { use mrpc::Value; });
+    }
+    imports.push(quote! { use serde_derive::{Deserialize, Serialize}; });
+    if used.contains("types ::") {
+        imports.push(quote! { use super::types; });
+    }
+
+    let toks = quote! {
+        //! Options structs for the `Dict(...)` parameters of the generated API. These are
+        //! generated from the keysets in the API definition by `protoc opts`.
+        #(#imports)*
+
+        #body
+    };
+    print!("{}", protoc::format_with_prettyplease(toks));
+    Ok(())
+}
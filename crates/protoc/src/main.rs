@@ -1,17 +1,33 @@
 //! The protoc crate contains the protocol compiler for nvi.
+use std::path::PathBuf;
+
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 /// API types
 mod api;
+/// Compare API snapshots
+mod diff;
 /// Documentation for API functions
 mod docs;
 /// Dump API definitions
 mod dump;
+/// Generate methods on the handle types
+mod handles;
+/// Regenerate API documentation from Neovim's help files
+mod helpdocs;
+/// Generate options structs from API keysets
+mod keysets;
+/// Generate the typed option catalogue
+mod options;
 /// Overrides for API generation
 mod overrides;
 /// Protocol compiler
 mod protoc;
+/// Generate typed UI events
+mod redraw;
+/// Generate Vimscript function bindings from LuaCATS annotations
+mod vimfn;
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
@@ -22,6 +38,11 @@ struct Cli {
     #[command(subcommand)]
     /// The command to execute
     command: Option<Commands>,
+
+    #[arg(long, global = true)]
+    /// Read the API definition from a snapshot saved with `snapshot`, rather than from the nvim
+    /// binary on the path. Takes a path, or a release like `0.11` to use the checked-in snapshot.
+    api_info: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
@@ -35,15 +56,76 @@ enum Commands {
     },
     /// Generate the Rust protocol definitions
     Protoc {},
+    /// Generate the options structs for keyset parameters
+    Opts {
+        #[arg(long)]
+        /// Path to api.txt, for the field docs. Defaults to the file in the Neovim runtime
+        /// directory.
+        path: Option<PathBuf>,
+    },
+    /// Generate the typed UI events sent in redraw notifications
+    Redraw {},
+    /// Generate methods on the Buffer, Window and TabPage handle types
+    Handles {},
+    /// Save a snapshot of the API definition from the nvim binary on the path
+    Snapshot {
+        #[arg(default_value = "crates/protoc/api")]
+        /// The directory to save the snapshot in
+        dir: PathBuf,
+    },
+    /// Report added, removed and changed functions and types between two API snapshots
+    Diff {
+        /// The old snapshot, as a path or a release like `0.10`
+        old: PathBuf,
+        /// The new snapshot, as a path or a release like `0.11`
+        new: PathBuf,
+    },
+    /// Generate typed bindings for Vimscript builtin functions from the LuaCATS meta file
+    Vimfn {
+        #[arg(long)]
+        /// Path to vimfn.lua. Defaults to the file in the Neovim runtime directory.
+        path: Option<PathBuf>,
+    },
+    /// Generate the typed option catalogue from nvim_get_all_options_info
+    Options {
+        #[arg(long)]
+        /// Path to a JSON dump of nvim_get_all_options_info. Defaults to querying nvim.
+        path: Option<PathBuf>,
+    },
+    /// Regenerate the API documentation table from Neovim's api.txt help file
+    Docs {
+        #[arg(long)]
+        /// Path to api.txt. Defaults to the file in the Neovim runtime directory.
+        path: Option<PathBuf>,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    let snapshot = cli
+        .api_info
+        .as_deref()
+        .map(api::find_snapshot)
+        .transpose()?;
+    let snapshot = snapshot.as_deref();
     match &cli.command {
-        Some(Commands::Dump { raw }) => dump::dump(*raw)?,
-        Some(Commands::Protoc {}) => protoc::protoc()?,
+        Some(Commands::Dump { raw }) => dump::dump(*raw, snapshot)?,
+        Some(Commands::Protoc {}) => protoc::protoc(snapshot)?,
+        Some(Commands::Opts { path }) => keysets::opts(path.clone(), snapshot)?,
+        Some(Commands::Redraw {}) => redraw::redraw(snapshot)?,
+        Some(Commands::Handles {}) => handles::handles(snapshot)?,
+        Some(Commands::Snapshot { dir }) => {
+            let path = api::snapshot(dir)?;
+            println!("Wrote {}", path.display());
+        }
+        Some(Commands::Diff { old, new }) => {
+            diff::diff(&api::find_snapshot(old)?, &api::find_snapshot(new)?)?
+        }
+        Some(Commands::Vimfn { path }) => vimfn::vimfn(path.clone())?,
+        Some(Commands::Options { path }) => options::options(path.clone())?,
+        Some(Commands::Docs { path }) => helpdocs::helpdocs(path.clone(), snapshot)?,
         None => {
             unreachable!()
         }
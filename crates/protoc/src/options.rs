@@ -0,0 +1,393 @@
+use std::{collections::BTreeMap, fs, path::PathBuf, process::Command};
+
+use anyhow::{Result, bail};
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+use serde_derive::Deserialize;
+
+use crate::{overrides, protoc};
+
+/// Option metadata, as returned by `nvim_get_all_options_info`.
+#[derive(Debug, Deserialize)]
+pub struct OptionInfo {
+    /// The full option name
+    pub name: String,
+    /// The abbreviated name, or an empty string
+    pub shortname: String,
+    /// The value type: "boolean", "number" or "string"
+    #[serde(rename = "type")]
+    pub typ: String,
+    /// The default value
+    pub default: serde_json::Value,
+    /// The scope: "global", "win" or "buf"
+    pub scope: String,
+    /// Whether the option is global-local
+    pub global_local: bool,
+}
+
+/// Get the option metadata from the `nvim` binary on the path.
+fn get_options_info() -> Result<Vec<u8>> {
+    let output = Command::new("nvim")
+        .args([
+            "--clean",
+            "--headless",
+            "-c",
+            "lua io.stdout:write(vim.json.encode(vim.api.nvim_get_all_options_info()))",
+            "-c",
+            "qa!",
+        ])
+        .output()?;
+    if !output.status.success() || output.stdout.is_empty() {
+        bail!("could not get option info from nvim: {}", output.status);
+    }
+    Ok(output.stdout)
+}
+
+/// Convert an option name to a constant name, e.g. `modifiable` to `Modifiable`.
+fn const_name(name: &str) -> String {
+    let mut c = name.chars();
+    c.next()
+        .map(|f| f.to_uppercase().chain(c).collect())
+        .unwrap_or_default()
+}
+
+/// Generate the catalogue entry, constant and, for global and global-local options, accessor for an
+/// option.
+fn generate_option(o: &OptionInfo) -> Result<(TokenStream, TokenStream, Option<TokenStream>)> {
+    let name = &o.name;
+    let shortname = &o.shortname;
+    let global_local = o.global_local;
+    let (typ, val_type, default) = match (o.typ.as_str(), &o.default) {
+        ("boolean", serde_json::Value::Bool(b)) => (
+            quote! { OptType::Boolean },
+            quote! { bool },
+            quote! { OptValue::Boolean(#b) },
+        ),
+        ("number", serde_json::Value::Number(n)) => {
+            let Some(n) = n.as_i64() else {
+                bail!("option {name} has a non-integer default: {n}");
+            };
+            (
+                quote! { OptType::Number },
+                quote! { i64 },
+                quote! { OptValue::Number(#n) },
+            )
+        }
+        ("string", serde_json::Value::String(s)) => (
+            quote! { OptType::String },
+            quote! { String },
+            quote! { OptValue::String(#s) },
+        ),
+        (t, d) => bail!("option {name} has unsupported type {t} with default {d}"),
+    };
+    let (scope, opt_type) = match (o.scope.as_str(), global_local) {
+        ("global", _) => (quote! { OptScope::Global }, quote! { GlobalOpt }),
+        ("win", false) => (quote! { OptScope::Win }, quote! { WinOpt }),
+        ("win", true) => (quote! { OptScope::Win }, quote! { GlobalLocalWinOpt }),
+        ("buf", false) => (quote! { OptScope::Buf }, quote! { BufOpt }),
+        ("buf", true) => (quote! { OptScope::Buf }, quote! { GlobalLocalBufOpt }),
+        (s, _) => bail!("option {name} has unknown scope {s}"),
+    };
+
+    let entry = quote! {
+        OptInfo {
+            name: #name,
+            shortname: #shortname,
+            typ: #typ,
+            scope: #scope,
+            global_local: #global_local,
+            default: #default,
+        },
+    };
+
+    let cid = Ident::new(&const_name(name), Span::call_site());
+    let short = if shortname.is_empty() {
+        String::new()
+    } else {
+        format!(" (`'{shortname}'`)")
+    };
+    let scope_desc = if global_local {
+        format!("global-local {}", o.scope)
+    } else {
+        o.scope.clone()
+    };
+    let doc = format!(
+        " `'{name}'`{short}: a {scope_desc} {} option. Default: `{}`.",
+        o.typ, o.default
+    );
+    let constant = quote! {
+        #[doc = #doc]
+        pub const #cid: #opt_type<#val_type> = #opt_type::new(#name);
+    };
+
+    let accessor = (o.scope == "global" || global_local).then(|| {
+        let fid = Ident::new(&protoc::clean_name(name), Span::call_site());
+        let doc = format!(" Get the global value of `'{name}'`.");
+        quote! {
+            #[doc = #doc]
+            pub async fn #fid(&self) -> Result<#val_type> {
+                self.get(Opt::#cid).await
+            }
+        }
+    });
+    Ok((entry, constant, accessor))
+}
+
+/// Write the generated option catalogue to stdout, for the options in `overrides::OPTIONS`. The
+/// option metadata is read from a JSON file holding the output of `nvim_get_all_options_info`, or
+/// from the `nvim` binary on the path.
+pub fn options(path: Option<PathBuf>) -> Result<()> {
+    let data = match path {
+        Some(p) => fs::read(p)?,
+        None => get_options_info()?,
+    };
+    // Sort by name, so the output is stable.
+    let info: BTreeMap<String, OptionInfo> = serde_json::from_slice(&data)?;
+
+    let mut entries = vec![];
+    let mut constants = vec![];
+    let mut accessors = vec![];
+    for o in info
+        .values()
+        .filter(|o| overrides::OPTIONS.contains(&o.name.as_str()))
+    {
+        let (entry, constant, accessor) = generate_option(o)?;
+        entries.push(entry);
+        constants.push(constant);
+        accessors.extend(accessor);
+    }
+
+    let toks = quote! {
+        //! Typed Neovim options. Each option is a constant on `Opt`, typed by its value and scope,
+        //! so that reading or writing an option with the wrong type or through the wrong kind of
+        //! handle fails at compile time. Global-local options have their own types, which can be
+        //! used both globally and through a window or buffer. Generated from
+        //! `nvim_get_all_options_info` by `protoc options`, for the options listed in protoc's
+        //! `overrides::OPTIONS`.
+        use std::marker::PhantomData;
+
+        use serde::{Serialize, de::DeserializeOwned};
+
+        use super::{NvimApi, opts};
+        use crate::error::Result;
+
+        /// The type of an option value.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum OptType {
+            /// A boolean option, e.g. `'modifiable'`
+            Boolean,
+            /// A number option, e.g. `'columns'`
+            Number,
+            /// A string option, e.g. `'filetype'`
+            String,
+        }
+
+        /// The scope of an option.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum OptScope {
+            /// A global option
+            Global,
+            /// A window-local option
+            Win,
+            /// A buffer-local option
+            Buf,
+        }
+
+        /// An option value.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub enum OptValue {
+            /// A boolean value
+            Boolean(bool),
+            /// A number value
+            Number(i64),
+            /// A string value
+            String(&'static str),
+        }
+
+        /// Metadata for an option.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+        pub struct OptInfo {
+            /// The full option name
+            pub name: &'static str,
+            /// The abbreviated name, or an empty string
+            pub shortname: &'static str,
+            /// The value type
+            pub typ: OptType,
+            /// The scope
+            pub scope: OptScope,
+            /// Whether a window or buffer can override this global option
+            pub global_local: bool,
+            /// The default value
+            pub default: OptValue,
+        }
+
+        /// Metadata for every option in this module, sorted by name.
+        pub const OPTIONS: &[OptInfo] = &[#(#entries)*];
+
+        /// A global option with a value of type `T`.
+        #[derive(Debug)]
+        pub struct GlobalOpt<T> {
+            name: &'static str,
+            _type: PhantomData<T>,
+        }
+
+        /// A window-local option with a value of type `T`.
+        #[derive(Debug)]
+        pub struct WinOpt<T> {
+            name: &'static str,
+            _type: PhantomData<T>,
+        }
+
+        /// A buffer-local option with a value of type `T`.
+        #[derive(Debug)]
+        pub struct BufOpt<T> {
+            name: &'static str,
+            _type: PhantomData<T>,
+        }
+
+        /// A global-local window option with a value of type `T`: a global value that each window
+        /// can override. Converts into both a `GlobalOpt` and a `WinOpt`.
+        #[derive(Debug)]
+        pub struct GlobalLocalWinOpt<T> {
+            name: &'static str,
+            _type: PhantomData<T>,
+        }
+
+        /// A global-local buffer option with a value of type `T`: a global value that each buffer
+        /// can override. Converts into both a `GlobalOpt` and a `BufOpt`.
+        #[derive(Debug)]
+        pub struct GlobalLocalBufOpt<T> {
+            name: &'static str,
+            _type: PhantomData<T>,
+        }
+
+        impl<T> GlobalOpt<T> {
+            const fn new(name: &'static str) -> Self {
+                Self { name, _type: PhantomData }
+            }
+
+            /// The option name.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T> WinOpt<T> {
+            const fn new(name: &'static str) -> Self {
+                Self { name, _type: PhantomData }
+            }
+
+            /// The option name.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T> BufOpt<T> {
+            const fn new(name: &'static str) -> Self {
+                Self { name, _type: PhantomData }
+            }
+
+            /// The option name.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T> GlobalLocalWinOpt<T> {
+            const fn new(name: &'static str) -> Self {
+                Self { name, _type: PhantomData }
+            }
+
+            /// The option name.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T> GlobalLocalBufOpt<T> {
+            const fn new(name: &'static str) -> Self {
+                Self { name, _type: PhantomData }
+            }
+
+            /// The option name.
+            pub fn name(&self) -> &'static str {
+                self.name
+            }
+        }
+
+        impl<T> From<GlobalLocalWinOpt<T>> for GlobalOpt<T> {
+            fn from(o: GlobalLocalWinOpt<T>) -> Self {
+                Self::new(o.name)
+            }
+        }
+
+        impl<T> From<GlobalLocalWinOpt<T>> for WinOpt<T> {
+            fn from(o: GlobalLocalWinOpt<T>) -> Self {
+                Self::new(o.name)
+            }
+        }
+
+        impl<T> From<GlobalLocalBufOpt<T>> for GlobalOpt<T> {
+            fn from(o: GlobalLocalBufOpt<T>) -> Self {
+                Self::new(o.name)
+            }
+        }
+
+        impl<T> From<GlobalLocalBufOpt<T>> for BufOpt<T> {
+            fn from(o: GlobalLocalBufOpt<T>) -> Self {
+                Self::new(o.name)
+            }
+        }
+
+        /// All Neovim options, e.g. `Opt::Modifiable`.
+        #[derive(Debug)]
+        pub struct Opt;
+
+        #[allow(non_upper_case_globals)]
+        impl Opt {
+            #(#constants)*
+        }
+
+        /// Typed access to global options, and the global values of global-local options.
+        #[derive(Debug, Clone)]
+        pub struct Options<'a> {
+            nvim: &'a NvimApi,
+        }
+
+        impl<'a> Options<'a> {
+            /// Create option accessors that call through an API handle.
+            pub fn new(nvim: &'a NvimApi) -> Self {
+                Self { nvim }
+            }
+
+            /// Get the global value of an option, like `:setglobal`.
+            pub async fn get<T: DeserializeOwned>(&self, opt: impl Into<GlobalOpt<T>>) -> Result<T> {
+                let opt = opt.into();
+                self.nvim
+                    .get_option_value(opt.name, opts::SetOptionValue::default().scope("global".into()))
+                    .await
+            }
+
+            /// Set the global value of an option, like `:setglobal`.
+            pub async fn set<T: Serialize>(
+                &self,
+                opt: impl Into<GlobalOpt<T>>,
+                value: impl Into<T>,
+            ) -> Result<()> {
+                let opt = opt.into();
+                self.nvim
+                    .set_option_value(
+                        opt.name,
+                        value.into(),
+                        opts::SetOptionValue::default().scope("global".into()),
+                    )
+                    .await
+            }
+
+            #(#accessors)*
+        }
+    };
+    print!("{}", protoc::format_with_prettyplease(toks));
+    Ok(())
+}
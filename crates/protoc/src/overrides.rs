@@ -5,8 +5,129 @@ use quote::quote;
 pub const IDENT_MAP: &[(&str, &str)] = &[("fn", "func"), ("type", "typ")];
 
 /// Skip these, because they have LuaRef parameters, that don't seem to be supported on the client
-/// yet.
-pub const SKIP_FUNCTIONS: &[&str] = &["nvim_buf_call", "nvim_win_call"];
+/// yet. The options of `nvim_set_decoration_provider` are all callbacks.
+pub const SKIP_FUNCTIONS: &[&str] = &[
+    "nvim_buf_call",
+    "nvim_set_decoration_provider",
+    "nvim_win_call",
+];
+
+/// Method names on the handle types that don't follow from the function name.
+pub const HANDLE_METHOD_NAMES: &[(&str, &str)] = &[("nvim_tabpage_list_wins", "windows")];
+
+/// Struct names for keysets whose name doesn't make a good type name.
+pub const KEYSET_NAMES: &[(&str, &str)] = &[("highlight", "SetHl"), ("option", "SetOptionValue")];
+
+/// The options that `protoc options` generates typed constants for. Neovim has a few hundred
+/// options, so we cover a selection of common ones.
+pub const OPTIONS: &[&str] = &[
+    "autoindent",
+    "autowrite",
+    "background",
+    "belloff",
+    "bufhidden",
+    "buflisted",
+    "buftype",
+    "cmdheight",
+    "colorcolumn",
+    "columns",
+    "conceallevel",
+    "confirm",
+    "cursorline",
+    "expandtab",
+    "fileencoding",
+    "fileformat",
+    "filetype",
+    "foldenable",
+    "foldmethod",
+    "hidden",
+    "hlsearch",
+    "ignorecase",
+    "incsearch",
+    "laststatus",
+    "linebreak",
+    "lines",
+    "list",
+    "modeline",
+    "modifiable",
+    "modified",
+    "mouse",
+    "number",
+    "pumheight",
+    "readonly",
+    "relativenumber",
+    "report",
+    "ruler",
+    "scrolloff",
+    "shiftwidth",
+    "showmode",
+    "showtabline",
+    "sidescrolloff",
+    "signcolumn",
+    "smartcase",
+    "softtabstop",
+    "spell",
+    "splitbelow",
+    "splitright",
+    "swapfile",
+    "tabstop",
+    "termguicolors",
+    "textwidth",
+    "timeoutlen",
+    "updatetime",
+    "winfixheight",
+    "winfixwidth",
+    "winhighlight",
+    "wrap",
+    "wrapscan",
+];
+
+/// The Vimscript functions that `protoc vimfn` generates bindings for. The full set of builtins
+/// is large, and many only make sense interactively, so we bind a selection.
+pub const VIMFN_FUNCTIONS: &[&str] = &[
+    "bufname",
+    "bufnr",
+    "executable",
+    "exists",
+    "expand",
+    "fnamemodify",
+    "getcharmod",
+    "getcharstr",
+    "getcwd",
+    "getline",
+    "getreg",
+    "has",
+    "keytrans",
+    "mode",
+    "printf",
+    "setreg",
+    "stdpath",
+    "strftime",
+    "tempname",
+    "type",
+    "win_getid",
+];
+
+/// Vimscript functions whose last annotated parameter repeats, like `printf({fmt}, {expr1} ...)`.
+/// The LuaCATS annotations only name the first of the repeated arguments.
+pub const VIMFN_VARARGS: &[&str] = &["printf"];
+
+/// Field types for keyset keys that the API types as `Object`, but which accept a narrower type.
+pub fn get_keyset_type(keyset: &str, key: &str) -> Option<TokenStream> {
+    Some(match (keyset, key) {
+        ("clear_autocmds" | "create_autocmd" | "exec_autocmds" | "get_autocmds", "group") => {
+            quote! { types::Group }
+        }
+        ("clear_autocmds" | "create_autocmd" | "exec_autocmds" | "get_autocmds", "pattern") => {
+            quote! { Vec<String> }
+        }
+        ("clear_autocmds" | "get_autocmds", "event") => quote! { Vec<String> },
+        ("create_autocmd", "callback") => quote! { String },
+        ("create_augroup", "clear") => quote! { bool },
+        ("highlight", "fg" | "bg" | "sp" | "ctermfg" | "ctermbg") => quote! { String },
+        _ => return None,
+    })
+}
 
 /// An argument override
 pub struct Arg {
@@ -27,23 +148,18 @@ pub struct Override {
 /// Get an override for a function
 pub fn get_override(name: &str) -> Option<Override> {
     Some(match name {
-        "nvim_buf_delete" => Override {
-            args: vec![
-                Arg {
-                    name: "buf".into(),
-                    typ: quote! { &Buffer },
-                },
-                Arg {
-                    name: "opts".into(),
-                    typ: quote! { opts::BufDelete },
-                },
-            ],
-            ret: None,
+        "nvim_buf_get_lines" => Override {
+            args: vec![],
+            ret: Some(quote! { Vec<NvimString> }),
+        },
+        "nvim_buf_get_text" => Override {
+            args: vec![],
+            ret: Some(quote! { Vec<NvimString> }),
         },
         "nvim_buf_set_lines" => Override {
             args: vec![Arg {
                 name: "replacement".into(),
-                typ: quote! { Vec<String> },
+                typ: quote! { Vec<NvimString> },
             }],
             ret: None,
         },
@@ -54,41 +170,22 @@ pub fn get_override(name: &str) -> Option<Override> {
         "nvim_buf_set_text" => Override {
             args: vec![Arg {
                 name: "replacement".into(),
-                typ: quote! { Vec<String> },
+                typ: quote! { Vec<NvimString> },
             }],
             ret: None,
         },
-        "nvim_clear_autocmds" => Override {
+        "nvim_create_autocmd" => Override {
             args: vec![Arg {
-                name: "opts".into(),
-                typ: quote! { opts::ClearAutocmds },
+                name: "event".into(),
+                typ: quote! { &[Event] },
             }],
             ret: None,
         },
-        "nvim_create_autocmd" => Override {
-            args: vec![
-                Arg {
-                    name: "opts".into(),
-                    typ: quote! { opts::CreateAutocmd },
-                },
-                Arg {
-                    name: "event".into(),
-                    typ: quote! { &[Event] },
-                },
-            ],
-            ret: None,
-        },
         "nvim_exec_autocmds" => Override {
-            args: vec![
-                Arg {
-                    name: "event".into(),
-                    typ: quote! { &[Event] },
-                },
-                Arg {
-                    name: "opts".into(),
-                    typ: quote! { opts::ExecAutocmds },
-                },
-            ],
+            args: vec![Arg {
+                name: "event".into(),
+                typ: quote! { &[Event] },
+            }],
             ret: None,
         },
         "nvim_get_api_info" => Override {
@@ -101,7 +198,7 @@ pub fn get_override(name: &str) -> Option<Override> {
         },
         "nvim_get_mark" => Override {
             args: vec![],
-            ret: Some(quote! { (i64, i64, i64, String) }),
+            ret: Some(quote! { (i64, i64, i64, NvimString) }),
         },
         "nvim_get_proc_children" => Override {
             args: vec![],
@@ -133,7 +230,7 @@ pub fn get_override(name: &str) -> Option<Override> {
         "nvim_put" => Override {
             args: vec![Arg {
                 name: "lines".into(),
-                typ: quote! { Vec<String> },
+                typ: quote! { Vec<NvimString> },
             }],
             ret: None,
         },
@@ -148,20 +245,6 @@ pub fn get_override(name: &str) -> Option<Override> {
             }],
             ret: None,
         },
-        "nvim_set_hl" => Override {
-            args: vec![Arg {
-                name: "val".into(),
-                typ: quote! { opts::SetHl },
-            }],
-            ret: None,
-        },
-        "nvim_set_option_value" => Override {
-            args: vec![Arg {
-                name: "opts".into(),
-                typ: quote! { opts::SetOptionValue },
-            }],
-            ret: None,
-        },
         "nvim_win_get_config" => Override {
             args: vec![],
             ret: Some(quote! { WindowConf }),
@@ -1,16 +1,26 @@
-use anyhow::Result;
-use proc_macro2::{Ident, Span, TokenStream};
+use std::path::Path;
+
+use anyhow::{Result, bail};
+use proc_macro2::{Ident, Literal, Span, TokenStream};
 use quote::quote;
 
-use crate::{api, docs, overrides};
+use crate::{api, docs, keysets, overrides};
+
+/// The API level of the oldest Neovim release we support (0.9). Functions introduced at or before
+/// this level are always available, and functions deprecated at or before it are not generated.
+pub const BASE_API_LEVEL: u32 = 11;
+
+/// The cargo features that gate functions introduced after `BASE_API_LEVEL`, by API level.
+const API_LEVEL_FEATURES: &[(u32, &str)] =
+    &[(12, "nvim-0-10"), (13, "nvim-0-11"), (14, "nvim-0-12")];
 
 /// Format code with prettyplease
-fn format_with_prettyplease(code: TokenStream) -> String {
+pub fn format_with_prettyplease(code: TokenStream) -> String {
     prettyplease::unparse(&syn::parse2(code).expect("Failed to parse token stream"))
 }
 
 /// Clean a name to be a valid identifier
-fn clean_name(name: &str) -> String {
+pub fn clean_name(name: &str) -> String {
     let mut name = name.to_string();
     for (a, b) in overrides::IDENT_MAP {
         if name == *a {
@@ -48,6 +58,15 @@ fn generate_argument(func: &str, p: &api::Parameter, meta_count: i32) -> (TokenS
                 },
                 false,
             ),
+            api::Type::KeyDict(keyset) => {
+                let typ = Ident::new(&keysets::struct_name(keyset), Span::call_site());
+                (
+                    quote! {
+                        opts::#typ
+                    },
+                    false,
+                )
+            }
             api::Type::ArrayOf { typ, .. } => {
                 let typ = mk_return_type(typ);
                 (
@@ -130,7 +149,7 @@ fn mk_return_type(t: &api::Type) -> TokenStream {
         api::Type::Array => quote! {
             Vec<Value>
         },
-        api::Type::Dict => quote! {
+        api::Type::Dict | api::Type::KeyDict(_) => quote! {
             HashMap<String, Value>
         },
         api::Type::ArrayOf { typ, .. } => {
@@ -159,8 +178,9 @@ fn mk_return_type(t: &api::Type) -> TokenStream {
         api::Type::Object => quote! {
             Value
         },
+        // Strings from Neovim may not be valid UTF-8
         api::Type::String => quote! {
-            String
+            NvimString
         },
         api::Type::Tabpage => quote! {
             TabPage
@@ -174,23 +194,28 @@ fn mk_return_type(t: &api::Type) -> TokenStream {
     }
 }
 
-/// Make an argument value
-/// Retrieves and formats the documentation for a given function name.
-/// Returns a vec of TokenStream, each representing a doc comment line.
-fn get_docs(name: &str) -> Vec<TokenStream> {
+/// Retrieves the documentation for a given function name, as a vec of lines with the common
+/// indentation and surrounding blank lines removed.
+pub fn doc_lines(name: &str) -> Vec<String> {
     let docs = docs::DOCS.iter().find(|(n, _)| *n == name).map(|(_, doc)| {
-        let lines: Vec<_> = doc.lines().map(|line| line.trim()).collect();
+        // Strip the common indentation, preserving the relative indentation of code blocks.
+        let indent = doc
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| line.len() - line.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        let lines: Vec<_> = doc
+            .lines()
+            .map(|line| line.get(indent..).unwrap_or_default().trim_end())
+            .collect();
         // Find first and last non-empty line
         let start = lines.iter().position(|line| !line.is_empty()).unwrap_or(0);
         let end = lines.iter().rposition(|line| !line.is_empty()).unwrap_or(0);
 
         // Only take the lines between start and end (inclusive)
         if start <= end {
-            lines[start..=end]
-                .iter()
-                .map(|&line| format!(" {line}"))
-                .map(|line| quote!(#[doc = #line]))
-                .collect()
+            lines[start..=end].iter().map(|l| l.to_string()).collect()
         } else {
             Vec::new()
         }
@@ -199,10 +224,66 @@ fn get_docs(name: &str) -> Vec<TokenStream> {
     docs.unwrap_or_default()
 }
 
-/// Generate a function definition
-fn generate_function(f: &api::Function) -> TokenStream {
-    // All functions have the nvim_ prefix, so we strip it.
-    let id = Ident::new(&f.name[5..], Span::call_site());
+/// Retrieves and formats the documentation for a given function name.
+/// Returns a vec of TokenStream, each representing a doc comment line.
+fn get_docs(name: &str) -> Vec<TokenStream> {
+    doc_lines(name)
+        .iter()
+        .map(|line| format!(" {line}"))
+        .map(|line| quote!(#[doc = #line]))
+        .collect()
+}
+
+/// Generate the attributes that gate a function on the Neovim version it was introduced in, and
+/// mark it deprecated if it has been deprecated.
+pub fn generate_gates(f: &api::Function) -> Result<TokenStream> {
+    let mut ret = quote! {};
+    if f.since > BASE_API_LEVEL {
+        let Some((_, feature)) = API_LEVEL_FEATURES.iter().find(|(l, _)| *l == f.since) else {
+            bail!(
+                "{} requires API level {}, which has no cargo feature",
+                f.name,
+                f.since
+            );
+        };
+        ret.extend(quote! { #[cfg(feature = #feature)] });
+    }
+    if let Some(d) = f.deprecated_since {
+        let note = format!("deprecated since API level {d}");
+        ret.extend(quote! { #[deprecated(note = #note)] });
+    }
+    Ok(ret)
+}
+
+/// The parts of a generated function signature.
+pub struct Signature {
+    /// The generic parameters, including angle brackets, or nothing
+    pub generics: TokenStream,
+    /// The where clause, or nothing
+    pub where_clause: TokenStream,
+    /// The generic parameters for a notification, which has no return value
+    pub notify_generics: TokenStream,
+    /// The where clause for a notification
+    pub notify_where_clause: TokenStream,
+    /// The arguments, as `name: type`
+    pub args: Vec<TokenStream>,
+    /// The argument names
+    pub arg_names: Vec<Ident>,
+    /// The return type, without the `Result`
+    pub ret_type: TokenStream,
+}
+
+/// Make the generic parameter list and where clause for a set of generic parameters.
+fn generic_clauses(params: &[Ident], bounds: &[TokenStream]) -> (TokenStream, TokenStream) {
+    if params.is_empty() {
+        (quote! {}, quote! {})
+    } else {
+        (quote! { <#(#params),*> }, quote! { where #(#bounds),* })
+    }
+}
+
+/// Generate the signature for an API function.
+pub fn signature(f: &api::Function) -> Signature {
     let name = &f.name;
 
     let mut args = vec![];
@@ -225,6 +306,7 @@ fn generate_function(f: &api::Function) -> TokenStream {
         generic_params.push(param.clone());
         where_bounds.push(quote! { #param: Serialize });
     }
+    let (notify_generics, notify_where_clause) = generic_clauses(&generic_params, &where_bounds);
 
     let ret_type = if let Some(ret_type) = overrides::get_return_override(name) {
         ret_type
@@ -247,23 +329,54 @@ fn generate_function(f: &api::Function) -> TokenStream {
         mk_return_type(&f.return_type)
     };
 
-    let generics = if !generic_params.is_empty() {
-        quote! { <#(#generic_params),*> }
-    } else {
-        quote! {}
-    };
-
-    let where_clause = if !where_bounds.is_empty() {
-        quote! { where #(#where_bounds),* }
-    } else {
-        quote! {}
-    };
+    let (generics, where_clause) = generic_clauses(&generic_params, &where_bounds);
 
     let arg_names: Vec<Ident> = f
         .parameters
         .iter()
         .map(|p| Ident::new(&clean_name(&p.1), Span::call_site()))
         .collect();
+
+    Signature {
+        generics,
+        where_clause,
+        notify_generics,
+        notify_where_clause,
+        args,
+        arg_names,
+        ret_type,
+    }
+}
+
+/// Prefixes, after the `nvim_` and handle prefixes, of functions that only read editor state.
+const GETTER_PREFIXES: &[&str] = &["get_", "list_", "is_"];
+
+/// Whether a function is a pure getter, which returns a value and has no side effects. Sending a
+/// getter as a notification would just discard the result, so getters get no `_notify` variant.
+fn is_getter(f: &api::Function) -> bool {
+    let name = f.name.trim_start_matches("nvim_");
+    let name = ["buf_", "win_", "tabpage_"]
+        .iter()
+        .find_map(|p| name.strip_prefix(p))
+        .unwrap_or(name);
+    !matches!(f.return_type, api::Type::Void) && GETTER_PREFIXES.iter().any(|p| name.starts_with(p))
+}
+
+/// Generate a function definition, and a `_notify` variant unless the function is a getter.
+fn generate_function(f: &api::Function) -> Result<TokenStream> {
+    // All functions have the nvim_ prefix, so we strip it.
+    let id = Ident::new(&f.name[5..], Span::call_site());
+    let name = &f.name;
+
+    let Signature {
+        generics,
+        where_clause,
+        notify_generics,
+        notify_where_clause,
+        args,
+        arg_names,
+        ret_type,
+    } = signature(f);
     let req_expr = match arg_names.as_slice() {
         [] => quote! { NO_PARAMS },
         [single] => quote! { #single },
@@ -271,71 +384,155 @@ fn generate_function(f: &api::Function) -> TokenStream {
     };
 
     let doc_lines = get_docs(&f.name);
+    let gates = generate_gates(f)?;
+    let since = Literal::u64_unsuffixed(f.since.into());
+    let notify_id = Ident::new(&format!("{id}_notify"), Span::call_site());
+    let notify_doc =
+        format!(" Send [`Self::{id}`] as a notification, without waiting for a response.");
     let fn_def = quote! {
         #(#doc_lines)*
+        #gates
         pub async fn #id #generics(&self, #(#args),*) -> Result<#ret_type>
     };
 
-    quote! {
+    let call = quote! {
         #fn_def
             #where_clause
         {
+            self.require(#name, #since)?;
             #[allow(unused_variables)]
             let req = #req_expr;
             #[allow(clippy::needless_question_mark)]
             Ok(self.rpc_call(#name, req).await?)
         }
+    };
+    if is_getter(f) {
+        return Ok(call);
     }
+
+    Ok(quote! {
+        #call
+
+        #[doc = #notify_doc]
+        #[doc = ""]
+        #[doc = " Errors are reported asynchronously through `nvim_error_event`, which plugins receive in"]
+        #[doc = " `NviPlugin::error_event`."]
+        #gates
+        pub async fn #notify_id #notify_generics(&self, #(#args),*) -> Result<()>
+            #notify_where_clause
+        {
+            self.require(#name, #since)?;
+            #[allow(unused_variables)]
+            let req = #req_expr;
+            Ok(self.rpc_notify(#name, req).await?)
+        }
+    })
 }
 
 /// Write the compiled protocol definition file to stdout.
-pub fn protoc() -> Result<()> {
-    let a = api::get_api()?;
-    let funcs: Vec<TokenStream> = a
+pub fn protoc(snapshot: Option<&Path>) -> Result<()> {
+    let a = api::get_api(snapshot)?;
+    let funcs = a
         .functions
-        .into_iter()
+        .iter()
         .filter(|f| !overrides::SKIP_FUNCTIONS.contains(&f.name.as_str()))
-        .filter(|f| f.deprecated_since.is_none())
-        .map(|f| generate_function(&f))
-        .collect();
+        .filter(|f| f.deprecated_since.is_none_or(|d| d > BASE_API_LEVEL))
+        .map(generate_function)
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    // Sort by id, so the output is stable.
+    let mut ext_types: Vec<_> = a.types.iter().collect();
+    ext_types.sort_by_key(|(_, t)| t.id);
+    let ext_types = ext_types.into_iter().map(|(name, t)| {
+        let id = Ident::new(
+            &format!("{}_EXT_TYPE", name.to_uppercase()),
+            Span::call_site(),
+        );
+        let code = Literal::i8_unsuffixed(t.id as i8);
+        let doc = format!(" The MessagePack extension type code for {name} handles");
+        quote! {
+            #[doc = #doc]
+            pub const #id: i8 = #code;
+        }
+    });
     let toks = quote!(
         #![allow(clippy::needless_question_mark)]
         #![allow(clippy::needless_borrow)]
         #![allow(clippy::doc_lazy_continuation)]
-        use std::collections::HashMap;
+        use std::{
+            collections::HashMap,
+            sync::{Arc, OnceLock},
+        };
 
         use mrpc::Value;
         use serde::{Serialize, de::DeserializeOwned};
         use tracing::trace;
 
-        use crate::error::{Result};
+        use crate::error::{Error, Result};
         use super::types::*;
         use super::opts;
 
         const NO_PARAMS: [(); 0] = [];
 
+        #(#ext_types)*
+
         #[derive(Clone, Debug)]
         /// Generated bindings for Neovim's MessagePack-RPC API.
         pub struct NvimApi {
             pub(crate) rpc_sender: mrpc::RpcSender,
+            /// The API level of the connected Neovim, once detected.
+            pub(crate) api_level: Arc<OnceLock<u64>>,
         }
 
         impl NvimApi {
-            /// Make a typed request over the MessagePack-RPC protocol.
+            /// Create an API handle for an RPC connection.
+            pub(crate) fn new(rpc_sender: mrpc::RpcSender) -> Self {
+                Self {
+                    rpc_sender,
+                    api_level: Arc::new(OnceLock::new()),
+                }
+            }
+
+            /// Query the connected Neovim for its API level. Once the level is known, calling a
+            /// function that is newer than the connected Neovim fails with `Error::Unsupported`,
+            /// rather than an opaque remote error.
+            pub async fn detect_api_level(&self) -> Result<u64> {
+                let (_, info) = self.get_api_info().await?;
+                Ok(*self.api_level.get_or_init(|| info.version.api_level))
+            }
+
+            /// Check that the connected Neovim supports a function introduced at `required_level`.
+            /// Always succeeds if the API level has not been detected.
+            pub(crate) fn require(&self, function: &str, required_level: u64) -> Result<()> {
+                match self.api_level.get() {
+                    Some(level) if *level < required_level => Err(Error::Unsupported {
+                        function: function.into(),
+                        required_level,
+                    }),
+                    _ => Ok(()),
+                }
+            }
+
+            /// Make a typed request over the MessagePack-RPC protocol. Errors record the method that
+            /// failed.
             pub async fn rpc_call<Req, Resp>(
                 &self,
                 method: &str,
                 req: Req,
-            ) -> Result<Resp, mrpc::RpcError>
+            ) -> Result<Resp>
             where
                 Req: Serialize,
                 Resp: DeserializeOwned,
             {
-                let params = mrpc::serialize_params(&req)?;
+                let params = mrpc::serialize_params(&req).map_err(|e| Error::rpc(method, e))?;
                 trace!("send request: {:?} {:?}", method, params);
-                let ret = self.rpc_sender.send_request(method, &params).await?;
+                let ret = self
+                    .rpc_sender
+                    .send_request(method, &params)
+                    .await
+                    .map_err(|e| Error::rpc(method, e))?;
                 trace!("got response for {:?}: {:?}", method, ret);
-                mrpc::deserialize_response(&ret)
+                mrpc::deserialize_response(&ret).map_err(|e| Error::rpc(method, e))
             }
 
             /// Send a typed notification over the MessagePack-RPC protocol.
@@ -343,13 +540,16 @@ pub fn protoc() -> Result<()> {
                 &self,
                 method: &str,
                 req: Req,
-            ) -> Result<(), mrpc::RpcError>
+            ) -> Result<()>
             where
                 Req: Serialize,
             {
-                let params = mrpc::serialize_params(&req)?;
+                let params = mrpc::serialize_params(&req).map_err(|e| Error::rpc(method, e))?;
                 trace!("send notification: {:?} {:?}", method, params);
-                self.rpc_sender.send_notification(method, &params).await
+                self.rpc_sender
+                    .send_notification(method, &params)
+                    .await
+                    .map_err(|e| Error::rpc(method, e))
             }
 
             /// Make a raw request over the MessagePack-RPC protocol.
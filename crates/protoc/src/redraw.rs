@@ -0,0 +1,176 @@
+use std::path::Path;
+
+use anyhow::Result;
+use proc_macro2::{Ident, Literal, Span, TokenStream};
+use quote::quote;
+
+use crate::{api, protoc};
+
+/// Convert a snake_case name to CamelCase, e.g. `grid_line` to `GridLine`.
+fn camel_case(name: &str) -> String {
+    name.split('_')
+        .map(|w| {
+            let mut c = w.chars();
+            c.next()
+                .map(|f| f.to_uppercase().chain(c).collect::<String>())
+                .unwrap_or_default()
+        })
+        .collect()
+}
+
+/// The type of a UI event field.
+fn field_type(t: &api::Type) -> TokenStream {
+    match t {
+        api::Type::Array => quote! { Vec<Value> },
+        api::Type::ArrayOf { typ, .. } => {
+            let typ = field_type(typ);
+            quote! { Vec<#typ> }
+        }
+        api::Type::Boolean => quote! { bool },
+        api::Type::Buffer => quote! { Buffer },
+        api::Type::Dict | api::Type::Dictionary | api::Type::KeyDict(_) => {
+            quote! { HashMap<String, Value> }
+        }
+        api::Type::Float => quote! { f64 },
+        api::Type::Integer => quote! { i64 },
+        api::Type::String => quote! { String },
+        api::Type::Tabpage => quote! { TabPage },
+        api::Type::Window => quote! { Window },
+        api::Type::Function | api::Type::LuaRef | api::Type::Object | api::Type::Void => {
+            quote! { Value }
+        }
+    }
+}
+
+/// Generate the enum variant and decoder match arm for a UI event.
+fn generate_event(e: &api::UIEvent) -> (TokenStream, TokenStream) {
+    let variant = Ident::new(&camel_case(&e.name), Span::call_site());
+    let name = &e.name;
+    let doc = format!(" The `{}` event.", e.signature());
+    let names: Vec<Ident> = e
+        .parameters
+        .iter()
+        .map(|p| Ident::new(&protoc::clean_name(&p.1), Span::call_site()))
+        .collect();
+    let types: Vec<TokenStream> = e.parameters.iter().map(|p| field_type(&p.0)).collect();
+    let docs: Vec<String> = e
+        .parameters
+        .iter()
+        .map(|p| format!(" `{}: {}`", p.1, p.0))
+        .collect();
+
+    if names.is_empty() {
+        return (
+            quote! {
+                #[doc = #doc]
+                #variant,
+            },
+            quote! { #name => Self::#variant, },
+        );
+    }
+    let count = Literal::usize_unsuffixed(names.len());
+    (
+        quote! {
+            #[doc = #doc]
+            #variant {
+                #(
+                    #[doc = #docs]
+                    #names: #types,
+                )*
+            },
+        },
+        quote! {
+            #name => {
+                let (#(#names,)*): (#(#types,)*) = decode_args(args, #count)?;
+                Self::#variant { #(#names),* }
+            }
+        },
+    )
+}
+
+/// Write the generated UI event definitions to stdout.
+pub fn redraw(snapshot: Option<&Path>) -> Result<()> {
+    let a = api::get_api(snapshot)?;
+    let (variants, arms): (Vec<TokenStream>, Vec<TokenStream>) =
+        a.ui_events.iter().map(generate_event).unzip();
+
+    let toks = quote! {
+        //! Typed UI events, as sent to attached UIs in `redraw` notifications. Generated from the
+        //! `ui_events` in the API definition by `protoc redraw`.
+        use std::collections::HashMap;
+
+        use mrpc::Value;
+        use serde::de::DeserializeOwned;
+
+        use super::types::{Buffer, TabPage, Window};
+        use crate::error::{Error, Result};
+
+        /// Decode the arguments of a single event. Newer versions of Neovim may append arguments
+        /// to an event, so anything past the arguments we know about is ignored.
+        fn decode_args<T: DeserializeOwned>(args: &Value, count: usize) -> Result<T> {
+            let Value::Array(args) = args else {
+                return Err(Error::Decode {
+                    msg: format!("expected event arguments to be an array, got {args}"),
+                    source: None,
+                });
+            };
+            let args = Value::Array(args.iter().take(count).cloned().collect());
+            Ok(serde_rmpv::from_value(&args)?)
+        }
+
+        /// An event sent to attached UIs.
+        #[derive(Debug, Clone, PartialEq)]
+        pub enum RedrawEvent {
+            #(#variants)*
+            /// An event this version of nvi doesn't know about.
+            Unknown {
+                /// The event name
+                name: String,
+                /// The raw event arguments
+                args: Value,
+            },
+        }
+
+        impl RedrawEvent {
+            /// Decode a single event from its name and arguments.
+            pub fn decode(name: &str, args: &Value) -> Result<Self> {
+                Ok(match name {
+                    #(#arms)*
+                    _ => Self::Unknown {
+                        name: name.into(),
+                        args: args.clone(),
+                    },
+                })
+            }
+
+            /// Decode the parameters of a `redraw` notification. Each parameter is a batch of
+            /// events of the same kind: the event name, followed by the arguments of each event.
+            pub fn parse(params: &[Value]) -> Result<Vec<Self>> {
+                let mut ret = vec![];
+                for batch in params {
+                    let Some(batch) = batch.as_array() else {
+                        return Err(Error::Decode {
+                            msg: format!("expected a redraw batch to be an array, got {batch}"),
+                            source: None,
+                        });
+                    };
+                    let Some((name, events)) = batch.split_first() else {
+                        continue;
+                    };
+                    let Some(name) = name.as_str() else {
+                        return Err(Error::Decode {
+                            msg: format!("expected a redraw event name, got {name}"),
+                            source: None,
+                        });
+                    };
+                    for args in events {
+                        ret.push(Self::decode(name, args)?);
+                    }
+                }
+                Ok(ret)
+            }
+        }
+    };
+    print!("{}", protoc::format_with_prettyplease(toks));
+    Ok(())
+}
@@ -0,0 +1,351 @@
+use std::{fs, mem, path::PathBuf};
+
+use anyhow::Result;
+use nvi::vimhelp;
+use proc_macro2::{Ident, Span, TokenStream};
+use quote::quote;
+
+use crate::{api, overrides, protoc};
+
+/// Rust keywords that can't be used as plain identifiers. Vimscript functions and parameters with
+/// these names are emitted as raw identifiers, e.g. `r#type`.
+const KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+    "extern", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro", "match",
+    "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static", "struct", "trait",
+    "try", "type", "typeof", "unsafe", "use", "virtual", "where", "while", "yield",
+];
+
+/// A parameter of a Vimscript function, from a `@param` annotation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    /// The parameter name, or `...` for varargs
+    pub name: String,
+    /// The LuaCATS type
+    pub typ: String,
+    /// Whether the parameter is optional, written `name?`
+    pub optional: bool,
+}
+
+/// A Vimscript function definition from the LuaCATS meta file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Function {
+    /// The function name
+    pub name: String,
+    /// The doc comment, in Vim help format
+    pub docs: String,
+    /// The parameters, in order
+    pub params: Vec<Param>,
+    /// The LuaCATS return type, if annotated
+    pub ret: Option<String>,
+    /// Whether the function is marked `@deprecated`
+    pub deprecated: bool,
+}
+
+/// Parse the function definitions from `vimfn.lua`. Each definition is a run of `---` comment
+/// lines - documentation, then `@param` and `@return` annotations - followed by a line like
+/// `function vim.fn.expand(string, nosuf, list) end`. Overloaded functions appear once for each
+/// definition.
+pub fn parse(text: &str) -> Vec<Function> {
+    let mut ret: Vec<Function> = vec![];
+    let mut docs = vec![];
+    let mut params = vec![];
+    let mut ret_type = None;
+    let mut deprecated = false;
+    for line in text.lines() {
+        if let Some(comment) = line.strip_prefix("---") {
+            let comment = comment.strip_prefix(' ').unwrap_or(comment);
+            if let Some(p) = comment.strip_prefix("@param ") {
+                let (name, typ) = p.trim().split_once(' ').unwrap_or((p.trim(), "any"));
+                let (name, optional) = match name.strip_suffix('?') {
+                    Some(n) => (n, true),
+                    None => (name, false),
+                };
+                params.push(Param {
+                    name: name.into(),
+                    typ: typ.trim().into(),
+                    optional,
+                });
+            } else if let Some(r) = comment.strip_prefix("@return ") {
+                // Return annotations may be followed by a name or description
+                ret_type.get_or_insert(r.split_whitespace().next().unwrap_or("any").to_string());
+            } else if comment.starts_with("@deprecated") {
+                deprecated = true;
+            } else if !comment.starts_with('@') {
+                docs.push(comment.to_string());
+            }
+            continue;
+        }
+        // Functions whose result type depends on their arguments, like `expand()`, are defined
+        // once for each signature. We keep every definition, and later definitions without docs
+        // share the docs of the first.
+        if let Some(def) = line.strip_prefix("function vim.fn.")
+            && let Some((name, _)) = def.split_once('(')
+        {
+            let mut docs = docs.join("\n").trim().to_string();
+            if docs.is_empty()
+                && let Some(f) = ret.iter().find(|f| f.name == name)
+            {
+                docs.clone_from(&f.docs);
+            }
+            ret.push(Function {
+                name: name.into(),
+                docs,
+                params: mem::take(&mut params),
+                ret: ret_type.take(),
+                deprecated,
+            });
+        }
+        docs.clear();
+        params.clear();
+        ret_type = None;
+        deprecated = false;
+    }
+    ret
+}
+
+/// Make an identifier, escaping Rust keywords.
+fn ident(name: &str) -> Ident {
+    if KEYWORDS.contains(&name) {
+        Ident::new_raw(name, Span::call_site())
+    } else {
+        Ident::new(name, Span::call_site())
+    }
+}
+
+/// Split a LuaCATS type on top-level `|` separators, dropping `nil`. Returns the alternatives, and
+/// whether `nil` was one of them.
+fn alternatives(typ: &str) -> (Vec<&str>, bool) {
+    let mut parts = vec![];
+    let mut depth = 0;
+    let mut start = 0;
+    for (i, c) in typ.char_indices() {
+        match c {
+            '(' | '<' | '{' => depth += 1,
+            ')' | '>' | '}' => depth -= 1,
+            '|' if depth == 0 => {
+                parts.push(typ[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(typ[start..].trim());
+    let nullable = parts.contains(&"nil");
+    parts.retain(|p| *p != "nil");
+    (parts, nullable)
+}
+
+/// The Rust type for a LuaCATS type, as (argument type, return type). Returns `None` for types we
+/// can't express concretely, which become generic.
+fn rust_type(typ: &str) -> Option<(TokenStream, TokenStream)> {
+    let (alts, _) = alternatives(typ.trim_end_matches('?'));
+    let is_int = |a: &str| a == "integer" || a.parse::<i64>().is_ok();
+    let is_str = |a: &str| a == "string" || a.starts_with('\'') || a.starts_with('"');
+    let is_bool = |a: &str| matches!(a, "boolean" | "true" | "false");
+    if alts.is_empty() {
+        return None;
+    }
+    if alts.iter().all(|a| is_int(a)) {
+        return Some((quote! { i64 }, quote! { i64 }));
+    }
+    if alts.iter().all(|a| is_str(a)) {
+        return Some((quote! { &str }, quote! { String }));
+    }
+    if alts.iter().all(|a| is_bool(a)) {
+        return Some((quote! { bool }, quote! { bool }));
+    }
+    match alts.as_slice() {
+        ["number"] => Some((quote! { f64 }, quote! { f64 })),
+        [a] if a.ends_with("[]") => {
+            let (_, inner) = rust_type(a.trim_end_matches("[]"))?;
+            Some((quote! { Vec<#inner> }, quote! { Vec<#inner> }))
+        }
+        _ => None,
+    }
+}
+
+/// Whether a LuaCATS type has a list alternative, e.g. `string|string[]`.
+fn has_list(typ: &str) -> bool {
+    alternatives(typ).0.iter().any(|a| a.ends_with("[]"))
+}
+
+/// The method name for each function definition, and whether it's a list variant. The first
+/// definition of an overloaded function keeps the plain name. Later definitions returning a list
+/// get a `_list` suffix, and any others are numbered.
+fn method_names(funcs: &[Function]) -> Vec<(String, bool)> {
+    let mut ret = vec![];
+    for (i, f) in funcs.iter().enumerate() {
+        let n = funcs[..i].iter().filter(|p| p.name == f.name).count();
+        ret.push(match n {
+            0 => (f.name.clone(), false),
+            _ if f.ret.as_deref().is_some_and(has_list) => (format!("{}_list", f.name), true),
+            _ => (format!("{}_{}", f.name, n + 1), false),
+        });
+    }
+    ret
+}
+
+/// Generate a method for a Vimscript function definition. For the list variants of overloaded
+/// functions, the return type is narrowed to the list alternatives.
+fn generate_function(f: &Function, method: &str, list_variant: bool) -> TokenStream {
+    let id = ident(method);
+    let name = &f.name;
+    let varargs_from = overrides::VIMFN_VARARGS
+        .contains(&f.name.as_str())
+        .then(|| f.params.len().saturating_sub(1));
+
+    let mut args = vec![];
+    let mut required = vec![];
+    let mut optional = vec![];
+    let mut varargs = false;
+    for (i, p) in f.params.iter().enumerate() {
+        if p.name == "..." || varargs_from == Some(i) {
+            args.push(quote! { rest: Vec<Value> });
+            varargs = true;
+            continue;
+        }
+        // Parameters that can only be false just select an overload, and are left to Vim's
+        // default.
+        let (alts, nullable) = alternatives(&p.typ);
+        if !alts.is_empty() && alts.iter().all(|a| *a == "false") {
+            continue;
+        }
+        let pid = ident(&protoc::clean_name(&p.name));
+        let typ = rust_type(&p.typ).map(|(t, _)| t);
+        // Once one parameter is optional, every following parameter must be too. Untyped optional
+        // parameters take a `Value`, because `None` can't be inferred for an `impl Serialize`.
+        if p.optional || nullable || !optional.is_empty() {
+            let typ = typ.unwrap_or(quote! { Value });
+            args.push(quote! { #pid: Option<#typ> });
+            optional.push(quote! { #pid.map(to_value).transpose()? });
+        } else {
+            let typ = typ.unwrap_or(quote! { impl Serialize });
+            args.push(quote! { #pid: #typ });
+            required.push(quote! { to_value(#pid)? });
+        }
+    }
+
+    let mut body = if varargs {
+        quote! {
+            let mut args = positional(vec![#(#required),*], vec![#(#optional),*]);
+            args.extend(rest);
+        }
+    } else {
+        quote! {
+            let args = positional(vec![#(#required),*], vec![#(#optional),*]);
+        }
+    };
+    let (generics, ret_type) = match f.ret.as_deref() {
+        // Functions without a result still return a value over RPC, usually 0, so we discard it.
+        None | Some("nil") => {
+            body.extend(quote! {
+                self.nvim.call_function::<Value>(#name, args).await?;
+                Ok(())
+            });
+            (quote! {}, quote! { () })
+        }
+        Some(r) => {
+            body.extend(quote! { self.nvim.call_function(#name, args).await });
+            let lists;
+            let r = if list_variant {
+                lists = alternatives(r)
+                    .0
+                    .into_iter()
+                    .filter(|a| a.ends_with("[]"))
+                    .collect::<Vec<_>>()
+                    .join("|");
+                lists.as_str()
+            } else {
+                r
+            };
+            match rust_type(r) {
+                Some((_, t)) => (quote! {}, t),
+                None => (quote! { <T: DeserializeOwned> }, quote! { T }),
+            }
+        }
+    };
+
+    let md = vimhelp::parse(&f.docs).to_markdown();
+    let doc_lines = md.lines().map(|l| format!(" {l}").trim_end().to_string());
+    let deprecated = f
+        .deprecated
+        .then(|| quote! { #[deprecated(note = "deprecated in Vimscript")] });
+
+    quote! {
+        #(#[doc = #doc_lines])*
+        #deprecated
+        pub async fn #id #generics(&self, #(#args),*) -> Result<#ret_type> {
+            #body
+        }
+    }
+}
+
+/// Write the generated Vimscript function bindings to stdout, for the functions in
+/// `overrides::VIMFN_FUNCTIONS`. If no path is given, the meta file is found in the Neovim runtime
+/// directory.
+pub fn vimfn(path: Option<PathBuf>) -> Result<()> {
+    let path = match path {
+        Some(p) => p,
+        None => api::get_runtime()?
+            .join("lua")
+            .join("vim")
+            .join("_meta")
+            .join("vimfn.lua"),
+    };
+    let defs: Vec<Function> = parse(&fs::read_to_string(&path)?)
+        .into_iter()
+        .filter(|f| overrides::VIMFN_FUNCTIONS.contains(&f.name.as_str()))
+        .collect();
+    let funcs: Vec<TokenStream> = defs
+        .iter()
+        .zip(method_names(&defs))
+        .map(|(f, (m, list))| generate_function(f, &m, list))
+        .collect();
+    let toks = quote! {
+        //! Typed bindings for Vimscript builtin functions, the functions available as `vim.fn` in
+        //! Lua. Each call goes through `nvim_call_function`. Generated from the LuaCATS
+        //! annotations in `$VIMRUNTIME/lua/vim/_meta/vimfn.lua` by `protoc vimfn`, for the
+        //! functions listed in protoc's `overrides::VIMFN_FUNCTIONS`.
+        #![allow(clippy::doc_lazy_continuation)]
+        #![allow(deprecated)]
+
+        use mrpc::Value;
+        use serde::{Serialize, de::DeserializeOwned};
+
+        use super::NvimApi;
+        use crate::error::Result;
+
+        /// Convert an argument to a MessagePack value.
+        fn to_value(v: impl Serialize) -> Result<Value> {
+            Ok(serde_rmpv::to_value(&v)?)
+        }
+
+        /// Build a positional argument list. Trailing unset optional arguments are dropped, so
+        /// that Vim applies its defaults. Unset arguments followed by set ones are passed as nil.
+        fn positional(required: Vec<Value>, optional: Vec<Option<Value>>) -> Vec<Value> {
+            let set = optional.iter().rposition(Option::is_some).map_or(0, |i| i + 1);
+            required
+                .into_iter()
+                .chain(optional.into_iter().take(set).map(|v| v.unwrap_or(Value::Nil)))
+                .collect()
+        }
+
+        /// Typed bindings for Vimscript builtin functions.
+        #[derive(Clone, Debug)]
+        pub struct VimFn<'a> {
+            nvim: &'a NvimApi,
+        }
+
+        impl<'a> VimFn<'a> {
+            /// Create bindings that call through an API handle.
+            pub fn new(nvim: &'a NvimApi) -> Self {
+                Self { nvim }
+            }
+
+            #(#funcs)*
+        }
+    };
+    print!("{}", protoc::format_with_prettyplease(toks));
+    Ok(())
+}
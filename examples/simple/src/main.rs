@@ -47,8 +47,15 @@ impl Simple {
 
     /// The `#[autocmd]` attribute macro marks a method as an autocmd handler. Autocmds are methods
     /// that are called when an event occurs in the editor. The only argument apart from client
-    /// must be an `AutocmdEvent`.
-    #[autocmd(["BufEnter", "BufLeave"], patterns=["*.rs"], group="test", nested=true)]
+    /// must be an `AutocmdEvent`. The autocmd is documented with the method's doc comment, unless
+    /// a `docs` argument is given.
+    #[autocmd(
+        ["BufEnter", "BufLeave"],
+        patterns=["*.rs"],
+        group="test",
+        nested=true,
+        docs="Count entering and leaving Rust buffers."
+    )]
     async fn on_buf_enter(&mut self, client: &Client, evt: AutocmdEvent) -> Result<()> {
         self.n += 1;
         client.info(&format!("bufenter: {evt:?}")).await
@@ -62,9 +69,14 @@ impl Simple {
 
     /// Return a `Highlights` struct that defines the highlight groups for this plugin. We break
     /// from convention somewhat because we use the `snake_case` name of our addon as a prefix. In
-    /// this case, we're defining a group called simpleNormal with a red foreground.
+    /// this case, we're defining a group called simple_Normal with a red foreground. The doc string
+    /// tells users what the group styles, so they can theme it.
     fn highlights(&self) -> Result<Highlights> {
-        Ok(Highlights::default().hl("Normal", Hl::default().fg("red")?))
+        Ok(Highlights::default().hl_doc(
+            "Normal",
+            Hl::default().fg("red")?,
+            "Text in the simple plugin's output.",
+        ))
     }
 }
 
@@ -0,0 +1,49 @@
+use nvi::{Client, demo::Demos, lua, lua_exec, test};
+use nvi_macros::{nvi_plugin, request};
+
+#[derive(Clone, Default)]
+struct TestPlugin {}
+
+#[nvi_plugin]
+impl TestPlugin {
+    #[request]
+    async fn double(&self, _: &Client, n: u64) -> u64 {
+        n * 2
+    }
+}
+
+fn demos() -> Demos {
+    let mut demos = Demos::new();
+    demos.add("double", |c| async move {
+        let v: u64 = lua!(c, "return test_plugin.double(21)").await?;
+        assert_eq!(v, 42);
+        Ok(())
+    });
+    demos.add("cwd", |c| async move {
+        c.getcwd().await?;
+        Ok(())
+    });
+    demos
+}
+
+#[tokio::test]
+async fn it_runs_demos_headless() {
+    test::run_demos(&demos(), TestPlugin::default)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn it_reports_failing_demos() {
+    let mut demos = demos();
+    demos.add("broken", |c| async move {
+        lua_exec!(c, "error('broken demo')").await?;
+        Ok(())
+    });
+    let err = test::run_demos(&demos, TestPlugin::default)
+        .await
+        .unwrap_err()
+        .to_string();
+    assert!(err.contains("broken:"));
+    assert!(!err.contains("double:"));
+}
@@ -1,13 +1,17 @@
-use std::sync::{
-    Arc,
-    atomic::{AtomicU32, Ordering},
+use std::{
+    fmt,
+    sync::{
+        Arc,
+        atomic::{AtomicU32, Ordering},
+    },
 };
 
 use nvi::{
     Client, NviPlugin,
-    error::Result,
+    error::{ErrorCode as _, Result},
+    lua,
     nvim::{opts, types::Event},
-    test,
+    serde_json, test,
 };
 use nvi_macros::*;
 use tokio::sync::broadcast;
@@ -130,3 +134,181 @@ async fn it_derives_combined_handlers() {
 
     nvit.finish().await.unwrap();
 }
+
+#[tokio::test]
+#[traced_test]
+async fn it_raises_structured_errors_in_lua() {
+    #[derive(Debug, ErrorCode)]
+    enum LookupError {
+        NotFound(#[nvi(data)] String),
+        #[nvi(code = "denied")]
+        PermissionDenied,
+    }
+
+    impl fmt::Display for LookupError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Self::NotFound(k) => write!(f, "no such key: {k}"),
+                Self::PermissionDenied => write!(f, "permission denied"),
+            }
+        }
+    }
+
+    #[derive(Clone)]
+    struct TestPlugin {}
+
+    #[nvi_plugin]
+    impl TestPlugin {
+        #[request]
+        async fn lookup(&self, _: &Client, key: String) -> Result<u32, LookupError> {
+            match key.as_str() {
+                "one" => Ok(1),
+                "secret" => Err(LookupError::PermissionDenied),
+                _ => Err(LookupError::NotFound(key)),
+            }
+        }
+    }
+
+    assert_eq!(LookupError::NotFound("x".into()).code(), "not_found");
+    assert_eq!(LookupError::PermissionDenied.code(), "denied");
+    assert_eq!(
+        LookupError::NotFound("x".into()).data(),
+        Some(serde_json::Value::from("x"))
+    );
+    assert_eq!(LookupError::PermissionDenied.data(), None);
+
+    let nvit = test::NviTest::builder()
+        .with_plugin(TestPlugin {})
+        .run()
+        .await
+        .unwrap();
+
+    let v: u32 = lua!(nvit.client, "return test_plugin.lookup('one')")
+        .await
+        .unwrap();
+    assert_eq!(v, 1);
+
+    let (ok, kind, plugin, method, message, display, data): (
+        bool,
+        String,
+        String,
+        String,
+        String,
+        String,
+        String,
+    ) = lua!(
+        nvit.client,
+        "
+            local ok, err = pcall(test_plugin.lookup, 'two')
+            return { ok, err.kind, err.plugin, err.method, err.message, tostring(err), err.data }
+            "
+    )
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert_eq!(data, "two");
+    assert_eq!(kind, "not_found");
+    assert_eq!(plugin, "test_plugin");
+    assert_eq!(method, "lookup");
+    assert_eq!(message, "no such key: two");
+    assert_eq!(display, "test_plugin.lookup: no such key: two");
+
+    let kind: String = lua!(
+        nvit.client,
+        "
+        local _, err = pcall(test_plugin.lookup, 'secret')
+        return err.kind
+        "
+    )
+    .await
+    .unwrap();
+    assert_eq!(kind, "denied");
+
+    nvit.finish().await.unwrap();
+}
+
+#[tokio::test]
+#[traced_test]
+async fn it_registers_set_log_level() {
+    #[derive(Clone)]
+    struct TestPlugin {}
+
+    #[nvi_plugin]
+    impl TestPlugin {
+        #[request]
+        async fn ping(&self, _: &Client) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    let nvit = test::NviTest::builder()
+        .with_plugin(TestPlugin {})
+        .run()
+        .await
+        .unwrap();
+
+    // Tests install their own subscriber, so the request reports that nvi logging isn't set up.
+    let (ok, kind): (bool, String) = lua!(
+        nvit.client,
+        "
+        local ok, err = pcall(test_plugin.set_log_level, 'debug')
+        return { ok, err.kind }
+        "
+    )
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert_eq!(kind, "user");
+
+    nvit.finish().await.unwrap();
+}
+
+#[tokio::test]
+#[traced_test]
+async fn it_isolates_handler_panics() {
+    #[derive(Clone)]
+    struct TestPlugin {}
+
+    #[nvi_plugin]
+    impl TestPlugin {
+        #[request]
+        async fn explode(&self, _: &Client, really: bool) -> Result<u32> {
+            if really {
+                panic!("kaboom");
+            }
+            Ok(1)
+        }
+    }
+
+    let nvit = test::NviTest::builder()
+        .with_plugin(TestPlugin {})
+        .run()
+        .await
+        .unwrap();
+
+    let (ok, kind, message): (bool, String, String) = lua!(
+        nvit.client,
+        "
+        local ok, err = pcall(test_plugin.explode, true)
+        return { ok, err.kind, err.message }
+        "
+    )
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert_eq!(kind, "panic");
+    assert!(message.contains("kaboom"), "{message}");
+
+    // The plugin keeps serving, and counts the panic
+    let v: u32 = lua!(nvit.client, "return test_plugin.explode(false)")
+        .await
+        .unwrap();
+    assert_eq!(v, 1);
+    let report: nvi::StatusReport = lua!(nvit.client, "return test_plugin.__nvi_status()")
+        .await
+        .unwrap();
+    assert_eq!(report.status, nvi::Status::Running);
+    assert_eq!(report.panics, 1);
+
+    nvit.finish().await.unwrap();
+}
@@ -0,0 +1,81 @@
+use nvi::{Client, error::Result, host::PluginHost, lua, test};
+use nvi_macros::*;
+use tracing_test::traced_test;
+
+#[derive(Clone)]
+struct Alpha {}
+
+#[nvi_plugin]
+impl Alpha {
+    #[request]
+    async fn ping(&self, _: &Client) -> Result<String> {
+        Ok("alpha".into())
+    }
+}
+
+#[derive(Clone)]
+struct Beta {}
+
+#[nvi_plugin]
+impl Beta {
+    #[request]
+    async fn ping(&self, _: &Client) -> Result<String> {
+        Ok("beta".into())
+    }
+
+    #[request]
+    async fn explode(&self, _: &Client) -> Result<()> {
+        panic!("kaboom");
+    }
+}
+
+#[tokio::test]
+#[traced_test]
+async fn it_hosts_plugins() {
+    let host = PluginHost::new("suite")
+        .with_plugin(Alpha {})
+        .unwrap()
+        .with_plugin(Beta {})
+        .unwrap();
+    let nvit = test::NviTest::builder()
+        .with_plugin(host)
+        .run()
+        .await
+        .unwrap();
+
+    // Methods with the same name are routed to their own plugin
+    let (a, b): (String, String) = lua!(nvit.client, "return { alpha.ping(), beta.ping() }")
+        .await
+        .unwrap();
+    assert_eq!((a.as_str(), b.as_str()), ("alpha", "beta"));
+
+    // A panic in one plugin is reported against it, and the others keep serving
+    let (ok, plugin): (bool, String) = lua!(
+        nvit.client,
+        "
+        local ok, err = pcall(beta.explode)
+        return { ok, err.plugin }
+        "
+    )
+    .await
+    .unwrap();
+    assert!(!ok);
+    assert_eq!(plugin, "beta");
+    let a: String = lua!(nvit.client, "return alpha.ping()").await.unwrap();
+    assert_eq!(a, "alpha");
+
+    let report: nvi::StatusReport = lua!(nvit.client, "return suite.__nvi_status()")
+        .await
+        .unwrap();
+    assert_eq!(report.status, nvi::Status::Running);
+    assert_eq!(report.plugins["alpha"].panics, 0);
+    assert_eq!(report.plugins["beta"].panics, 1);
+    assert_eq!(report.plugins["beta"].status, nvi::Status::Running);
+
+    let beta: nvi::StatusReport = lua!(nvit.client, "return beta.__nvi_status()")
+        .await
+        .unwrap();
+    assert_eq!(beta.panics, 1);
+
+    nvit.finish().await.unwrap();
+}
@@ -2,6 +2,14 @@
 #[cfg(test)]
 mod client;
 #[cfg(test)]
+mod demos;
+#[cfg(test)]
 mod derives;
 #[cfg(test)]
+mod host;
+#[cfg(test)]
 mod nvitest;
+#[cfg(test)]
+mod plugin_client;
+#[cfg(test)]
+mod text;
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use nvi::{
+    Client,
+    error::{Error, Result},
+    manifest::Manifest,
+    test,
+};
+use nvi_macros::*;
+use tracing_test::traced_test;
+
+#[derive(Clone)]
+struct Greeter {}
+
+#[nvi_plugin]
+/// Greets people.
+impl Greeter {
+    /// Say hello.
+    #[request]
+    async fn greet(&self, _: &Client, name: String) -> Result<String> {
+        Ok(format!("hello {name}"))
+    }
+
+    /// Refuse to greet.
+    #[request]
+    async fn refuse(&self, _: &Client) -> Result<()> {
+        Err(Error::User("not today".into()))
+    }
+}
+
+plugin_client!(GreeterClient, "manifests/greeter.json");
+
+#[test]
+fn it_keeps_the_manifest_current() {
+    let manifest = Manifest::new(&Greeter {}).unwrap().to_json().unwrap();
+    assert_eq!(
+        manifest,
+        include_str!("../manifests/greeter.json").trim_end(),
+        "regenerate tests/manifests/greeter.json"
+    );
+}
+
+#[tokio::test]
+#[traced_test]
+async fn it_calls_plugins_through_generated_clients() {
+    let nvit = test::NviTest::builder()
+        .with_plugin(Greeter {})
+        .run()
+        .await
+        .unwrap();
+
+    let greeter = GreeterClient::new(&nvit.client).await.unwrap();
+    assert_eq!(greeter.greet("world".into()).await.unwrap(), "hello world");
+    let e = greeter.refuse().await.unwrap_err();
+    assert_eq!(e.kind(), "user");
+    assert_eq!(e.to_string(), "greeter.refuse: not today");
+    match e.root() {
+        Error::Plugin { message, .. } => assert_eq!(message, "not today"),
+        e => panic!("unexpected error: {e:?}"),
+    }
+
+    let greeter = GreeterClient::wait(&nvit.client, Duration::from_secs(1))
+        .await
+        .unwrap();
+    assert_eq!(greeter.greet("again".into()).await.unwrap(), "hello again");
+
+    // Discovery fails for plugins that aren't loaded
+    assert!(
+        nvit.client
+            .plugin_status("missing")
+            .await
+            .unwrap()
+            .is_none()
+    );
+
+    nvit.finish().await.unwrap();
+}
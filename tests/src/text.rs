@@ -0,0 +1,60 @@
+use std::fs;
+
+use nvi::{nvim::types::NvimString, test::NviTest};
+use tracing_test::traced_test;
+
+#[tokio::test]
+#[traced_test]
+async fn it_round_trips_latin1_text() {
+    let nvit = NviTest::builder().run().await.unwrap();
+    let c = &nvit.client;
+    let buf = c.nvim.get_current_buf().await.unwrap();
+
+    // "café" and "naïve" in Latin-1, which isn't valid UTF-8
+    let lines = vec![NvimString::new(b"caf\xe9"), NvimString::new(b"na\xefve")];
+    buf.set_lines(c, 0, -1, true, lines.clone()).await.unwrap();
+    assert_eq!(buf.lines(c, 0, -1, true).await.unwrap(), lines);
+    assert_eq!(c.nvim.get_current_line().await.unwrap(), lines[0]);
+    assert!(lines[0].to_str().is_err());
+    assert_eq!(lines[0].to_string_lossy(), "caf\u{fffd}");
+
+    let text = buf.text(c, 0, 0, 0, 3, Default::default()).await.unwrap();
+    assert_eq!(text, vec!["caf"]);
+    nvit.finish().await.unwrap();
+}
+
+#[tokio::test]
+#[traced_test]
+async fn it_round_trips_binary_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("data.bin");
+    let data: Vec<u8> = (1..=255u8).filter(|b| *b != b'\n').collect();
+    fs::write(&path, [&data[..], b"\n", &data[..], b"\n"].concat()).unwrap();
+
+    let nvit = NviTest::builder().run().await.unwrap();
+    let c = &nvit.client;
+    c.nvim
+        .command(&format!("edit ++bin {}", path.display()))
+        .await
+        .unwrap();
+    let buf = c.nvim.get_current_buf().await.unwrap();
+    let lines = buf.lines(c, 0, -1, true).await.unwrap();
+    assert_eq!(lines, vec![NvimString::new(data.clone()); 2]);
+    assert_eq!(
+        c.nvim.buf_get_name(&buf).await.unwrap(),
+        path.to_str().unwrap()
+    );
+
+    // Write the lines back in reverse, and check the file is byte-for-byte what we sent
+    let mut reversed = data.clone();
+    reversed.reverse();
+    buf.set_lines(c, 0, 1, true, vec![NvimString::new(reversed.clone())])
+        .await
+        .unwrap();
+    c.nvim.command("write").await.unwrap();
+    assert_eq!(
+        fs::read(&path).unwrap(),
+        [&reversed[..], b"\n", &data[..], b"\n"].concat()
+    );
+    nvit.finish().await.unwrap();
+}